@@ -0,0 +1,561 @@
+use crate::input;
+use crate::style::styled;
+use crate::validate::{lint, Level};
+use ansi_term::Color;
+use rand::seq::SliceRandom;
+use rand::thread_rng;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{self, Write};
+use std::path::PathBuf;
+use terms::{
+    FillInTheBlankQuestion, Kind, MatchingPair, MatchingQuestion, MultipleChoiceQuestion,
+    Questions, SpellingQuestion, Status,
+};
+
+#[derive(clap::Args)]
+pub struct AddArgs {
+    /// 문제를 덧붙일 YAML 문제 파일 (없으면 새로 만듭니다)
+    file: PathBuf,
+    /// 유형을 묻지 않고 이 유형의 문제를 만듭니다
+    #[arg(long, value_name = "KIND")]
+    kind: Option<Kind>,
+    /// 챕터를 묻지 않고 이 챕터에 넣습니다
+    #[arg(long, value_name = "N")]
+    chapter: Option<u32>,
+}
+
+// 입력이 끝나거나(Ctrl-D) Ctrl-C 를 누르면 None 을 돌려 마법사를 그만둡니다
+fn ask(prompt: &str) -> Option<String> {
+    print!("{}", prompt);
+    io::stdout().flush().unwrap();
+    match input::read_line() {
+        input::Line::Text(line) => Some(line.trim().to_string()),
+        _ => None,
+    }
+}
+
+fn ask_required(prompt: &str) -> Option<String> {
+    loop {
+        let answer = ask(prompt)?;
+        if !answer.is_empty() {
+            return Some(answer);
+        }
+        println!("{}", styled(Color::Red).paint("비워 둘 수 없습니다."));
+    }
+}
+
+fn ask_yes(prompt: &str, default: bool) -> Option<bool> {
+    let answer = ask(prompt)?;
+    Some(match answer.to_lowercase().as_str() {
+        "y" | "yes" | "예" | "ㅇ" => true,
+        "n" | "no" | "아니오" | "ㄴ" => false,
+        _ => default,
+    })
+}
+
+fn ask_kind() -> Option<Kind> {
+    let kinds = &Kind::ALL[..4];
+    for (i, kind) in kinds.iter().enumerate() {
+        println!("{}. {}", i + 1, kind.label());
+    }
+    loop {
+        let answer = ask_required("유형 번호: ")?;
+        match answer.parse::<usize>() {
+            Ok(n) if (1..=kinds.len()).contains(&n) => return Some(kinds[n - 1]),
+            _ => println!(
+                "{}",
+                styled(Color::Red).paint("목록에 있는 번호를 입력하세요.")
+            ),
+        }
+    }
+}
+
+fn ask_chapter(questions: &Questions) -> Option<u32> {
+    let chapters: Vec<String> = questions
+        .chapters
+        .iter()
+        .map(|c| c.chapter.to_string())
+        .collect();
+    if !chapters.is_empty() {
+        println!("지금 있는 챕터: {}", chapters.join(", "));
+    }
+    loop {
+        match ask_required("챕터 번호: ")?.parse() {
+            Ok(chapter) => return Some(chapter),
+            Err(_) => println!("{}", styled(Color::Red).paint("숫자를 입력하세요.")),
+        }
+    }
+}
+
+fn ask_tags() -> Option<Vec<String>> {
+    let tags = ask("태그 (콤마로 구분, 없으면 Enter): ")?;
+    Some(
+        tags.split(',')
+            .map(str::trim)
+            .filter(|t| !t.is_empty())
+            .map(str::to_string)
+            .collect(),
+    )
+}
+
+// 챕터 안의 목록 이름과 그 목록에 넣을 문제 하나
+struct New {
+    key: &'static str,
+    question: serde_yaml::Value,
+}
+
+impl New {
+    fn of(key: &'static str, question: impl Serialize) -> Self {
+        Self {
+            key,
+            question: serde_yaml::to_value(question).unwrap(),
+        }
+    }
+}
+
+fn ask_question(kind: Kind) -> Option<New> {
+    Some(match kind {
+        Kind::MultipleChoice => {
+            let question = ask_required("문제: ")?;
+            let mut options = Vec::new();
+            // 최소 개수를 채운 뒤에는 빈 줄로 보기 입력을 끝냅니다
+            while options.len() < MultipleChoiceQuestion::MAX_OPTIONS {
+                let letter = (b'a' + options.len() as u8) as char;
+                if options.len() < MultipleChoiceQuestion::MIN_OPTIONS {
+                    options.push(ask_required(&format!("보기 {}: ", letter))?);
+                    continue;
+                }
+                let option = ask(&format!("보기 {} (끝내려면 Enter): ", letter))?;
+                if option.is_empty() {
+                    break;
+                }
+                options.push(option);
+            }
+            let last = (b'a' + options.len() as u8 - 1) as char;
+            let answer = loop {
+                let answer = ask_required(&format!("정답 (a~{}): ", last))?.to_ascii_lowercase();
+                match answer.chars().next() {
+                    Some(c) if answer.len() == 1 && ('a'..=last).contains(&c) => break c,
+                    _ => println!(
+                        "{}",
+                        styled(Color::Red).paint(format!("a~{} 중 하나를 입력하세요.", last))
+                    ),
+                }
+            };
+            New::of(
+                "multiple_choice",
+                MultipleChoiceQuestion {
+                    question,
+                    id: None,
+                    variants: Vec::new(),
+                    options,
+                    answer: answer.into(),
+                    tags: ask_tags()?,
+                    status: Status::Active,
+                    points: None,
+                    weight: None,
+                    source: None,
+                    page: None,
+                    explanation: None,
+                    translations: BTreeMap::new(),
+                },
+            )
+        }
+        Kind::Matching => {
+            let question = ask_required("설명 (예: 용어에 맞는 정의를 고르세요): ")?;
+            println!("짝을 입력하세요. 용어를 비워 두면 끝납니다.");
+            let mut pairs = Vec::new();
+            loop {
+                let term = ask(&format!("용어 {}: ", pairs.len() + 1))?;
+                if term.is_empty() {
+                    if pairs.len() >= 2 {
+                        break;
+                    }
+                    println!(
+                        "{}",
+                        styled(Color::Red).paint("짝이 두 개 이상 있어야 합니다.")
+                    );
+                    continue;
+                }
+                let definition = ask_required(&format!("정의 {}: ", pairs.len() + 1))?;
+                pairs.push(MatchingPair {
+                    term,
+                    definition,
+                    sentence: None,
+                    audio: None,
+                    base: None,
+                });
+            }
+            New::of(
+                "matching",
+                MatchingQuestion {
+                    question,
+                    pairs,
+                    tags: ask_tags()?,
+                    status: Status::Active,
+                    points: None,
+                    weight: None,
+                    source: None,
+                    page: None,
+                    explanation: None,
+                    translations: BTreeMap::new(),
+                    normalize: None,
+                    round: false,
+                    group: None,
+                },
+            )
+        }
+        Kind::FillInTheBlank => {
+            println!("빈칸은 밑줄 세 개 이상(___)으로 적으세요.");
+            let question = ask_required("문제: ")?;
+            let answer = ask_required("정답 (빈칸이 여러 개면 콤마로 구분): ")?;
+            New::of(
+                "fill_in_the_blanks",
+                FillInTheBlankQuestion {
+                    question,
+                    id: None,
+                    variants: Vec::new(),
+                    answer: answer.into(),
+                    tags: ask_tags()?,
+                    status: Status::Active,
+                    points: None,
+                    weight: None,
+                    source: None,
+                    page: None,
+                    explanation: None,
+                    translations: BTreeMap::new(),
+                    normalize: None,
+                },
+            )
+        }
+        Kind::Custom => return None,
+        Kind::Spelling => {
+            let question = ask_required("뜻: ")?;
+            let answer = ask_required("올바른 철자: ")?;
+            // 틀린 철자를 하나도 적지 않으면 보기 없이 철자를 직접 쓰는 문제가 됩니다
+            let mut options = Vec::new();
+            loop {
+                let wrong = ask(&format!(
+                    "틀린 철자 {} (끝내려면 Enter): ",
+                    options.len() + 1
+                ))?;
+                if wrong.is_empty() {
+                    break;
+                }
+                options.push(wrong);
+            }
+            if !options.is_empty() {
+                // 정답이 늘 같은 자리에 있지 않도록 보기 순서를 섞어 둡니다
+                options.push(answer.clone());
+                options.shuffle(&mut thread_rng());
+            }
+            New::of(
+                "spelling",
+                SpellingQuestion {
+                    question,
+                    id: None,
+                    variants: Vec::new(),
+                    options,
+                    answer: answer.into(),
+                    audio: None,
+                    tags: ask_tags()?,
+                    status: Status::Active,
+                    points: None,
+                    weight: None,
+                    source: None,
+                    page: None,
+                    explanation: None,
+                    translations: BTreeMap::new(),
+                    normalize: None,
+                },
+            )
+        }
+    })
+}
+
+pub fn run(args: AddArgs) {
+    if args.kind == Some(Kind::Custom) {
+        println!(
+            "{}",
+            styled(Color::Red).paint("기타 유형은 직접 문제 파일에 적어야 합니다.")
+        );
+        return;
+    }
+    loop {
+        let data = match fs::read_to_string(&args.file) {
+            Ok(data) => data,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => "chapters: []\n".to_string(),
+            Err(e) => {
+                println!(
+                    "{} {}",
+                    styled(Color::Red).paint("파일을 읽을 수 없습니다:"),
+                    e
+                );
+                return;
+            }
+        };
+        let questions = match Questions::parse(&data) {
+            Ok(questions) => questions,
+            Err(e) => {
+                println!(
+                    "{} {}",
+                    styled(Color::Red).paint("문제 파일에 문법 오류가 있어 덧붙일 수 없습니다:"),
+                    e
+                );
+                return;
+            }
+        };
+
+        let Some(added) = add_one(&args, &data, &questions) else {
+            println!();
+            return;
+        };
+        if !added || ask_yes("하나 더 추가할까요? (y/N) ", false) != Some(true) {
+            return;
+        }
+        println!();
+    }
+}
+
+// 문제 하나를 받아 저장합니다. 저장했으면 Some(true), 그만두면 None
+fn add_one(args: &AddArgs, data: &str, questions: &Questions) -> Option<bool> {
+    let kind = match args.kind {
+        Some(kind) => kind,
+        None => ask_kind()?,
+    };
+    let chapter = match args.chapter {
+        Some(chapter) => chapter,
+        None => ask_chapter(questions)?,
+    };
+    let new = ask_question(kind)?;
+
+    let snippet = item(&new.question, "");
+    println!("\n{}", styled(Color::Cyan).paint("추가할 문제:"));
+    print!("{}", snippet);
+
+    let question = std::slice::from_ref(&new.question);
+    let updated = match splice(data, questions, chapter, new.key, question) {
+        Some(text) => text,
+        None => {
+            // 새로 만드는 파일이면 지킬 서식이 없습니다
+            if !questions.chapters.is_empty() {
+                println!(
+                    "{}",
+                    styled(Color::Yellow)
+                        .paint("원래 서식을 유지할 수 없어 파일 전체를 다시 씁니다.")
+                );
+            }
+            rewrite(data, [(chapter, new.key, question)])
+        }
+    };
+
+    let old = lint(data);
+    let errors: Vec<_> = lint(&updated)
+        .difference(&old)
+        .filter(|d| d.level == Level::Error)
+        .cloned()
+        .collect();
+    for error in &errors {
+        println!(
+            "{} {}: {}",
+            styled(Color::Red).paint("오류"),
+            error.location,
+            error.message
+        );
+    }
+    let prompt = if errors.is_empty() {
+        "추가할까요? (Y/n) "
+    } else {
+        "오류가 있습니다. 그래도 추가할까요? (y/N) "
+    };
+    if !ask_yes(prompt, errors.is_empty())? {
+        println!("추가하지 않았습니다.");
+        return Some(false);
+    }
+
+    match fs::write(&args.file, updated) {
+        Ok(()) => {
+            println!(
+                "{}",
+                styled(Color::Green).paint(format!(
+                    "챕터 {}에 {} 문제를 추가했습니다.",
+                    chapter,
+                    kind.label()
+                ))
+            );
+            Some(true)
+        }
+        Err(e) => {
+            println!(
+                "{} {}",
+                styled(Color::Red).paint("파일을 쓸 수 없습니다:"),
+                e
+            );
+            Some(false)
+        }
+    }
+}
+
+// YAML 목록 항목 하나를 `indent` 만큼 들여 씁니다
+fn item(question: &serde_yaml::Value, indent: &str) -> String {
+    let yaml = serde_yaml::to_string(question).unwrap();
+    let mut out = String::new();
+    for (i, line) in yaml.lines().enumerate() {
+        let marker = if i == 0 { "- " } else { "  " };
+        out += &format!("{}{}{}\n", indent, marker, line);
+    }
+    out
+}
+
+pub fn indent_of(line: &str) -> usize {
+    line.len() - line.trim_start().len()
+}
+
+// 내용이 있는 줄인지 (빈 줄과 주석은 블록의 끝을 정하는 데 쓰지 않습니다)
+pub fn is_content(line: &str) -> bool {
+    let trimmed = line.trim();
+    !trimmed.is_empty() && !trimmed.starts_with('#')
+}
+
+// `start` 줄 다음부터 들여쓰기가 `indent` 이하인 첫 내용 줄 앞까지가 블록입니다.
+// 블록 끝의 빈 줄과 주석은 다음 블록의 것으로 봅니다
+fn block_end(lines: &[&str], start: usize, indent: usize) -> usize {
+    let mut end = start + 1;
+    let mut last = start + 1;
+    while end < lines.len() {
+        if is_content(lines[end]) {
+            if indent_of(lines[end]) <= indent {
+                break;
+            }
+            last = end + 1;
+        }
+        end += 1;
+    }
+    last
+}
+
+// 가능하면 원래 파일의 글자를 그대로 두고 새 문제만 끼워 넣습니다. 끼워 넣은 결과가 맞지 않으면 None
+pub fn splice(
+    data: &str,
+    questions: &Questions,
+    chapter: u32,
+    key: &str,
+    new: &[serde_yaml::Value],
+) -> Option<String> {
+    let before = count(questions, chapter, key);
+    insert(data, chapter, key, new).filter(|text| {
+        Questions::parse(text).is_ok_and(|q| count(&q, chapter, key) == before + new.len())
+    })
+}
+
+// 챕터의 `key` 목록에 있는 문제 수
+pub fn count(questions: &Questions, chapter: u32, key: &str) -> usize {
+    questions
+        .chapters
+        .iter()
+        .filter(|c| c.chapter == chapter)
+        .filter_map(|c| {
+            let chapter = serde_yaml::to_value(c).ok()?;
+            chapter.get(key)?.as_sequence().map(Vec::len)
+        })
+        .sum()
+}
+
+// 챕터의 `key` 목록 끝에 문제들을 글자 그대로 끼워 넣습니다. 끼워 넣을 곳을 찾지 못하면 None
+pub fn insert(data: &str, chapter: u32, key: &str, new: &[serde_yaml::Value]) -> Option<String> {
+    let items = |indent: &str| -> String { new.iter().map(|q| item(q, indent)).collect() };
+    let lines: Vec<&str> = data.lines().collect();
+    let is_chapter = |line: &str| {
+        line.trim_start()
+            .strip_prefix("- chapter:")
+            .is_some_and(|n| n.trim().parse() == Ok(chapter))
+    };
+    let root = lines.iter().position(|l| l.trim_end() == "chapters:")?;
+
+    let mut out: Vec<String> = lines.iter().map(|l| l.to_string()).collect();
+    let Some(start) = lines.iter().position(|l| is_chapter(l)) else {
+        // 새 챕터는 다른 챕터와 같은 들여쓰기로 파일 끝에 붙입니다
+        let dash = lines
+            .iter()
+            .find(|l| l.trim_start().starts_with("- chapter:"))
+            .map_or(2, |l| indent_of(l));
+        let pad = " ".repeat(dash);
+        let mut block = format!("{}- chapter: {}\n{}  {}:\n", pad, chapter, pad, key);
+        block += &items(&format!("{}    ", pad));
+        // 챕터 목록은 `- ` 가 들여쓰기 없이 시작할 수도 있습니다
+        let end = (root + 1..lines.len())
+            .filter(|&i| is_content(lines[i]))
+            .find(|&i| indent_of(lines[i]) == 0 && !lines[i].starts_with('-'))
+            .map_or(lines.len(), |next| {
+                (root + 1..next)
+                    .rev()
+                    .find(|&i| is_content(lines[i]))
+                    .map_or(root + 1, |i| i + 1)
+            });
+        out.insert(end, block.trim_end().to_string());
+        return Some(out.join("\n") + "\n");
+    };
+
+    let dash = indent_of(lines[start]);
+    let field = dash + 2;
+    let end = block_end(&lines, start, dash);
+    let list = (start + 1..end).find(|&i| {
+        indent_of(lines[i]) == field
+            && lines[i].trim_end() == format!("{}{}:", " ".repeat(field), key)
+    });
+    let block = match list {
+        Some(list) => {
+            let list_end = block_end(&lines, list, field);
+            // 기존 항목과 같은 들여쓰기를 씁니다 (`- ` 가 키와 같은 줄에 있는 경우도 있습니다)
+            let item_indent = lines[list + 1..list_end]
+                .iter()
+                .find(|l| l.trim_start().starts_with("- "))
+                .map_or(field + 2, |l| indent_of(l));
+            let block = items(&" ".repeat(item_indent));
+            out.insert(list_end, block.trim_end().to_string());
+            return Some(out.join("\n") + "\n");
+        }
+        None => {
+            let pad = " ".repeat(field);
+            format!("{}{}:\n{}", pad, key, items(&format!("{}  ", pad)))
+        }
+    };
+    out.insert(end, block.trim_end().to_string());
+    Some(out.join("\n") + "\n")
+}
+
+// 끼워 넣을 수 없으면 파일을 읽은 그대로 고쳐서 다시 씁니다 (주석과 서식은 사라집니다)
+pub fn rewrite<'a>(
+    data: &str,
+    new: impl IntoIterator<Item = (u32, &'a str, &'a [serde_yaml::Value])>,
+) -> String {
+    let mut questions: serde_yaml::Value = serde_yaml::from_str(data).unwrap();
+    if questions.get("chapters").is_none_or(|c| !c.is_sequence()) {
+        questions["chapters"] = serde_yaml::Value::Sequence(Vec::new());
+    }
+    let chapters = questions["chapters"].as_sequence_mut().unwrap();
+    for (chapter, key, new) in new {
+        let index = match chapters.iter().position(|c| {
+            c.get("chapter").and_then(serde_yaml::Value::as_u64) == Some(chapter as u64)
+        }) {
+            Some(index) => index,
+            None => {
+                let mut value = serde_yaml::Mapping::new();
+                value.insert("chapter".into(), chapter.into());
+                chapters.push(value.into());
+                chapters.len() - 1
+            }
+        };
+        let target = &mut chapters[index];
+        if target.get(key).is_none_or(|l| !l.is_sequence()) {
+            target[key] = serde_yaml::Value::Sequence(Vec::new());
+        }
+        target[key]
+            .as_sequence_mut()
+            .unwrap()
+            .extend(new.iter().cloned());
+    }
+    serde_yaml::to_string(&questions).unwrap()
+}
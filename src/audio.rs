@@ -0,0 +1,206 @@
+use std::io;
+use std::path::Path;
+use std::process::{Command, Stdio};
+#[cfg(feature = "record")]
+use {
+    crate::style::styled,
+    ansi_term::Color,
+    cpal::traits::{DeviceTrait, HostTrait, StreamTrait},
+    cpal::{FromSample, Sample, SampleFormat, SizedSample},
+    std::sync::{Arc, Mutex},
+    std::thread,
+    std::time::Duration,
+};
+
+// 설정에 `audio_player` 가 없을 때 차례로 찾아 쓰는 재생 프로그램. 파일 경로를 마지막 인자로 붙입니다
+const PLAYERS: &[&str] = &[
+    "afplay",
+    "paplay",
+    "aplay -q",
+    "ffplay -nodisp -autoexit -loglevel quiet",
+    "mpv --really-quiet",
+];
+
+// 녹음 파일을 끝까지 틉니다. `record` 기능을 켜면 WAV 는 직접 틀고, 그 밖에는 재생 프로그램에 맡깁니다
+pub fn play(path: &Path, player: Option<&str>) -> io::Result<()> {
+    if !path.is_file() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("녹음 파일이 없습니다: {}", path.display()),
+        ));
+    }
+    #[cfg(feature = "record")]
+    if player.is_none()
+        && path
+            .extension()
+            .is_some_and(|e| e.eq_ignore_ascii_case("wav"))
+    {
+        let (samples, rate) = read_wav(path)?;
+        return play_samples(&samples, rate);
+    }
+    external(path, player)
+}
+
+fn external(path: &Path, player: Option<&str>) -> io::Result<()> {
+    let candidates: Vec<&str> = match player {
+        Some(player) => vec![player],
+        None => PLAYERS.to_vec(),
+    };
+    for candidate in candidates {
+        let mut words = candidate.split_whitespace();
+        let Some(program) = words.next() else {
+            continue;
+        };
+        let status = Command::new(program)
+            .args(words)
+            .arg(path)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status();
+        match status {
+            Ok(status) if status.success() => return Ok(()),
+            Ok(status) => {
+                return Err(io::Error::other(format!(
+                    "{} 이(가) 녹음을 틀지 못했습니다 ({})",
+                    program, status
+                )))
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Err(io::Error::new(
+        io::ErrorKind::NotFound,
+        "녹음을 틀 프로그램이 없습니다. 설정의 audio_player 에 재생 명령을 적으세요",
+    ))
+}
+
+// 여러 채널은 평균 내어 한 채널로 읽습니다
+#[cfg(feature = "record")]
+fn read_wav(path: &Path) -> io::Result<(Vec<f32>, u32)> {
+    let reader = hound::WavReader::open(path).map_err(io::Error::other)?;
+    let spec = reader.spec();
+    let samples: Result<Vec<f32>, _> = match spec.sample_format {
+        hound::SampleFormat::Float => reader.into_samples::<f32>().collect(),
+        hound::SampleFormat::Int => {
+            let scale = (1i64 << (spec.bits_per_sample.max(1) - 1)) as f32;
+            reader
+                .into_samples::<i32>()
+                .map(|s| s.map(|s| s as f32 / scale))
+                .collect()
+        }
+    };
+    let samples = samples.map_err(io::Error::other)?;
+    let mono = samples
+        .chunks(spec.channels.max(1) as usize)
+        .map(|frame| frame.iter().sum::<f32>() / frame.len() as f32)
+        .collect();
+    Ok((mono, spec.sample_rate))
+}
+
+#[cfg(feature = "record")]
+pub fn play_samples(samples: &[f32], rate: u32) -> io::Result<()> {
+    let device = cpal::default_host()
+        .default_output_device()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "소리를 낼 장치가 없습니다"))?;
+    let supported = device.default_output_config().map_err(io::Error::other)?;
+    let config = supported.config();
+    // 장치의 표본 빈도에 맞춰 가장 가까운 표본을 골라 늘이거나 줄입니다
+    let (from, to) = (rate.max(1) as u64, config.sample_rate.0.max(1) as u64);
+    let len = samples.len() as u64 * to / from;
+    let resampled: Vec<f32> = (0..len)
+        .map(|i| samples[(i * from / to) as usize])
+        .collect();
+    let stream = match supported.sample_format() {
+        SampleFormat::F32 => output::<f32>(&device, &config, resampled),
+        SampleFormat::I16 => output::<i16>(&device, &config, resampled),
+        SampleFormat::U16 => output::<u16>(&device, &config, resampled),
+        format => Err(io::Error::other(format!(
+            "이 장치의 표본 형식({})은 틀 수 없습니다",
+            format
+        ))),
+    }?;
+    stream.play().map_err(io::Error::other)?;
+    // 장치의 버퍼가 다 비워지도록 조금 더 기다립니다
+    thread::sleep(Duration::from_secs_f64(len as f64 / to as f64) + Duration::from_millis(200));
+    Ok(())
+}
+
+#[cfg(feature = "record")]
+fn output<T: SizedSample + FromSample<f32>>(
+    device: &cpal::Device,
+    config: &cpal::StreamConfig,
+    samples: Vec<f32>,
+) -> io::Result<cpal::Stream> {
+    let channels = config.channels.max(1) as usize;
+    let mut samples = samples.into_iter();
+    device
+        .build_output_stream(
+            config,
+            move |data: &mut [T], _| {
+                for frame in data.chunks_mut(channels) {
+                    frame.fill(T::from_sample(samples.next().unwrap_or(0.0)));
+                }
+            },
+            stream_error,
+            None,
+        )
+        .map_err(io::Error::other)
+}
+
+// 기본 마이크로 `secs` 초 동안 녹음해 한 채널 표본과 표본 빈도를 돌려줍니다
+#[cfg(feature = "record")]
+pub fn record(secs: f64) -> io::Result<(Vec<f32>, u32)> {
+    let device = cpal::default_host()
+        .default_input_device()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "녹음할 마이크가 없습니다"))?;
+    let supported = device.default_input_config().map_err(io::Error::other)?;
+    let config = supported.config();
+    let recorded = Arc::new(Mutex::new(Vec::new()));
+    let stream = match supported.sample_format() {
+        SampleFormat::F32 => input::<f32>(&device, &config, recorded.clone()),
+        SampleFormat::I16 => input::<i16>(&device, &config, recorded.clone()),
+        SampleFormat::U16 => input::<u16>(&device, &config, recorded.clone()),
+        format => Err(io::Error::other(format!(
+            "이 마이크의 표본 형식({})은 녹음할 수 없습니다",
+            format
+        ))),
+    }?;
+    stream.play().map_err(io::Error::other)?;
+    thread::sleep(Duration::from_secs_f64(secs.max(0.0)));
+    drop(stream);
+    let samples = std::mem::take(&mut *recorded.lock().unwrap());
+    Ok((samples, config.sample_rate.0))
+}
+
+#[cfg(feature = "record")]
+fn input<T: SizedSample>(
+    device: &cpal::Device,
+    config: &cpal::StreamConfig,
+    recorded: Arc<Mutex<Vec<f32>>>,
+) -> io::Result<cpal::Stream>
+where
+    f32: FromSample<T>,
+{
+    let channels = config.channels.max(1) as usize;
+    device
+        .build_input_stream(
+            config,
+            move |data: &[T], _| {
+                let mut recorded = recorded.lock().unwrap();
+                for frame in data.chunks(channels) {
+                    let sum: f32 = frame.iter().map(|&s| f32::from_sample(s)).sum();
+                    recorded.push(sum / frame.len() as f32);
+                }
+            },
+            stream_error,
+            None,
+        )
+        .map_err(io::Error::other)
+}
+
+#[cfg(feature = "record")]
+fn stream_error(e: cpal::StreamError) {
+    println!("{} {}", styled(Color::Red).paint("소리 장치 오류:"), e);
+}
@@ -0,0 +1,213 @@
+//! 문제가 아주 많은 문제 파일을 고른 챕터만 읽습니다.
+
+use crate::error::ParseError;
+use crate::normalize::Normalize;
+use crate::question::{Chapter, Kind};
+use serde::de::{DeserializeSeed, IgnoredAny, MapAccess, SeqAccess, Visitor};
+use serde::{Deserialize, Deserializer};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::ops::Range;
+
+#[derive(Deserialize)]
+struct Number {
+    chapter: u32,
+}
+
+// 챕터 안의 문제는 읽지 않고 건너뜁니다
+#[derive(Deserialize)]
+struct Index {
+    #[serde(default)]
+    points: BTreeMap<Kind, f64>,
+    #[serde(default)]
+    normalize: Option<Normalize>,
+    chapters: Vec<Number>,
+}
+
+// 처음에는 챕터 번호와 파일 전체의 설정만 훑어 두고, 문제는 [`Bank::load`] 로 고른 챕터만 만듭니다.
+// 고르지 않은 챕터는 문법만 확인하므로 틀린 곳이 있어도 그 챕터를 고를 때에야 알려줍니다
+pub struct Bank {
+    yaml: String,
+    pub points: BTreeMap<Kind, f64>,
+    pub normalize: Option<Normalize>,
+    chapters: Vec<u32>,
+}
+
+impl Bank {
+    pub fn parse(yaml: String) -> Result<Self, ParseError> {
+        let index: Index = match serde_yaml::from_str(&yaml) {
+            Ok(index) => index,
+            Err(_) => serde_path_to_error::deserialize(serde_yaml::Deserializer::from_str(&yaml))
+                .map_err(|e| ParseError::new(&yaml, e))?,
+        };
+        Ok(Self {
+            points: index.points,
+            normalize: index.normalize,
+            chapters: index.chapters.into_iter().map(|c| c.chapter).collect(),
+            yaml,
+        })
+    }
+
+    // 파일에 적힌 순서대로의 챕터 번호
+    pub fn chapters(&self) -> &[u32] {
+        &self.chapters
+    }
+
+    pub fn load(&self, selected: impl Fn(u32) -> bool) -> Result<Vec<Chapter>, ParseError> {
+        let wanted: Vec<bool> = self.chapters.iter().map(|&c| selected(c)).collect();
+        if !wanted.contains(&true) {
+            return Ok(Vec::new());
+        }
+        if let Some(chapters) = self.load_spans(&wanted) {
+            return Ok(chapters);
+        }
+        // 틀린 곳의 경로는 틀렸을 때만 다시 읽으며 찾습니다
+        if let Ok(chapters) =
+            Root(&wanted).deserialize(serde_yaml::Deserializer::from_str(&self.yaml))
+        {
+            return Ok(chapters);
+        }
+        let mut track = serde_path_to_error::Track::new();
+        let deserializer = serde_path_to_error::Deserializer::new(
+            serde_yaml::Deserializer::from_str(&self.yaml),
+            &mut track,
+        );
+        Root(&wanted).deserialize(deserializer).map_err(|e| {
+            ParseError::new(&self.yaml, serde_path_to_error::Error::new(track.path(), e))
+        })
+    }
+
+    // 고른 챕터의 줄만 잘라 읽습니다. 자르기 어려운 모양이거나 틀린 곳이 있으면 `None` 을 돌려주고 파일 전체를 다시 읽습니다
+    fn load_spans(&self, wanted: &[bool]) -> Option<Vec<Chapter>> {
+        let spans = spans(&self.yaml)?;
+        if spans.len() != self.chapters.len() {
+            return None;
+        }
+        let mut chapters = Vec::new();
+        for ((span, &wanted), &number) in spans.into_iter().zip(wanted).zip(&self.chapters) {
+            if !wanted {
+                continue;
+            }
+            let mut parsed: Vec<Chapter> = serde_yaml::from_str(&self.yaml[span]).ok()?;
+            match parsed.pop() {
+                Some(chapter) if parsed.is_empty() && chapter.chapter == number => {
+                    chapters.push(chapter)
+                }
+                _ => return None,
+            }
+        }
+        Some(chapters)
+    }
+}
+
+// 맨 위의 `chapters:` 아래에 블록 형태로 적힌 챕터마다 차지하는 범위.
+// 같은 들여쓰기의 `- ` 로 한 챕터가 시작하고, 그보다 덜 들여 쓴 줄이나 다른 키에서 목록이 끝납니다
+fn spans(yaml: &str) -> Option<Vec<Range<usize>>> {
+    let mut lines = yaml.split_inclusive('\n').scan(0, |offset, line| {
+        let start = *offset;
+        *offset += line.len();
+        Some((start, line))
+    });
+    lines.find(|(_, line)| line.trim_end() == "chapters:")?;
+    let mut indent = None;
+    let mut starts = Vec::new();
+    let mut end = yaml.len();
+    for (start, line) in lines {
+        let content = line.trim_start_matches(' ');
+        if content.trim().is_empty() || content.starts_with('#') {
+            continue;
+        }
+        let depth = line.len() - content.len();
+        let item = content == "-"
+            || content.starts_with("- ")
+            || content.starts_with("-\n")
+            || content.starts_with("-\r");
+        match indent {
+            None if item => indent = Some(depth),
+            None => return None,
+            Some(indent) if depth > indent => continue,
+            Some(indent) if depth == indent && item => {}
+            Some(_) => {
+                end = start;
+                break;
+            }
+        }
+        starts.push(start);
+    }
+    let ends = starts.iter().skip(1).copied().chain([end]);
+    Some(
+        starts
+            .iter()
+            .copied()
+            .zip(ends)
+            .map(|(start, end)| start..end)
+            .collect(),
+    )
+}
+
+// 맨 위에서는 `chapters` 만 읽습니다
+struct Root<'a>(&'a [bool]);
+
+impl<'de> DeserializeSeed<'de> for Root<'_> {
+    type Value = Vec<Chapter>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de> Visitor<'de> for Root<'_> {
+    type Value = Vec<Chapter>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("문제 파일")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut chapters = Vec::new();
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                "chapters" => chapters = map.next_value_seed(Chapters(self.0))?,
+                _ => {
+                    map.next_value::<IgnoredAny>()?;
+                }
+            }
+        }
+        Ok(chapters)
+    }
+}
+
+// 몇 번째 챕터를 읽을지는 처음 훑을 때 적어 둔 순서로 정합니다
+struct Chapters<'a>(&'a [bool]);
+
+impl<'de> DeserializeSeed<'de> for Chapters<'_> {
+    type Value = Vec<Chapter>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de> Visitor<'de> for Chapters<'_> {
+    type Value = Vec<Chapter>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("챕터 목록")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut chapters = Vec::new();
+        for &wanted in self.0 {
+            if wanted {
+                match seq.next_element::<Chapter>()? {
+                    Some(chapter) => chapters.push(chapter),
+                    None => break,
+                }
+            } else if seq.next_element::<IgnoredAny>()?.is_none() {
+                break;
+            }
+        }
+        while seq.next_element::<IgnoredAny>()?.is_some() {}
+        Ok(chapters)
+    }
+}
@@ -0,0 +1,352 @@
+use crate::history::Answer;
+use crate::stats::pad;
+use crate::style::styled;
+use crate::{format_duration, frequency, parse_duration, run_quiz, ChapterSet, QuizArgs, Source};
+use ansi_term::Color;
+use rand::rngs::StdRng;
+use rand::{thread_rng, Rng, SeedableRng};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use terms::score::{points, GradeBoundary, Grading, Score};
+use terms::{weighted_shuffle, BuiltQuestions, Kind};
+
+#[derive(clap::Args)]
+pub struct ExamArgs {
+    /// 시험의 부분, 문제 수, 배점, 시간을 적은 설계 파일 (TOML, `.yaml` 이면 YAML)
+    #[arg(long, value_name = "FILE")]
+    blueprint: PathBuf,
+    /// 내장된 문제 대신 사용할 YAML 문제 파일
+    #[arg(long, value_name = "FILE")]
+    bank: Option<PathBuf>,
+    /// YAML 대신 `terms db import`로 만든 SQLite 데이터베이스에서 문제를 읽습니다
+    #[cfg(feature = "sqlite")]
+    #[arg(long, value_name = "FILE", conflicts_with = "bank")]
+    db: Option<PathBuf>,
+    /// 문제를 고르는 시드. 같은 시드면 같은 시험지가 나옵니다
+    #[arg(long, value_name = "N")]
+    seed: Option<u64>,
+    /// 보기가 있는 문제를 전체 화면에서 j/k 등으로 골라 답합니다
+    #[arg(long)]
+    tui: bool,
+    /// 답마다 확정을 받지 않고, 끝난 뒤 답안지를 보여주지 않습니다
+    #[arg(long)]
+    no_confirm: bool,
+}
+
+// 실제 시험의 형식. 부분마다 어떤 문제를 몇 개, 만점 중 얼마의 배점으로 낼지 적습니다
+#[derive(Deserialize)]
+pub struct Blueprint {
+    #[serde(default)]
+    title: Option<String>,
+    // 실제 시험의 만점. 점수와 성적을 이 척도로 매깁니다
+    #[serde(default = "default_total")]
+    total: f64,
+    // 전체 문제 수. 문제 수를 적지 않은 부분은 몫(`share`)만큼 나눠 갖습니다
+    #[serde(default)]
+    count: Option<usize>,
+    #[serde(default)]
+    time_limit: Option<String>,
+    #[serde(default)]
+    negative_marking: Option<f64>,
+    // 합격 점수와 성적 구간은 만점 기준 점수로 적습니다
+    #[serde(default)]
+    pass: Option<f64>,
+    #[serde(default)]
+    grades: Vec<GradeBoundary>,
+    #[serde(rename = "section")]
+    sections: Vec<Section>,
+    // 고른 문제의 ID 마다 (부분, 배점)
+    #[serde(skip)]
+    placed: HashMap<String, (usize, f64)>,
+}
+
+#[derive(Deserialize)]
+struct Section {
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    kind: Option<Kind>,
+    // "1-5" 처럼 `--chapters` 와 같은 모양
+    #[serde(default)]
+    chapters: Option<String>,
+    #[serde(default)]
+    count: Option<usize>,
+    // 만점 중 이 부분의 몫 (%). 적지 않은 부분끼리 남은 몫을 문제 수에 비례해 나눕니다
+    #[serde(default)]
+    share: Option<f64>,
+    #[serde(skip)]
+    range: Option<ChapterSet>,
+    #[serde(skip)]
+    marks: f64,
+}
+
+fn default_total() -> f64 {
+    100.0
+}
+
+impl Section {
+    fn label(&self, index: usize) -> String {
+        match (&self.name, self.kind) {
+            (Some(name), _) => name.clone(),
+            (None, Some(kind)) => kind.label().to_string(),
+            (None, None) => format!("{}부", index + 1),
+        }
+    }
+
+    fn fits(&self, (question, chapter): &(Box<dyn terms::Askable>, u32)) -> bool {
+        self.kind.is_none_or(|kind| question.kind() == kind)
+            && self
+                .range
+                .as_ref()
+                .is_none_or(|ChapterSet(chapters)| chapters.contains(chapter))
+    }
+}
+
+impl Blueprint {
+    fn load(path: &Path) -> Result<Self, String> {
+        let data = fs::read_to_string(path).map_err(|e| e.to_string())?;
+        let yaml = path.extension().is_some_and(|e| e == "yaml" || e == "yml");
+        let mut blueprint: Blueprint = if yaml {
+            serde_yaml::from_str(&data).map_err(|e| e.to_string())?
+        } else {
+            toml::from_str(&data).map_err(|e| e.to_string())?
+        };
+        blueprint.resolve()?;
+        Ok(blueprint)
+    }
+
+    // 부분마다 문제 수와 문제 하나의 배점을 정합니다
+    fn resolve(&mut self) -> Result<(), String> {
+        if self.sections.is_empty() {
+            return Err("부분(`[[section]]`)이 하나도 없습니다".to_string());
+        }
+        if self.total <= 0.0 {
+            return Err("만점(`total`)은 0보다 커야 합니다".to_string());
+        }
+        for (i, section) in self.sections.iter_mut().enumerate() {
+            let label = section.label(i);
+            if section.count.is_none() {
+                let (Some(share), Some(count)) = (section.share, self.count) else {
+                    return Err(format!(
+                        "{}: 문제 수(`count`)나, 몫(`share`)과 전체 문제 수를 적으세요",
+                        label
+                    ));
+                };
+                section.count = Some((share / 100.0 * count as f64).round() as usize);
+            }
+            if section.count == Some(0) {
+                return Err(format!("{}: 문제 수가 0입니다", label));
+            }
+            if let Some(chapters) = &section.chapters {
+                section.range = Some(chapters.parse().map_err(|e| format!("{}: {}", label, e))?);
+            }
+        }
+        let shared: f64 = self.sections.iter().filter_map(|s| s.share).sum();
+        let rest: usize = self
+            .sections
+            .iter()
+            .filter(|s| s.share.is_none())
+            .filter_map(|s| s.count)
+            .sum();
+        if shared > 100.0 + 1e-9 || (rest == 0 && (shared - 100.0).abs() > 1e-9) {
+            return Err(format!(
+                "부분의 몫을 더하면 100% 가 되어야 합니다 (지금 {}%)",
+                points(shared)
+            ));
+        }
+        if rest > 0 && shared >= 100.0 {
+            return Err("몫을 적지 않은 부분에 남은 배점이 없습니다".to_string());
+        }
+        let total = self.total;
+        for section in &mut self.sections {
+            let count = section.count.unwrap_or(1) as f64;
+            section.marks = match section.share {
+                Some(share) => total * share / 100.0 / count,
+                None => total * (100.0 - shared) / 100.0 / rest as f64,
+            };
+        }
+        Ok(())
+    }
+
+    fn time_limit(&self) -> Result<Option<Duration>, String> {
+        self.time_limit.as_deref().map(parse_duration).transpose()
+    }
+
+    // 모든 부분이 챕터를 정했으면 그 챕터만 읽습니다
+    fn chapters(&self) -> Option<ChapterSet> {
+        let mut chapters = Vec::new();
+        for section in &self.sections {
+            chapters.extend(&section.range.as_ref()?.0);
+        }
+        chapters.sort_unstable();
+        chapters.dedup();
+        Some(ChapterSet(chapters))
+    }
+
+    // 부분의 차례대로, 부분 안에서는 섞어서 문제를 고릅니다. 한 문제는 한 부분에만 냅니다
+    pub fn assemble(
+        &mut self,
+        questions: BuiltQuestions,
+        seed: u64,
+    ) -> Result<BuiltQuestions, String> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut pool: Vec<_> = questions.into_iter().map(Some).collect();
+        let mut arranged = Vec::new();
+        let mut short = Vec::new();
+        for (i, section) in self.sections.iter().enumerate() {
+            let mut fits: Vec<usize> = (0..pool.len())
+                .filter(|&j| pool[j].as_ref().is_some_and(|q| section.fits(q)))
+                .collect();
+            weighted_shuffle(
+                &mut fits,
+                |&j| pool[j].as_ref().map_or(0.0, frequency),
+                &mut rng,
+            );
+            let count = section.count.unwrap_or_default();
+            if fits.len() < count {
+                short.push(format!(
+                    "{} {}개 중 {}개",
+                    section.label(i),
+                    count,
+                    fits.len()
+                ));
+                continue;
+            }
+            for j in fits.into_iter().take(count) {
+                let question = pool[j].take().unwrap();
+                self.placed.insert(question.0.id(), (i, section.marks));
+                arranged.push(question);
+            }
+        }
+        match short.is_empty() {
+            true => Ok(arranged),
+            false => Err(short.join(", ")),
+        }
+    }
+
+    pub fn marks(&self, id: &str) -> Option<f64> {
+        self.placed.get(id).map(|(_, marks)| *marks)
+    }
+
+    // 건너뛰거나 그만둬서 답하지 않은 문제도 만점에 넣어 실제 시험의 척도로 셉니다
+    pub fn scale(&self, score: Score) -> Score {
+        Score {
+            possible: self.total,
+            ..score
+        }
+    }
+
+    // 설계 파일에 합격 점수나 성적 구간이 있으면 설정 대신 씁니다
+    pub fn grading(&self) -> Option<Grading> {
+        if self.pass.is_none() && self.grades.is_empty() {
+            return None;
+        }
+        let percent = |value: f64| value / self.total * 100.0;
+        Some(Grading {
+            grades: self
+                .grades
+                .iter()
+                .map(|b| GradeBoundary {
+                    min: percent(b.min),
+                    grade: b.grade.clone(),
+                })
+                .collect(),
+            pass_mark: self.pass.map(percent),
+        })
+    }
+
+    pub fn announce(&self) {
+        let sections: Vec<String> = self
+            .sections
+            .iter()
+            .enumerate()
+            .map(|(i, s)| {
+                let count = s.count.unwrap_or_default();
+                format!(
+                    "{} {}문제({}점)",
+                    s.label(i),
+                    count,
+                    points(s.marks * count as f64)
+                )
+            })
+            .collect();
+        println!(
+            "{} {}",
+            styled(Color::Yellow.bold()).paint(self.title.as_deref().unwrap_or("모의시험")),
+            sections.join(", ")
+        );
+        let mut rules = vec![format!("만점 {}점", points(self.total))];
+        if let Ok(Some(limit)) = self.time_limit() {
+            rules.push(format!("제한 시간 {}", format_duration(limit)));
+        }
+        if let Some(fraction) = self.negative_marking {
+            rules.push(format!("오답은 배점의 {}% 감점", points(fraction * 100.0)));
+        }
+        println!("{}\n", rules.join(", "));
+    }
+
+    pub fn report(&self, answers: &[Answer]) {
+        println!("{}", styled(Color::Yellow.bold()).paint("부분별 점수"));
+        println!("{}{}점수", pad("부분", 16), pad("맞힘", 10));
+        for (i, section) in self.sections.iter().enumerate() {
+            let answered: Vec<&Answer> = answers
+                .iter()
+                .filter(|a| self.placed.get(&a.id).is_some_and(|(s, _)| *s == i))
+                .collect();
+            let count = section.count.unwrap_or_default();
+            let earned: f64 = answered
+                .iter()
+                .filter_map(|a| a.score)
+                .map(|s| s.earned)
+                .sum();
+            let correct = answered.iter().filter(|a| a.correct).count();
+            println!(
+                "{}{}{} / {}점",
+                pad(&section.label(i), 16),
+                pad(&format!("{}/{}", correct, count), 10),
+                points(earned),
+                points(section.marks * count as f64)
+            );
+        }
+        println!();
+    }
+}
+
+pub fn run(args: ExamArgs) {
+    let blueprint = Blueprint::load(&args.blueprint).unwrap_or_else(|e| {
+        println!(
+            "{} {}",
+            styled(Color::Red).paint("시험 설계 파일을 읽을 수 없습니다:"),
+            e
+        );
+        std::process::exit(1)
+    });
+    let time_limit = blueprint.time_limit().unwrap_or_else(|e| {
+        println!(
+            "{} {}",
+            styled(Color::Red).paint("제한 시간이 잘못되었습니다:"),
+            e
+        );
+        std::process::exit(1)
+    });
+    let mut quiz = QuizArgs {
+        bank: args.bank,
+        #[cfg(feature = "sqlite")]
+        db: args.db,
+        exam: true,
+        negative_marking: blueprint.negative_marking,
+        time_limit,
+        tui: args.tui,
+        no_confirm: args.no_confirm,
+        seed: Some(args.seed.unwrap_or_else(|| thread_rng().gen())),
+        ..Default::default()
+    };
+    quiz.chapters = blueprint
+        .chapters()
+        .or_else(|| Some(ChapterSet(Source::open(&quiz).chapters())));
+    quiz.blueprint = Some(Box::new(blueprint));
+    run_quiz(quiz);
+}
@@ -0,0 +1,157 @@
+use crate::style::styled;
+use crate::{load_questions, ChapterSet};
+use ansi_term::Color;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::PathBuf;
+use terms::{Filter, Kind};
+
+#[derive(clap::Args)]
+pub struct PrintCardsArgs {
+    /// 내장된 문제 대신 사용할 YAML 문제 파일
+    #[arg(long, value_name = "FILE")]
+    bank: Option<PathBuf>,
+    /// 이 챕터들의 짝짓기 문제만 카드로 만듭니다 (예: 1-3,5)
+    #[arg(long)]
+    chapters: Option<ChapterSet>,
+    /// 이 태그가 붙은 문제만 카드로 만듭니다 (여러 번 지정 가능)
+    #[arg(long = "tag", value_name = "TAG")]
+    tags: Vec<String>,
+    /// 인쇄용 HTML 파일을 쓸 곳
+    #[arg(long, short, value_name = "FILE", default_value = "cards.html")]
+    output: PathBuf,
+    /// 한 쪽에 넣을 카드 수
+    #[arg(long, value_name = "N", default_value_t = 8, value_parser = clap::value_parser!(u8).range(1..=40))]
+    per_page: u8,
+    /// 한 줄에 놓을 카드 수
+    #[arg(long, value_name = "N", default_value_t = 2, value_parser = clap::value_parser!(u8).range(1..=6))]
+    columns: u8,
+}
+
+struct Card {
+    term: String,
+    definition: String,
+    label: String,
+    reference: Option<String>,
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+const STYLE: &str = "
+@page { size: A4; margin: 10mm; }
+body { margin: 0; font-family: sans-serif; }
+.page { display: grid; height: 277mm; break-after: page; }
+.card { border: 1px dashed #999; padding: 6mm; display: flex; flex-direction: column;
+        justify-content: center; text-align: center; position: relative; overflow: hidden; }
+.front .text { font-size: 20pt; font-weight: bold; }
+.back .text { font-size: 12pt; }
+.label, .reference { position: absolute; left: 3mm; right: 3mm; font-size: 8pt; color: #666; }
+.label { top: 2mm; }
+.reference { bottom: 2mm; }
+@media screen { .page { border-bottom: 4px solid #ccc; } }
+";
+
+// 뒷면은 긴 쪽으로 뒤집어 양면 인쇄할 때 앞면과 겹치도록 줄마다 좌우를 바꿔 놓습니다
+fn render(cards: &[Card], per_page: usize, columns: usize) -> String {
+    let rows = per_page.div_ceil(columns);
+    let mut html = format!(
+        "<!DOCTYPE html>\n<html lang=\"ko\">\n<head>\n<meta charset=\"utf-8\">\n<title>terms 카드</title>\n<style>{}</style>\n</head>\n<body>\n",
+        STYLE
+    );
+    let grid = format!(
+        "grid-template-columns: repeat({}, 1fr); grid-template-rows: repeat({}, 1fr);",
+        columns, rows
+    );
+    for page in cards.chunks(per_page) {
+        html += &format!("<div class=\"page\" style=\"{}\">\n", grid);
+        for card in page {
+            let _ = writeln!(
+                html,
+                "<div class=\"card front\"><div class=\"label\">{}</div><div class=\"text\">{}</div></div>",
+                escape(&card.label),
+                escape(&card.term)
+            );
+        }
+        html += "</div>\n";
+
+        html += &format!("<div class=\"page\" style=\"{}\">\n", grid);
+        for row in 0..rows {
+            for column in (0..columns).rev() {
+                let Some(card) = page.get(row * columns + column) else {
+                    html += "<div></div>\n";
+                    continue;
+                };
+                let reference = card
+                    .reference
+                    .as_ref()
+                    .map(|r| format!("<div class=\"reference\">{}</div>", escape(r)))
+                    .unwrap_or_default();
+                let _ = writeln!(
+                    html,
+                    "<div class=\"card back\"><div class=\"text\">{}</div>{}</div>",
+                    escape(&card.definition),
+                    reference
+                );
+            }
+        }
+        html += "</div>\n";
+    }
+    html + "</body>\n</html>\n"
+}
+
+pub fn run(args: PrintCardsArgs) {
+    let questions = load_questions(args.bank.as_deref());
+    let filter = Filter {
+        tags: args.tags.clone(),
+        kinds: vec![Kind::Matching],
+        include_drafts: false,
+    };
+    let mut cards = Vec::new();
+    for mut chapter in questions.chapters {
+        if let Some(ChapterSet(chapters)) = &args.chapters {
+            if !chapters.contains(&chapter.chapter) {
+                continue;
+            }
+        }
+        chapter.retain(&filter);
+        for question in &chapter.matching {
+            for pair in &question.pairs {
+                cards.push(Card {
+                    term: pair.term.clone(),
+                    definition: pair.definition.clone(),
+                    label: format!("챕터 {} · {}", chapter.chapter, question.question),
+                    reference: question.reference(),
+                });
+            }
+        }
+    }
+    if cards.is_empty() {
+        println!("카드로 만들 짝짓기 문제가 없습니다.");
+        return;
+    }
+
+    let per_page = args.per_page as usize;
+    let html = render(&cards, per_page, args.columns as usize);
+    match fs::write(&args.output, html) {
+        Ok(()) => println!(
+            "{} {} (카드 {}장, {}쪽)\n브라우저에서 열어 양면(긴 쪽으로 넘김)으로 인쇄하세요.",
+            styled(Color::Green).paint("카드를 만들었습니다:"),
+            args.output.display(),
+            cards.len(),
+            cards.len().div_ceil(per_page) * 2
+        ),
+        Err(e) => {
+            println!(
+                "{} {}",
+                styled(Color::Red).paint("파일을 쓸 수 없습니다:"),
+                e
+            );
+            std::process::exit(1);
+        }
+    }
+}
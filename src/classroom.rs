@@ -0,0 +1,380 @@
+use crate::stats::csv_field;
+use crate::style::{self, styled};
+use crate::{config, get_user_input, history, Prompter, QuizArgs};
+use ansi_term::Color;
+use chrono::{DateTime, Local};
+use rand::RngCore;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::fmt::Write as _;
+use std::fs;
+use std::io::{self, IsTerminal};
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+use terms::score::{self, Grading, Score};
+use terms::{Askable, Frontend, Kind, Marker};
+
+// 선생님 쪽 서버가 나눠 주는 시험. 정답은 서버에만 두고 모든 학생에게 같은 차례의 문제 글과 보기만 보냅니다
+#[derive(Serialize, Deserialize)]
+pub struct Assignment {
+    pub bank: String,
+    pub questions: Vec<Prompt>,
+    pub time_limit_secs: Option<u64>,
+    // 서버에 서명 키가 있으면 `questions` 를 JSON 으로 적은 글에 한 서명
+    #[cfg(feature = "sign")]
+    #[serde(default)]
+    pub signature: Option<crate::sign::Detached>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Prompt {
+    pub id: String,
+    pub chapter: u32,
+    pub kind: Kind,
+    pub text: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub options: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub marker: Option<Marker>,
+}
+
+impl Prompt {
+    pub fn new(question: &dyn Askable, chapter: u32, rng: &mut dyn RngCore) -> Self {
+        let (options, marker) = match question.choices() {
+            Some((options, marker)) => (options.to_vec(), Some(marker)),
+            None => (Vec::new(), None),
+        };
+        Self {
+            id: question.id(),
+            chapter,
+            kind: question.kind(),
+            text: question.prompt(rng),
+            options,
+            marker,
+        }
+    }
+}
+
+// 학생이 보낸 답안을 서버의 문제로 채점한 결과
+#[derive(Serialize, Deserialize)]
+pub struct Graded {
+    pub points: Score,
+    pub grade: Option<String>,
+    pub passed: Option<bool>,
+    pub session: history::Session,
+}
+
+// 수업 순위표(`terms leaderboard --server`)에 보내는 학생별 결과
+#[derive(Serialize, Deserialize)]
+pub struct Standing {
+    pub student: String,
+    pub bank: String,
+    pub started_at: DateTime<Local>,
+    pub correct: usize,
+    pub total: usize,
+    pub points: Score,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Report {
+    pub student: String,
+    pub session: history::Session,
+}
+
+impl Report {
+    pub fn points(&self) -> Score {
+        let mut points = Score::default();
+        for answer in &self.session.answers {
+            points += answer
+                .score
+                .unwrap_or_else(|| Score::from_correct(answer.correct));
+        }
+        points
+    }
+}
+
+// 서버가 알려 준 오류(이미 제출한 이름, 제한 시간 지남 등)를 그대로 보여줄 수 있게 상태 코드를 직접 봅니다
+fn post<T: DeserializeOwned>(url: &str, path: &str, body: serde_json::Value) -> Result<T, String> {
+    let agent = ureq::Agent::new_with_config(
+        ureq::Agent::config_builder()
+            .http_status_as_error(false)
+            .build(),
+    );
+    let mut response = agent
+        .post(format!("{}{}", url.trim_end_matches('/'), path))
+        .send_json(body)
+        .map_err(|e| e.to_string())?;
+    let status = response.status().as_u16();
+    let value: serde_json::Value = response.body_mut().read_json().map_err(|e| e.to_string())?;
+    if status >= 400 {
+        return Err(match value["error"].as_str() {
+            Some(message) => message.to_string(),
+            None => format!("HTTP {}", status),
+        });
+    }
+    serde_json::from_value(value).map_err(|e| e.to_string())
+}
+
+// 서버는 이 이름으로 처음 받아 간 때부터 제한 시간을 잽니다
+fn fetch(url: &str, student: &str) -> Result<Assignment, String> {
+    let assignment: Assignment = post(url, "/api/exam", json!({ "student": student }))?;
+    #[cfg(feature = "sign")]
+    crate::sign::enforce(
+        url,
+        match &assignment.signature {
+            Some(signature) => crate::sign::verify(
+                serde_json::to_string(&assignment.questions)
+                    .unwrap()
+                    .as_bytes(),
+                signature,
+            ),
+            // 믿을 키를 정해 두었으면 서명 없는 시험은 받지 않습니다
+            None if !config::Config::load().trusted_keys.is_empty() => {
+                Err("서명되지 않은 시험입니다".to_string())
+            }
+            None => Ok(()),
+        },
+    );
+    Ok(assignment)
+}
+
+// 시험을 받아 시험 모드로 풀고 낸 답만 제출합니다. 채점은 서버가 해서 돌려줍니다
+pub fn take(url: &str, args: &QuizArgs) {
+    let student = match args.student.clone() {
+        Some(student) => student,
+        None => loop {
+            let student = get_user_input("이름(또는 학번)을 입력하세요: ");
+            if !student.is_empty() {
+                break student;
+            }
+        },
+    };
+    let assignment = match fetch(url, &student) {
+        Ok(assignment) => assignment,
+        Err(e) => {
+            println!(
+                "{} {}",
+                styled(Color::Red).paint("시험을 받아올 수 없습니다:"),
+                e
+            );
+            return;
+        }
+    };
+    let config = config::Config::load();
+    let romanized = args.romanized.or(config.romanized);
+    let confirm = !args.no_confirm && io::stdin().is_terminal();
+    let questions = &assignment.questions;
+    println!("{}: 문제 {}개", assignment.bank, questions.len());
+    if let Some(secs) = assignment.time_limit_secs {
+        println!(
+            "제한 시간: {}",
+            crate::format_duration(Duration::from_secs(secs))
+        );
+    }
+    println!();
+
+    let started_at = Local::now();
+    let start = Instant::now();
+    let deadline = assignment
+        .time_limit_secs
+        .map(|secs| start + Duration::from_secs(secs));
+    let mut given: Vec<Option<String>> = vec![None; questions.len()];
+    let mut times = vec![0; questions.len()];
+    let mut position = 0;
+    while position < questions.len() {
+        let prompt = &questions[position];
+        println!(
+            "{} {}",
+            styled(Color::Yellow.bold()).paint(format!("[{}/{}]", position + 1, questions.len())),
+            styled(Color::Yellow).paint(format!("챕터 {}", prompt.chapter))
+        );
+        let mut prompter = Prompter::new(true, args.tui, deadline, config.keys.clone());
+        prompter.romanized = romanized;
+        prompter.converting = romanized.is_some();
+        prompter.confirm = confirm;
+        prompter.show(&prompt.text);
+        let asked_at = Instant::now();
+        let answer = match prompt.marker {
+            Some(marker) => prompter.choose(&prompt.text, &prompt.options, marker, marker.prompt()),
+            None => prompter.answer("당신의 답변: "),
+        };
+        times[position] += asked_at.elapsed().as_millis() as u64;
+        if prompter.quit || prompter.timed_out {
+            break;
+        }
+        println!();
+        if prompter.back {
+            position = position.saturating_sub(1);
+            continue;
+        }
+        given[position] = answer;
+        position += 1;
+    }
+
+    let answers = questions
+        .iter()
+        .zip(given)
+        .zip(times)
+        .map(|((prompt, answer), time_ms)| history::Answer {
+            id: prompt.id.clone(),
+            text: prompt.text.clone(),
+            chapter: prompt.chapter,
+            kind: prompt.kind,
+            group: None,
+            correct: false,
+            score: None,
+            time_ms,
+            lifeline: false,
+            options: Vec::new(),
+            given: answer.into_iter().collect(),
+            expected: String::new(),
+        })
+        .collect();
+    let session = history::Session {
+        bank: assignment.bank.clone(),
+        started_at,
+        duration_secs: start.elapsed().as_secs_f64(),
+        answers,
+    };
+    let graded: Graded = match post(
+        url,
+        "/api/reports",
+        json!({ "student": student, "session": session }),
+    ) {
+        Ok(graded) => graded,
+        Err(e) => {
+            println!(
+                "{} {}",
+                styled(Color::Red).paint("답안을 제출할 수 없습니다:"),
+                e
+            );
+            return;
+        }
+    };
+    println!("{}", styled(Color::Green).paint("답안을 제출했습니다."));
+    print_graded(&graded);
+    if let Err(e) = history::append(graded.session) {
+        println!(
+            "{} {}",
+            styled(Color::Red).paint("학습 기록을 저장할 수 없습니다:"),
+            e
+        );
+    }
+}
+
+fn print_graded(graded: &Graded) {
+    let answers = &graded.session.answers;
+    for (i, answer) in answers.iter().enumerate() {
+        if answer.correct {
+            continue;
+        }
+        let given = answer.given.last().map_or("(빈 답안)", String::as_str);
+        println!(
+            "{} {} {} {} {}",
+            styled(Color::Yellow).paint(format!("{}.", i + 1)),
+            answer.text,
+            styled(Color::Red).paint(given),
+            styled(Color::Cyan).paint("정답:"),
+            styled(Color::Green).paint(&answer.expected)
+        );
+    }
+    println!(
+        "{} 문제 중 {} 문제를 맞혔습니다. 점수: {} ({:.1}%)",
+        answers.len(),
+        style::emphasis(Color::Yellow)
+            .paint(answers.iter().filter(|a| a.correct).count().to_string()),
+        graded.points,
+        graded.points.percent()
+    );
+    if let Some(grade) = &graded.grade {
+        println!("성적: {}", styled(Color::Yellow.bold()).paint(grade));
+    }
+    match graded.passed {
+        Some(true) => println!("{}", styled(Color::Green.bold()).paint("합격")),
+        Some(false) => println!("{}", styled(Color::Red.bold()).paint("불합격")),
+        None => {}
+    }
+}
+
+pub struct Gradebook {
+    path: PathBuf,
+    reports: Vec<Report>,
+}
+
+impl Gradebook {
+    // 이어서 모을 수 있도록 전에 저장한 JSON 성적부가 있으면 읽어 옵니다
+    pub fn open(path: PathBuf) -> io::Result<Self> {
+        let reports = match fs::read_to_string(path.with_extension("json")) {
+            Ok(data) => serde_json::from_str(&data)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Vec::new(),
+            Err(e) => return Err(e),
+        };
+        Ok(Self { path, reports })
+    }
+
+    pub fn has(&self, student: &str) -> bool {
+        self.reports.iter().any(|r| r.student == student)
+    }
+
+    // 같은 이름으로 다시 낸 답안은 서버가 받지 않으니 덧붙이기만 합니다
+    pub fn add(&mut self, report: Report, grading: &Grading) -> io::Result<Score> {
+        let points = report.points();
+        self.reports.push(report);
+        self.save(grading)?;
+        Ok(points)
+    }
+
+    fn save(&self, grading: &Grading) -> io::Result<()> {
+        let mut csv = String::from(
+            "student,started_at,correct,total,points,possible,percent,grade,passed,duration_secs\n",
+        );
+        for report in &self.reports {
+            let points = report.points();
+            let answers = &report.session.answers;
+            writeln!(
+                csv,
+                "{},{},{},{},{},{},{:.1},{},{},{:.0}",
+                csv_field(&report.student),
+                report.session.started_at.format("%Y-%m-%d %H:%M:%S"),
+                answers.iter().filter(|a| a.correct).count(),
+                answers.len(),
+                score::points(points.earned),
+                score::points(points.possible),
+                points.percent(),
+                csv_field(grading.grade(&points).unwrap_or_default()),
+                match grading.passed(&points) {
+                    Some(true) => "pass",
+                    Some(false) => "fail",
+                    None => "",
+                },
+                report.session.duration_secs
+            )
+            .unwrap();
+        }
+        fs::write(&self.path, csv)?;
+
+        let json = serde_json::to_string_pretty(&self.reports)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        fs::write(self.path.with_extension("json"), json)
+    }
+
+    pub fn standings(&self) -> Vec<Standing> {
+        self.reports
+            .iter()
+            .map(|report| Standing {
+                student: report.student.clone(),
+                bank: report.session.bank.clone(),
+                started_at: report.session.started_at,
+                correct: report.session.answers.iter().filter(|a| a.correct).count(),
+                total: report.session.answers.len(),
+                points: report.points(),
+            })
+            .collect()
+    }
+
+    pub fn path(&self) -> &PathBuf {
+        &self.path
+    }
+}
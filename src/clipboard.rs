@@ -0,0 +1,25 @@
+#[cfg(feature = "clipboard")]
+pub fn copy(text: &str) -> Result<(), String> {
+    use std::sync::{Mutex, OnceLock};
+
+    // X11 등에서는 클립보드를 연 프로그램이 살아 있는 동안만 내용이 남으므로 세션 내내 붙잡아 둡니다
+    static CLIPBOARD: OnceLock<Result<Mutex<arboard::Clipboard>, String>> = OnceLock::new();
+    let clipboard = CLIPBOARD.get_or_init(|| {
+        arboard::Clipboard::new()
+            .map(Mutex::new)
+            .map_err(|e| e.to_string())
+    });
+    match clipboard {
+        Ok(clipboard) => clipboard
+            .lock()
+            .unwrap()
+            .set_text(text)
+            .map_err(|e| e.to_string()),
+        Err(e) => Err(e.clone()),
+    }
+}
+
+#[cfg(not(feature = "clipboard"))]
+pub fn copy(_text: &str) -> Result<(), String> {
+    Err("클립보드 기능 없이 빌드되었습니다".to_string())
+}
@@ -0,0 +1,348 @@
+use crate::history;
+use crate::style::styled;
+use ansi_term::Color;
+use chrono::NaiveDate;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use terms::hangul;
+use terms::score::Grading;
+use terms::Kind;
+
+#[derive(Deserialize, Clone)]
+#[serde(transparent)]
+pub struct Key(String);
+
+impl Key {
+    fn new(key: &str) -> Self {
+        Key(key.to_string())
+    }
+
+    // 공백뿐인 키(예: " ")는 그대로, 나머지는 앞뒤 공백을 무시하고 비교합니다
+    pub fn matches(&self, input: &str) -> bool {
+        if self.0.trim().is_empty() {
+            !self.0.is_empty() && input == self.0
+        } else {
+            input.trim() == self.0.trim()
+        }
+    }
+
+    // 전체 화면에서 한 글자씩 친 것이 이 키의 앞부분인지
+    pub fn continues(&self, typed: &str) -> bool {
+        let key = self.0.trim_start();
+        key.len() > typed.len() && key.starts_with(typed)
+    }
+
+    // `:report 메모` 처럼 뒤에 글을 붙이는 명령이면 붙인 글을 돌려줍니다
+    pub fn strip<'a>(&self, input: &'a str) -> Option<&'a str> {
+        let key = self.0.trim();
+        let rest = input.trim().strip_prefix(key)?;
+        (!key.is_empty() && (rest.is_empty() || rest.starts_with(' '))).then(|| rest.trim())
+    }
+}
+
+impl std::fmt::Display for Key {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self.0.as_str() {
+            " " => write!(f, "<space>"),
+            key => write!(f, "{}", key),
+        }
+    }
+}
+
+#[derive(Deserialize, Clone)]
+#[serde(default)]
+pub struct Keys {
+    pub reveal: Key,
+    pub copy: Key,
+    pub skip: Key,
+    pub flag: Key,
+    pub report: Key,
+    pub suspend: Key,
+    pub back: Key,
+    pub note: Key,
+    pub hint: Key,
+    pub fifty: Key,
+    pub hangul: Key,
+    pub quit: Key,
+    pub confirm: Key,
+}
+
+impl Default for Keys {
+    fn default() -> Self {
+        Self {
+            reveal: Key::new(":reveal"),
+            copy: Key::new(":copy"),
+            skip: Key::new(":skip"),
+            flag: Key::new(":flag"),
+            report: Key::new(":report"),
+            suspend: Key::new(":suspend"),
+            back: Key::new(":back"),
+            note: Key::new(":note"),
+            hint: Key::new("?"),
+            fifty: Key::new(":50"),
+            hangul: Key::new(":hangul"),
+            quit: Key::new(":quit"),
+            confirm: Key::new("y"),
+        }
+    }
+}
+
+impl Keys {
+    pub fn print(&self) {
+        let bindings = [
+            ("reveal", &self.reveal, "답하지 않고 정답 보기 (오답 처리)"),
+            (
+                "copy",
+                &self.copy,
+                "정답을 보고 클립보드에 복사 (오답 처리)",
+            ),
+            ("skip", &self.skip, "문제 건너뛰기"),
+            ("flag", &self.flag, "다시 볼 문제로 표시/해제"),
+            (
+                "report",
+                &self.report,
+                "문제가 틀렸다고 신고 (뒤에 메모를 붙일 수 있음)",
+            ),
+            (
+                "suspend",
+                &self.suspend,
+                "이 문제를 앞으로 내지 않기 (`terms suspended` 로 되돌림)",
+            ),
+            (
+                "back",
+                &self.back,
+                "시험 모드에서 앞 문제로 돌아가 답 고치기",
+            ),
+            (
+                "note",
+                &self.note,
+                "이 문제에 내 메모 남기기 (다음에 나올 때 보여줌, 비우면 지움)",
+            ),
+            ("hint", &self.hint, "정답을 한 글자 더 보기 (글자마다 감점)"),
+            (
+                "fifty",
+                &self.fifty,
+                "50:50: 틀린 보기 절반 지우기 (`--lifelines` 로 정한 횟수만큼)",
+            ),
+            (
+                "hangul",
+                &self.hangul,
+                "로마자/두벌식 입력을 한글로 바꾸기 켜기/끄기",
+            ),
+            ("quit", &self.quit, "세션 끝내기"),
+            ("confirm", &self.confirm, "끝내기 확인"),
+        ];
+        for (name, key, description) in bindings {
+            println!(
+                "{:<8} {} {}",
+                name,
+                styled(Color::Yellow).paint(format!("{:<10}", key.to_string())),
+                description
+            );
+        }
+        println!(
+            "\n{} 의 keys: 항목에서 바꿀 수 있습니다.",
+            history::home_dir().join("config.yaml").display()
+        );
+    }
+}
+
+// 한 자모(한글이 아니면 한 글자)만 틀린 직접 쓴 답의 채점
+#[derive(Deserialize, Default, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum Typo {
+    #[default]
+    Wrong,
+    // 거의 정답: 절반 점수
+    Partial,
+    Correct,
+}
+
+// 한글을 칠 수 없는 터미널에서 영문 자판으로 쓴 답을 한글로 바꾸는 방식
+#[derive(Deserialize, clap::ValueEnum, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum Romanized {
+    // 로마자 표기 (simjang → 심장)
+    Roman,
+    // 두벌식 자판 글쇠 (tlawkd → 심장)
+    Dubeolsik,
+}
+
+impl Romanized {
+    pub fn convert(self, text: &str) -> String {
+        match self {
+            Romanized::Roman => hangul::from_romanization(text),
+            Romanized::Dubeolsik => hangul::from_dubeolsik(text),
+        }
+    }
+}
+
+// `terms progress` 로 확인할 목표. 챕터나 태그(둘 다 적으면 둘 다 맞는 문제)의 최근 `last` 문제 정답률이
+// `accuracy`% 이상이면 이룬 것입니다
+#[derive(Deserialize, Clone)]
+pub struct Goal {
+    #[serde(default)]
+    pub chapter: Option<u32>,
+    #[serde(default)]
+    pub tag: Option<String>,
+    // 이 문제 파일의 기록만 셉니다. 태그는 이 파일(생략하면 내장된 문제)에서 찾습니다
+    #[serde(default)]
+    pub bank: Option<PathBuf>,
+    pub accuracy: f64,
+    #[serde(default = "default_last")]
+    pub last: usize,
+    // 이 날까지 이루려는 목표 (예: 2026-10-16)
+    #[serde(default)]
+    pub by: Option<NaiveDate>,
+}
+
+fn default_last() -> usize {
+    30
+}
+
+// `terms import wordlist` 가 단어 목록의 한 줄로 만드는 문제 유형
+#[derive(Deserialize, clap::ValueEnum, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Synthesis {
+    // 용어와 정의를 `group_size` 개씩 묶은 짝짓기
+    Matching,
+    // 예문 속의 용어를 빈칸으로 바꾼 빈칸 문제
+    Cloze,
+    // 정의를 보고 용어의 철자를 쓰는 문제
+    Spelling,
+}
+
+#[derive(Deserialize, Clone)]
+#[serde(default)]
+pub struct ImportRules {
+    pub kinds: Vec<Synthesis>,
+    pub group_size: usize,
+    // 짝짓기 문제의 문제 글. 묶음이 여럿이면 뒤에 번호를 붙입니다
+    pub matching_question: String,
+    // 예문이 없을 때 정의에 용어가 나오면 정의로 빈칸 문제를 만듭니다
+    pub cloze_from_definition: bool,
+    // 이보다 많은 낱말로 된 용어는 철자 문제를 만들지 않습니다
+    pub spelling_max_words: usize,
+}
+
+impl Default for ImportRules {
+    fn default() -> Self {
+        Self {
+            kinds: vec![Synthesis::Matching, Synthesis::Cloze, Synthesis::Spelling],
+            group_size: 8,
+            matching_question: "용어와 정의를 짝지으세요.".to_string(),
+            cloze_from_definition: true,
+            spelling_max_words: 1,
+        }
+    }
+}
+
+// `terms sync` 로 기록을 주고받을 곳. WebDAV 와 S3 는 `sync` 기능을 켜야 씁니다
+#[derive(Deserialize)]
+#[cfg_attr(not(feature = "sync"), allow(dead_code))]
+#[serde(tag = "backend", rename_all = "snake_case")]
+pub enum Remote {
+    // 기록을 커밋해서 올릴 Git 저장소 주소
+    Git {
+        url: String,
+        #[serde(default = "default_branch")]
+        branch: String,
+    },
+    // 기록 파일을 올릴 폴더의 주소. 암호는 `TERMS_SYNC_PASSWORD` 환경 변수에 넣습니다
+    Webdav {
+        url: String,
+        #[serde(default)]
+        user: Option<String>,
+    },
+    // S3 호환 저장소. 키는 `AWS_ACCESS_KEY_ID`, `AWS_SECRET_ACCESS_KEY` 환경 변수에 넣습니다
+    S3 {
+        endpoint: String,
+        bucket: String,
+        #[serde(default = "default_region")]
+        region: String,
+        #[serde(default)]
+        prefix: String,
+    },
+}
+
+fn default_branch() -> String {
+    "main".to_string()
+}
+
+fn default_region() -> String {
+    "us-east-1".to_string()
+}
+
+// 두 기기에서 모두 바뀐 기록을 어떻게 할지
+#[derive(Deserialize, clap::ValueEnum, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Conflict {
+    // 문제(세션)마다 합치고, 같은 문제가 양쪽에서 바뀌었으면 나중에 바뀐 쪽을 남깁니다
+    #[default]
+    Merge,
+    // 파일을 통째로 나중에 고친 쪽의 것으로 맞춥니다
+    LastWriteWins,
+}
+
+#[derive(Deserialize)]
+pub struct SyncSettings {
+    #[serde(flatten)]
+    pub remote: Remote,
+    #[serde(default)]
+    pub conflict: Conflict,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+pub struct Config {
+    pub points: BTreeMap<Kind, f64>,
+    pub keys: Keys,
+    pub negative_marking: Option<f64>,
+    pub retries: Option<usize>,
+    pub typo: Typo,
+    pub romanized: Option<Romanized>,
+    // 서명을 믿을 공개 키 (`terms sign --public-key` 로 보는 16진수)
+    pub trusted_keys: Vec<String>,
+    pub goals: Vec<Goal>,
+    // 세션을 시작할 때 먼저 풀 지난 세션의 틀린 문제 수 (`--warm-up`)
+    pub warm_up: Option<usize>,
+    pub import: ImportRules,
+    // 발음 녹음을 틀 명령 (예: `mpv --really-quiet`). 파일 경로를 마지막 인자로 붙입니다
+    pub audio_player: Option<String>,
+    pub sync: Option<SyncSettings>,
+    #[serde(flatten)]
+    pub grading: Grading,
+}
+
+impl Config {
+    pub fn load() -> Self {
+        let path = history::home_dir().join("config.yaml");
+        let data = match fs::read_to_string(&path) {
+            Ok(data) => data,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Self::default(),
+            Err(e) => {
+                println!(
+                    "{} {}",
+                    styled(Color::Red).paint("설정 파일을 읽을 수 없습니다:"),
+                    e
+                );
+                return Self::default();
+            }
+        };
+        match serde_yaml::from_str(&data) {
+            Ok(config) => config,
+            Err(e) => {
+                println!(
+                    "{} {}: {}",
+                    styled(Color::Red).paint("설정 파일이 올바르지 않습니다:"),
+                    path.display(),
+                    e
+                );
+                Self::default()
+            }
+        }
+    }
+}
@@ -0,0 +1,371 @@
+use crate::style::styled;
+use crate::{load_questions, ChapterSet};
+use ansi_term::Color;
+use rusqlite::types::Value;
+use rusqlite::{params, params_from_iter, Connection};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use terms::{
+    Chapter, FillInTheBlankQuestion, Kind, MatchingQuestion, MultipleChoiceQuestion, Normalize,
+    Questions, SpellingQuestion,
+};
+
+#[derive(clap::Args)]
+pub struct DbArgs {
+    #[command(subcommand)]
+    command: DbCommand,
+}
+
+#[derive(clap::Subcommand)]
+enum DbCommand {
+    /// YAML 문제 파일을 데이터베이스로 가져옵니다. 파일에 있는 챕터는 새로 덮어씁니다
+    Import {
+        file: PathBuf,
+        #[arg(long, value_name = "FILE", default_value = "terms.db")]
+        db: PathBuf,
+    },
+    /// 데이터베이스의 문제를 YAML 문제 파일로 내보냅니다
+    Export {
+        file: PathBuf,
+        #[arg(long, value_name = "FILE", default_value = "terms.db")]
+        db: PathBuf,
+        /// 내보낼 챕터 (예: 1-3,5). 생략하면 전부 내보냅니다
+        #[arg(long)]
+        chapters: Option<ChapterSet>,
+    },
+}
+
+pub fn run(args: DbArgs) {
+    match args.command {
+        DbCommand::Import { file, db } => {
+            let questions = load_questions(Some(&file));
+            let result = Database::open(&db).and_then(|mut database| database.import(&questions));
+            match result {
+                Ok(count) => println!("{}개의 문제를 {}에 가져왔습니다.", count, db.display()),
+                Err(e) => println!(
+                    "{} {}",
+                    styled(Color::Red).paint("가져오기에 실패했습니다:"),
+                    e
+                ),
+            }
+        }
+        DbCommand::Export { file, db, chapters } => match export(&db, chapters, &file) {
+            Ok(count) => println!("{}개 챕터를 {}로 내보냈습니다.", count, file.display()),
+            Err(e) => println!(
+                "{} {}",
+                styled(Color::Red).paint("내보내기에 실패했습니다:"),
+                e
+            ),
+        },
+    }
+}
+
+fn export(db: &Path, chapters: Option<ChapterSet>, file: &Path) -> Result<usize> {
+    let database = Database::open(db)?;
+    let chapters = match chapters {
+        Some(ChapterSet(chapters)) => chapters,
+        None => database.chapters()?,
+    };
+    let questions = Questions {
+        points: database.points()?,
+        normalize: database.normalize()?,
+        chapters: database.load(&chapters, &[], &[])?,
+    };
+    fs::write(file, serde_yaml::to_string(&questions)?)?;
+    Ok(questions.chapters.len())
+}
+
+pub struct Database {
+    conn: Connection,
+}
+
+const SCHEMA: &str = "
+PRAGMA foreign_keys = ON;
+CREATE TABLE IF NOT EXISTS questions (
+    id INTEGER PRIMARY KEY,
+    chapter INTEGER NOT NULL,
+    kind TEXT NOT NULL,
+    position INTEGER NOT NULL,
+    data TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS questions_chapter_kind ON questions (chapter, kind);
+CREATE TABLE IF NOT EXISTS tags (
+    question INTEGER NOT NULL REFERENCES questions (id) ON DELETE CASCADE,
+    tag TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS tags_tag ON tags (tag, question);
+CREATE TABLE IF NOT EXISTS bank (
+    key TEXT PRIMARY KEY,
+    value TEXT NOT NULL
+);
+";
+
+type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+impl Database {
+    pub fn open(path: &Path) -> Result<Self> {
+        let conn = Connection::open(path)?;
+        conn.execute_batch(SCHEMA)?;
+        Ok(Self { conn })
+    }
+
+    pub fn import(&mut self, questions: &Questions) -> Result<usize> {
+        let tx = self.conn.transaction()?;
+        // 새 파일에서 지운 설정이 예전 값으로 남지 않도록 매번 지우고 다시 씁니다
+        tx.execute("DELETE FROM bank WHERE key IN ('points', 'normalize')", [])?;
+        if !questions.points.is_empty() {
+            tx.execute(
+                "INSERT INTO bank (key, value) VALUES ('points', ?1)",
+                [encode(&questions.points)?],
+            )?;
+        }
+        if let Some(normalize) = &questions.normalize {
+            tx.execute(
+                "INSERT INTO bank (key, value) VALUES ('normalize', ?1)",
+                [encode(normalize)?],
+            )?;
+        }
+        let mut count = 0;
+        for chapter in &questions.chapters {
+            tx.execute(
+                "DELETE FROM questions WHERE chapter = ?1",
+                [chapter.chapter],
+            )?;
+
+            let mut insert = |kind: Kind, data: String, tags: &[String]| -> Result<()> {
+                tx.execute(
+                    "INSERT INTO questions (chapter, kind, position, data) VALUES (?1, ?2, ?3, ?4)",
+                    params![chapter.chapter, kind.code(), count as i64, data],
+                )?;
+                let question = tx.last_insert_rowid();
+                for tag in tags {
+                    tx.execute(
+                        "INSERT INTO tags (question, tag) VALUES (?1, ?2)",
+                        params![question, tag],
+                    )?;
+                }
+                count += 1;
+                Ok(())
+            };
+
+            for q in &chapter.multiple_choice {
+                insert(Kind::MultipleChoice, encode(q)?, &q.tags)?;
+            }
+            for q in &chapter.matching {
+                insert(Kind::Matching, encode(q)?, &q.tags)?;
+            }
+            for q in &chapter.fill_in_the_blanks {
+                insert(Kind::FillInTheBlank, encode(q)?, &q.tags)?;
+            }
+            for q in &chapter.spelling {
+                insert(Kind::Spelling, encode(q)?, &q.tags)?;
+            }
+            // 직접 만든 유형은 키와 함께 통째로 저장해 두었다가 불러올 때 다시 나눕니다
+            for (key, questions) in &chapter.custom {
+                for q in questions.as_sequence().into_iter().flatten() {
+                    let tags: Vec<String> = q
+                        .get("tags")
+                        .and_then(|t| serde_yaml::from_value(t.clone()).ok())
+                        .unwrap_or_default();
+                    insert(Kind::Custom, encode(&BTreeMap::from([(key, q)]))?, &tags)?;
+                }
+            }
+        }
+        tx.commit()?;
+        Ok(count)
+    }
+
+    pub fn points(&self) -> Result<BTreeMap<Kind, f64>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT value FROM bank WHERE key = 'points'")?;
+        let mut rows = stmt.query([])?;
+        match rows.next()? {
+            Some(row) => decode(&row.get::<_, String>(0)?),
+            None => Ok(BTreeMap::new()),
+        }
+    }
+
+    pub fn normalize(&self) -> Result<Option<Normalize>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT value FROM bank WHERE key = 'normalize'")?;
+        let mut rows = stmt.query([])?;
+        match rows.next()? {
+            Some(row) => decode(&row.get::<_, String>(0)?).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    pub fn chapters(&self) -> Result<Vec<u32>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT DISTINCT chapter FROM questions ORDER BY chapter")?;
+        let chapters = stmt
+            .query_map([], |row| row.get(0))?
+            .collect::<rusqlite::Result<_>>()?;
+        Ok(chapters)
+    }
+
+    pub fn load(&self, chapters: &[u32], tags: &[String], kinds: &[Kind]) -> Result<Vec<Chapter>> {
+        let mut sql = format!(
+            "SELECT chapter, kind, data FROM questions WHERE chapter IN ({})",
+            placeholders(chapters.len())
+        );
+        let mut values: Vec<Value> = chapters.iter().map(|&c| Value::from(c)).collect();
+        if !kinds.is_empty() {
+            sql += &format!(" AND kind IN ({})", placeholders(kinds.len()));
+            values.extend(kinds.iter().map(|k| Value::from(k.code().to_string())));
+        }
+        if !tags.is_empty() {
+            sql += &format!(
+                " AND EXISTS (SELECT 1 FROM tags WHERE tags.question = questions.id AND tag IN ({}))",
+                placeholders(tags.len())
+            );
+            values.extend(tags.iter().map(|t| Value::from(t.clone())));
+        }
+        sql += " ORDER BY chapter, position";
+
+        let mut stmt = self.conn.prepare(&sql)?;
+        let mut rows = stmt.query(params_from_iter(values))?;
+        let mut loaded: BTreeMap<u32, Chapter> = BTreeMap::new();
+        while let Some(row) = rows.next()? {
+            let number: u32 = row.get(0)?;
+            let kind: String = row.get(1)?;
+            let data: String = row.get(2)?;
+            let chapter = loaded.entry(number).or_insert_with(|| Chapter {
+                chapter: number,
+                ..Default::default()
+            });
+            match kind.as_str() {
+                "mc" => chapter
+                    .multiple_choice
+                    .push(decode::<MultipleChoiceQuestion>(&data)?),
+                "match" => chapter.matching.push(decode::<MatchingQuestion>(&data)?),
+                "fill" => chapter
+                    .fill_in_the_blanks
+                    .push(decode::<FillInTheBlankQuestion>(&data)?),
+                "spell" => chapter.spelling.push(decode::<SpellingQuestion>(&data)?),
+                "custom" => {
+                    for (key, q) in decode::<BTreeMap<String, serde_yaml::Value>>(&data)? {
+                        let questions = chapter
+                            .custom
+                            .entry(key)
+                            .or_insert_with(|| serde_yaml::Value::Sequence(Vec::new()));
+                        if let serde_yaml::Value::Sequence(questions) = questions {
+                            questions.push(q);
+                        }
+                    }
+                }
+                other => return Err(format!("알 수 없는 문제 유형: {}", other).into()),
+            }
+        }
+        Ok(loaded.into_values().collect())
+    }
+}
+
+fn placeholders(count: usize) -> String {
+    vec!["?"; count].join(", ")
+}
+
+fn encode<T: Serialize>(question: &T) -> Result<String> {
+    Ok(serde_yaml::to_string(question)?)
+}
+
+fn decode<T: DeserializeOwned>(data: &str) -> Result<T> {
+    Ok(serde_yaml::from_str(data)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BANK: &str = r#"
+points:
+  matching: 2.0
+normalize: [trim, case, particles]
+chapters:
+  - chapter: 3
+    multiple_choice:
+      - question: 세포의 에너지를 만드는 곳은?
+        id: mito
+        variants: [에너지를 만드는 세포 소기관은?]
+        options: [핵, 미토콘드리아, 리보솜]
+        answer: b
+        tags: [세포]
+        status: draft
+        points: 1.5
+        weight: 3.0
+        source: 생명과학 교과서
+        page: 42-45
+        translations:
+          en:
+            question: Which organelle produces energy?
+            options: [Nucleus, Mitochondria, Ribosome]
+    matching:
+      - question: 용어와 뜻을 맞추세요
+        pairs:
+          - term: 삼투
+            definition: 물이 반투과성 막을 지나는 것
+            sentence: 삼투 때문에 세포가 부풀었다
+        status: retired
+    fill_in_the_blanks:
+      - question: 빛으로 양분을 만드는 과정은 ___ 이다
+        answer: [광합성, 광합성 작용]
+        normalize: [trim]
+        page: 7
+    spelling:
+      - question: 엽록체
+        options: [엽녹체, 엽록체]
+        answer: 엽록체
+    flashcards:
+      - front: 핵
+        back: 유전 물질이 있는 곳
+        tags: [세포]
+  - chapter: 4
+    fill_in_the_blanks:
+      - question: DNA 는 ___ 구조이다
+        answer: 이중 나선
+"#;
+
+    fn export(db: &Database) -> Questions {
+        Questions {
+            points: db.points().unwrap(),
+            normalize: db.normalize().unwrap(),
+            chapters: db.load(&db.chapters().unwrap(), &[], &[]).unwrap(),
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        let questions = Questions::parse(BANK).unwrap();
+        let mut db = Database::open(Path::new(":memory:")).unwrap();
+        assert_eq!(db.import(&questions).unwrap(), 6);
+
+        let exported = export(&db);
+        assert_eq!(
+            serde_yaml::to_value(&questions).unwrap(),
+            serde_yaml::to_value(&exported).unwrap()
+        );
+        assert_eq!(
+            db.load(&[3], &["세포".to_string()], &[]).unwrap()[0]
+                .custom
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn reimport_clears_bank_settings() {
+        let mut db = Database::open(Path::new(":memory:")).unwrap();
+        db.import(&Questions::parse(BANK).unwrap()).unwrap();
+        db.import(&Questions::parse("chapters: []").unwrap())
+            .unwrap();
+
+        assert!(db.points().unwrap().is_empty());
+        assert!(db.normalize().unwrap().is_none());
+    }
+}
@@ -0,0 +1,89 @@
+use crate::stats::pad;
+use crate::style::{self, styled};
+use ansi_term::Color;
+use unicode_segmentation::UnicodeSegmentation;
+
+enum Part<'a> {
+    Same(&'a str),
+    // 입력에만 있는 글자
+    Extra(&'a str),
+    // 정답에만 있는 글자
+    Missing(&'a str),
+}
+
+// 글자(자소가 조합된 한글 음절 같은 grapheme) 단위로 가장 긴 공통 부분열을 찾아 나머지를 표시합니다
+fn diff<'a>(typed: &'a str, correct: &'a str) -> Vec<Part<'a>> {
+    let a: Vec<&str> = typed.graphemes(true).collect();
+    let b: Vec<&str> = correct.graphemes(true).collect();
+    let same = |x: &str, y: &str| x.eq_ignore_ascii_case(y);
+
+    let mut lcs = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lcs[i][j] = if same(a[i], b[j]) {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    let mut parts = Vec::new();
+    while i < a.len() || j < b.len() {
+        if i < a.len() && j < b.len() && same(a[i], b[j]) {
+            parts.push(Part::Same(b[j]));
+            i += 1;
+            j += 1;
+        } else if j < b.len() && (i == a.len() || lcs[i][j + 1] >= lcs[i + 1][j]) {
+            parts.push(Part::Missing(b[j]));
+            j += 1;
+        } else {
+            parts.push(Part::Extra(a[i]));
+            i += 1;
+        }
+    }
+    parts
+}
+
+// 틀린 답 아래에 입력과 정답을 나란히 보여주고 다른 글자를 표시합니다.
+// 색을 쓰지 않을 때는 다른 글자를 [ ] 로 감쌉니다
+pub fn print(typed: &str, correct: &str) {
+    let (typed, correct) = (typed.trim(), correct.trim());
+    if typed.is_empty() || correct.is_empty() || typed.eq_ignore_ascii_case(correct) {
+        return;
+    }
+    let parts = diff(typed, correct);
+    let plain = style::is_plain();
+    let mark = |text: &str, color: Color| {
+        if plain {
+            format!("[{}]", text)
+        } else {
+            styled(color.bold().underline()).paint(text).to_string()
+        }
+    };
+
+    // 이어진 다른 글자는 한 덩어리로 표시합니다
+    let (mut given, mut expected) = (String::new(), String::new());
+    let (mut extra, mut missing) = (String::new(), String::new());
+    for part in parts.iter().chain([Part::Same("")].iter()) {
+        match part {
+            Part::Extra(text) => extra += text,
+            Part::Missing(text) => missing += text,
+            Part::Same(text) => {
+                if !extra.is_empty() {
+                    given += &mark(&extra, Color::Red);
+                    extra.clear();
+                }
+                if !missing.is_empty() {
+                    expected += &mark(&missing, Color::Green);
+                    missing.clear();
+                }
+                given += text;
+                expected += text;
+            }
+        }
+    }
+    println!("  {}{}", pad("입력:", 6), given);
+    println!("  {}{}", pad("정답:", 6), expected);
+}
@@ -0,0 +1,359 @@
+use crate::style::styled;
+use crate::web::Submission;
+use crate::{config, QuizArgs};
+use ansi_term::Color;
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, HashMap};
+use std::env;
+use std::thread;
+use std::time::Duration;
+use terms::score::{self, Score};
+use terms::{weight, Askable};
+use ureq::http::Response;
+use ureq::{Agent, Body};
+
+#[derive(clap::Args)]
+pub struct DiscordArgs {
+    /// 문제를 올릴 채널의 ID
+    #[arg(long, value_name = "ID")]
+    channel: String,
+    /// 문제마다 답할 수 있는 시간(초)
+    #[arg(long, value_name = "SECS", default_value_t = 30)]
+    window: u64,
+    #[command(flatten)]
+    quiz: QuizArgs,
+}
+
+const API: &str = "https://discord.com/api/v10";
+// 봇 토큰은 명령줄에 남지 않도록 환경 변수로만 받습니다
+const TOKEN_VAR: &str = "DISCORD_TOKEN";
+// 한 메시지에 달 수 있는 반응 수의 제한
+const MAX_REACTIONS: usize = 20;
+// 정답을 알린 뒤 다음 문제를 올리기까지 기다리는 시간
+const PAUSE: Duration = Duration::from_secs(5);
+
+struct Player {
+    name: String,
+    points: Score,
+    correct: usize,
+}
+
+struct Client {
+    agent: Agent,
+    authorization: String,
+}
+
+impl Client {
+    fn new(token: &str) -> Self {
+        let config = Agent::config_builder().http_status_as_error(false).build();
+        Client {
+            agent: Agent::new_with_config(config),
+            authorization: format!("Bot {}", token),
+        }
+    }
+
+    // 요청이 너무 잦다는 응답(429)을 받으면 알려 준 시간만큼 쉬었다가 다시 보냅니다
+    fn send(
+        &self,
+        request: impl Fn() -> Result<Response<Body>, ureq::Error>,
+    ) -> Result<Value, String> {
+        loop {
+            let mut response = request().map_err(|e| e.to_string())?;
+            let status = response.status().as_u16();
+            let body: Value = response.body_mut().read_json().unwrap_or(Value::Null);
+            match status {
+                429 => {
+                    let wait = body["retry_after"].as_f64().unwrap_or(1.0);
+                    thread::sleep(Duration::from_secs_f64(wait));
+                }
+                400.. => {
+                    return Err(format!(
+                        "{} {}",
+                        status,
+                        body["message"].as_str().unwrap_or_default()
+                    ))
+                }
+                _ => return Ok(body),
+            }
+        }
+    }
+
+    fn get(&self, path: &str) -> Result<Value, String> {
+        self.send(|| {
+            self.agent
+                .get(format!("{}{}", API, path))
+                .header("Authorization", &self.authorization)
+                .call()
+        })
+    }
+
+    fn post(&self, path: &str, body: &Value) -> Result<Value, String> {
+        self.send(|| {
+            self.agent
+                .post(format!("{}{}", API, path))
+                .header("Authorization", &self.authorization)
+                .send_json(body)
+        })
+    }
+
+    fn put(&self, path: &str) -> Result<Value, String> {
+        self.send(|| {
+            self.agent
+                .put(format!("{}{}", API, path))
+                .header("Authorization", &self.authorization)
+                .send_empty()
+        })
+    }
+}
+
+// 보기 순서대로 🇦, 🇧, ... 반응을 씁니다
+fn emoji(index: usize) -> char {
+    char::from_u32(0x1F1E6 + index as u32).unwrap()
+}
+
+fn encode(text: &str) -> String {
+    text.bytes().map(|b| format!("%{:02X}", b)).collect()
+}
+
+fn display_name(user: &Value) -> String {
+    user["global_name"]
+        .as_str()
+        .or(user["username"].as_str())
+        .unwrap_or_default()
+        .to_string()
+}
+
+fn message(
+    question: &dyn Askable,
+    chapter: u32,
+    number: usize,
+    total: usize,
+    window: u64,
+) -> String {
+    let prompt = question.prompt(&mut rand::thread_rng());
+    let prompt = crate::web::describe(question, &prompt, chapter, number, total);
+    let mut text = format!(
+        "**챕터 {}** ({}/{}) · {}\n{}\n",
+        chapter,
+        number,
+        total,
+        question.kind().label(),
+        prompt["text"].as_str().unwrap_or_default()
+    );
+    match question.choices() {
+        Some((options, _)) => {
+            for (i, option) in options.iter().enumerate() {
+                text += &format!("{} {}\n", emoji(i), option);
+            }
+            text += &format!("{}초 안에 보기를 누르거나 이 메시지에 답장하세요.", window);
+        }
+        None => text += &format!("{}초 안에 이 메시지에 답장하세요.", window),
+    }
+    text
+}
+
+// 참가자마다 한 답을 모읍니다. 답장이 반응보다 앞서고, 보기를 여러 개 누르면 답하지 않은 것으로 칩니다
+fn collect(
+    client: &Client,
+    channel: &str,
+    id: &str,
+    options: usize,
+) -> Result<HashMap<String, (String, Option<Submission>)>, String> {
+    let mut answers = HashMap::new();
+    let path = format!("/channels/{}/messages?after={}&limit=100", channel, id);
+    let replies = client.get(&path)?;
+    // 최근 메시지가 먼저 오므로 거꾸로 보면서 참가자의 첫 답장만 남깁니다
+    for reply in replies.as_array().into_iter().flatten().rev() {
+        let author = &reply["author"];
+        if author["bot"].as_bool() == Some(true)
+            || reply["message_reference"]["message_id"].as_str() != Some(id)
+        {
+            continue;
+        }
+        let Some(user) = author["id"].as_str() else {
+            continue;
+        };
+        answers.entry(user.to_string()).or_insert_with(|| {
+            let answer = reply["content"].as_str().map(str::to_string);
+            let submission = Submission {
+                answer,
+                choice: None,
+            };
+            (display_name(author), Some(submission))
+        });
+    }
+
+    let mut reactions: HashMap<String, (String, Vec<usize>)> = HashMap::new();
+    for i in 0..options.min(MAX_REACTIONS) {
+        let path = format!(
+            "/channels/{}/messages/{}/reactions/{}?limit=100",
+            channel,
+            id,
+            encode(&emoji(i).to_string())
+        );
+        for user in client.get(&path)?.as_array().into_iter().flatten() {
+            if user["bot"].as_bool() == Some(true) {
+                continue;
+            }
+            if let Some(id) = user["id"].as_str() {
+                let entry = reactions
+                    .entry(id.to_string())
+                    .or_insert_with(|| (display_name(user), Vec::new()));
+                entry.1.push(i);
+            }
+        }
+    }
+    for (user, (name, choices)) in reactions {
+        answers.entry(user).or_insert_with(|| {
+            let submission = match choices[..] {
+                [choice] => Some(Submission {
+                    answer: None,
+                    choice: Some(choice),
+                }),
+                _ => None,
+            };
+            (name, submission)
+        });
+    }
+    Ok(answers)
+}
+
+fn leaderboard(players: &BTreeMap<String, Player>) -> Vec<&Player> {
+    let mut ranked: Vec<&Player> = players.values().collect();
+    ranked.sort_by(|a, b| b.points.earned.total_cmp(&a.points.earned));
+    ranked
+}
+
+fn standings(players: &BTreeMap<String, Player>) -> String {
+    leaderboard(players)
+        .iter()
+        .take(10)
+        .enumerate()
+        .map(|(rank, player)| {
+            format!(
+                "{}. {} {}점 ({}개 정답)\n",
+                rank + 1,
+                player.name,
+                score::points(player.points.earned),
+                player.correct
+            )
+        })
+        .collect()
+}
+
+pub fn run(args: DiscordArgs) {
+    let Ok(token) = env::var(TOKEN_VAR) else {
+        println!(
+            "{} {} 환경 변수에 봇 토큰을 넣어 주세요.",
+            styled(Color::Red).paint("봇 토큰이 없습니다."),
+            TOKEN_VAR
+        );
+        std::process::exit(1);
+    };
+    let config = config::Config::load();
+    let (questions, bank_points) = crate::deal(&args.quiz);
+    if questions.is_empty() {
+        println!("풀 문제가 없습니다.");
+        return;
+    }
+    let client = Client::new(&token);
+    let channel = args.channel;
+    let window = args.window.max(1);
+    let mut players: BTreeMap<String, Player> = BTreeMap::new();
+    let total = questions.len();
+
+    let result = (|| -> Result<(), String> {
+        for (number, (question, chapter)) in questions.iter().enumerate() {
+            let generated = question.generate().map(|g| Box::new(g) as Box<dyn Askable>);
+            let question = generated.as_ref().unwrap_or(question);
+            let text = message(question.as_ref(), *chapter, number + 1, total, window);
+            let posted = client.post(
+                &format!("/channels/{}/messages", channel),
+                &json!({ "content": text }),
+            )?;
+            let id = posted["id"]
+                .as_str()
+                .ok_or("메시지 ID 를 받지 못했습니다")?;
+            let options = question
+                .choices()
+                .map_or(0, |(options, _)| options.len().min(MAX_REACTIONS));
+            for i in 0..options {
+                client.put(&format!(
+                    "/channels/{}/messages/{}/reactions/{}/@me",
+                    channel,
+                    id,
+                    encode(&emoji(i).to_string())
+                ))?;
+            }
+            println!("문제 {}/{} 을 올렸습니다.", number + 1, total);
+            thread::sleep(Duration::from_secs(window));
+
+            let weight = weight(question.as_ref(), &bank_points, &config.points);
+            let mut answers = collect(&client, &channel, id, options)?;
+            let mut winners = Vec::new();
+            for (user, (name, submission)) in answers.drain() {
+                let player = players.entry(user.clone()).or_insert_with(|| Player {
+                    name: name.clone(),
+                    points: Score::default(),
+                    correct: 0,
+                });
+                player.name = name;
+                let result = match submission.and_then(|s| s.resolve(question.as_ref())) {
+                    Some(answer) => question.grade(answer.trim()),
+                    None => Score::skipped(),
+                }
+                .weighted(weight);
+                if result.is_correct() {
+                    player.correct += 1;
+                    winners.push(player.name.clone());
+                }
+                player.points += result;
+            }
+            winners.sort();
+
+            let winners = match winners.is_empty() {
+                true => "맞힌 사람이 없습니다.".to_string(),
+                false => format!("맞힌 사람: {}", winners.join(", ")),
+            };
+            client.post(
+                &format!("/channels/{}/messages", channel),
+                &json!({
+                    "content": format!(
+                        "정답은 **{}**\n{}\n\n{}",
+                        question.correct_answer(),
+                        winners,
+                        standings(&players)
+                    ),
+                    "message_reference": { "message_id": id },
+                }),
+            )?;
+            if number + 1 < total {
+                thread::sleep(PAUSE);
+            }
+        }
+        client.post(
+            &format!("/channels/{}/messages", channel),
+            &json!({ "content": format!("**최종 순위**\n{}", standings(&players)) }),
+        )?;
+        Ok(())
+    })();
+
+    if let Err(e) = result {
+        println!(
+            "{} {}",
+            styled(Color::Red).paint("디스코드와 통신할 수 없습니다:"),
+            e
+        );
+        std::process::exit(1);
+    }
+    println!("\n{}", styled(Color::Yellow.bold()).paint("최종 순위"));
+    for (rank, player) in leaderboard(&players).iter().enumerate() {
+        println!(
+            "{:>2}. {} {}점 ({}개 정답)",
+            rank + 1,
+            player.name,
+            score::points(player.points.earned),
+            player.correct
+        );
+    }
+}
@@ -0,0 +1,137 @@
+use crate::style::styled;
+use aes_gcm::aead::{Aead, KeyInit};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use ansi_term::Color;
+use argon2::Argon2;
+use rand::{thread_rng, RngCore};
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use terms::Questions;
+
+#[derive(clap::Args)]
+pub struct EncryptArgs {
+    /// 암호를 걸 YAML 문제 파일
+    file: PathBuf,
+    /// 암호를 건 파일을 쓸 곳 (생략하면 `파일.yaml.enc`)
+    #[arg(long, short, value_name = "FILE")]
+    output: Option<PathBuf>,
+}
+
+// 파일 형식: MAGIC, 솔트 16바이트, 논스 12바이트, AES-256-GCM 암호문
+const MAGIC: &[u8] = b"terms-enc1\n";
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+// 묻지 않고 이 환경 변수의 암호를 씁니다 (스크립트나 서버에서 열 때)
+const PASSPHRASE_VAR: &str = "TERMS_PASSPHRASE";
+const ATTEMPTS: usize = 3;
+
+pub fn is_encrypted(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext == "enc")
+}
+
+// 암호에서 Argon2id 로 키를 만듭니다
+fn cipher(passphrase: &str, salt: &[u8]) -> Aes256Gcm {
+    let mut key = [0u8; 32];
+    Argon2::default()
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .expect("암호에서 키를 만들 수 없습니다");
+    Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&key))
+}
+
+fn seal(data: &[u8], passphrase: &str) -> Vec<u8> {
+    let mut salt = [0u8; SALT_LEN];
+    let mut nonce = [0u8; NONCE_LEN];
+    thread_rng().fill_bytes(&mut salt);
+    thread_rng().fill_bytes(&mut nonce);
+    let sealed = cipher(passphrase, &salt)
+        .encrypt(Nonce::from_slice(&nonce), data)
+        .expect("암호화할 수 없습니다");
+    [MAGIC, &salt, &nonce, &sealed].concat()
+}
+
+fn open(data: &[u8], passphrase: &str) -> Result<String, String> {
+    let body = data
+        .strip_prefix(MAGIC)
+        .filter(|body| body.len() > SALT_LEN + NONCE_LEN)
+        .ok_or("terms encrypt 로 암호를 건 파일이 아닙니다")?;
+    let (salt, rest) = body.split_at(SALT_LEN);
+    let (nonce, sealed) = rest.split_at(NONCE_LEN);
+    let plain = cipher(passphrase, salt)
+        .decrypt(Nonce::from_slice(nonce), sealed)
+        .map_err(|_| "암호가 틀렸거나 파일이 손상되었습니다")?;
+    String::from_utf8(plain).map_err(|e| e.to_string())
+}
+
+fn prompt(message: &str) -> Result<String, String> {
+    rpassword::prompt_password(message).map_err(|e| format!("암호를 읽을 수 없습니다: {}", e))
+}
+
+// 암호를 물어 풀어 둔 YAML 을 돌려줍니다. 틀리면 몇 번 더 묻습니다
+pub fn read(path: &Path) -> Result<String, String> {
+    let data = fs::read(path).map_err(|e| e.to_string())?;
+    if let Ok(passphrase) = env::var(PASSPHRASE_VAR) {
+        return open(&data, &passphrase);
+    }
+    let mut attempt = 1;
+    loop {
+        let passphrase = prompt(&format!("{} 의 암호: ", path.display()))?;
+        match open(&data, &passphrase) {
+            Err(e) if attempt < ATTEMPTS && data.starts_with(MAGIC) => {
+                println!("{}", styled(Color::Red).paint(e));
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+fn passphrase() -> Result<String, String> {
+    if let Ok(passphrase) = env::var(PASSPHRASE_VAR) {
+        return Ok(passphrase);
+    }
+    loop {
+        let first = prompt("걸 암호: ")?;
+        if first.is_empty() {
+            println!("{}", styled(Color::Red).paint("빈 암호는 쓸 수 없습니다."));
+            continue;
+        }
+        if prompt("한 번 더: ")? == first {
+            return Ok(first);
+        }
+        println!("{}", styled(Color::Red).paint("두 암호가 다릅니다."));
+    }
+}
+
+pub fn run(args: EncryptArgs) {
+    let output = args.output.unwrap_or_else(|| {
+        let mut name = args.file.clone().into_os_string();
+        name.push(".enc");
+        PathBuf::from(name)
+    });
+    let result = fs::read_to_string(&args.file)
+        .map_err(|e| e.to_string())
+        // 풀 수 없는 파일에 암호를 걸지 않도록 먼저 읽어 봅니다
+        .and_then(|data| {
+            Questions::parse(&data).map_err(|e| format!("문제 파일을 읽을 수 없습니다: {}", e))?;
+            Ok(data)
+        })
+        .and_then(|data| Ok(seal(data.as_bytes(), &passphrase()?)))
+        .and_then(|sealed| fs::write(&output, sealed).map_err(|e| e.to_string()));
+    match result {
+        Ok(()) => println!(
+            "{} {}\n`terms quiz --bank {}` 로 열 때 암호를 묻습니다.",
+            styled(Color::Green).paint("암호를 건 문제 파일을 만들었습니다:"),
+            output.display(),
+            output.display()
+        ),
+        Err(e) => {
+            println!(
+                "{} {}",
+                styled(Color::Red).paint("암호를 걸 수 없습니다:"),
+                e
+            );
+            std::process::exit(1);
+        }
+    }
+}
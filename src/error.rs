@@ -0,0 +1,114 @@
+//! 문제 파일을 읽다 난 오류.
+
+use serde_yaml::Value;
+use thiserror::Error;
+
+// 문제 파일의 어디가 틀렸는지. 줄과 칸은 1부터 셉니다
+#[derive(Debug, Error)]
+#[error("{}: {message}", self.location())]
+pub struct ParseError {
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+    // `chapters[2].matching[0]` 처럼 틀린 항목까지의 경로
+    pub path: String,
+    // 틀린 항목이 들어 있는 챕터의 번호
+    pub chapter: Option<u32>,
+    pub message: String,
+}
+
+impl ParseError {
+    pub(crate) fn new(yaml: &str, error: serde_path_to_error::Error<serde_yaml::Error>) -> Self {
+        let path = match error.path().to_string() {
+            path if path == "." => String::new(),
+            path => path,
+        };
+        let inner = error.into_inner();
+        let (line, column) = match inner.location() {
+            Some(at) => (Some(at.line()), Some(at.column())),
+            None => (None, None),
+        };
+        // serde_yaml 의 메시지에 붙어 있는 경로와 위치는 따로 보여주므로 뗍니다
+        let mut message = inner.to_string();
+        if let Some((head, rest)) = message.split_once(": ") {
+            if !head.is_empty() && path.starts_with(head) {
+                message = rest.to_string();
+            }
+        }
+        if let (Some(line), Some(column)) = (line, column) {
+            message = message.replacen(&format!(" at line {} column {}", line, column), "", 1);
+        }
+        Self {
+            line,
+            column,
+            chapter: chapter(yaml, &path),
+            path,
+            message,
+        }
+    }
+
+    // 예: `12번째 줄 5번째 칸, 챕터 7 빈칸 1번 › answer`
+    pub fn location(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(line) = self.line {
+            parts.push(match self.column {
+                Some(column) => format!("{}번째 줄 {}번째 칸", line, column),
+                None => format!("{}번째 줄", line),
+            });
+        }
+        let rest = self
+            .path
+            .strip_prefix("chapters[")
+            .and_then(|rest| rest.split_once(']'))
+            .map(|(_, rest)| rest.trim_start_matches('.'));
+        match (self.chapter, rest) {
+            (Some(chapter), Some("")) => parts.push(format!("챕터 {}", chapter)),
+            (Some(chapter), Some(rest)) => {
+                parts.push(format!("챕터 {} {}", chapter, question(rest)))
+            }
+            _ if !self.path.is_empty() => parts.push(self.path.clone()),
+            _ => {}
+        }
+        match parts.is_empty() {
+            true => "문법".to_string(),
+            false => parts.join(", "),
+        }
+    }
+}
+
+// `fill_in_the_blanks[0].answer` 를 문제 파일 검사처럼 `빈칸 1번 › answer` 로 씁니다
+fn question(path: &str) -> String {
+    let (head, field) = path.split_once('.').unwrap_or((path, ""));
+    let named = head.split_once('[').and_then(|(key, index)| {
+        let kind = match key {
+            "multiple_choice" => "객관식",
+            "matching" => "짝짓기",
+            "fill_in_the_blanks" => "빈칸",
+            "spelling" => "철자",
+            _ => key,
+        };
+        let index: usize = index.strip_suffix(']')?.parse().ok()?;
+        Some(format!("{} {}번", kind, index + 1))
+    });
+    let head = named.unwrap_or_else(|| head.to_string());
+    match field {
+        "" => head,
+        field => format!("{} › {}", head, field),
+    }
+}
+
+// 경로가 `chapters[i]` 로 시작하면 그 챕터의 `chapter` 번호를 찾습니다
+fn chapter(yaml: &str, path: &str) -> Option<u32> {
+    let index: usize = path
+        .strip_prefix("chapters[")?
+        .split_once(']')?
+        .0
+        .parse()
+        .ok()?;
+    let value: Value = serde_yaml::from_str(yaml).ok()?;
+    let number = value
+        .get("chapters")?
+        .get(index)?
+        .get("chapter")?
+        .as_u64()?;
+    u32::try_from(number).ok()
+}
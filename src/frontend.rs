@@ -0,0 +1,62 @@
+use crate::question::Marker;
+use crate::score::Score;
+
+// 문제를 보여주고 답을 받는 쪽 (터미널, 웹 페이지 등). 채점은 문제 쪽에서 합니다
+pub trait Frontend {
+    fn show(&mut self, question: &str);
+    fn answer(&mut self, prompt: &str) -> Option<String>;
+    // 보기 중 하나를 고르게 하고, 줄 입력으로 답했을 때와 같은 형태의 답을 돌려줍니다
+    fn choose(
+        &mut self,
+        question: &str,
+        options: &[String],
+        marker: Marker,
+        prompt: &str,
+    ) -> Option<String>;
+    // 직접 쓴 답이 틀렸을 때 오타 허용 같은 프론트엔드의 정책으로 다시 채점합니다
+    fn regrade(&mut self, _answer: &str, _correct_answer: &str, score: Score) -> Score {
+        score
+    }
+    // 직접 쓰는 답이면 정답을 미리 알려줘서 한 글자씩 힌트로 보여줄 수 있게 합니다
+    fn offer_hints(&mut self, _correct_answer: &str) {}
+    // 보기가 있으면 틀린 보기의 위치를 알려줘서 50:50 으로 지울 수 있게 합니다
+    fn offer_elimination(&mut self, _wrong: &[usize]) {}
+    // 힌트로 본 글자 수. 한 글자마다 그만큼 점수가 줄어듭니다
+    fn hints_taken(&self) -> usize {
+        0
+    }
+    // 답을 채점할 때마다 불립니다. 참이면 정답을 보여주지 않고 다시 답하게 합니다
+    fn try_again(&mut self, _score: Score) -> bool {
+        false
+    }
+    fn result(&mut self, score: Score, correct_answer: &str);
+    // 답하지 않고 넘어갔을 때의 점수
+    fn unanswered(&mut self, correct_answer: &str) -> Score;
+}
+
+// 힌트에서 세는 글자 (띄어쓰기나 쉼표는 세지 않고 처음부터 보여줍니다)
+fn is_letter(c: char) -> bool {
+    c.is_alphanumeric()
+}
+
+pub fn hint_length(answer: &str) -> usize {
+    answer.chars().filter(|&c| is_letter(c)).count()
+}
+
+// 앞에서부터 `count` 글자만 보여주고 나머지는 `_` 로 가립니다 (예: 심장 → 심_)
+pub fn hint(answer: &str, count: usize) -> String {
+    let mut shown = 0;
+    answer
+        .chars()
+        .map(|c| {
+            if !is_letter(c) {
+                c
+            } else if shown < count {
+                shown += 1;
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
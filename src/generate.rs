@@ -0,0 +1,482 @@
+use crate::add::{count, insert, rewrite};
+use crate::style::styled;
+use crate::{exit_with, file_name, ChapterSet, LoadError};
+use ansi_term::Color;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{thread_rng, Rng, SeedableRng};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use terms::{Chapter, FillInTheBlankQuestion, MultipleChoiceQuestion, Questions, Status};
+
+#[derive(clap::Args)]
+pub struct GenerateArgs {
+    #[command(subcommand)]
+    command: GenerateCommand,
+}
+
+#[derive(clap::Subcommand)]
+enum GenerateCommand {
+    /// 빈칸, 짝짓기 문제를 같은 챕터의 다른 답을 오답 보기로 삼아 객관식 문제로 바꿉니다
+    Mc(McArgs),
+    /// 짝짓기의 각 짝으로 정의(또는 `sentence` 예문) 속의 용어를 빈칸으로 만든 빈칸 문제를 만듭니다
+    Cloze(ClozeArgs),
+}
+
+#[derive(clap::Args)]
+struct Common {
+    /// 고칠 YAML 문제 파일. 만든 문제는 같은 챕터에 덧붙입니다
+    file: PathBuf,
+    /// 이 챕터들의 문제만 바꿉니다 (예: 1-3,5)
+    #[arg(long)]
+    chapters: Option<ChapterSet>,
+    /// 만든 문제를 작성 중(draft)으로 표시해서 검토하기 전에는 출제되지 않게 합니다
+    #[arg(long)]
+    draft: bool,
+    /// 파일을 고치지 않고 만든 문제만 출력합니다
+    #[arg(long)]
+    dry_run: bool,
+}
+
+impl Common {
+    fn includes(&self, chapter: &Chapter) -> bool {
+        self.chapters
+            .as_ref()
+            .is_none_or(|ChapterSet(chapters)| chapters.contains(&chapter.chapter))
+    }
+
+    fn status(&self, source: Status) -> Status {
+        if self.draft {
+            Status::Draft
+        } else {
+            source
+        }
+    }
+}
+
+#[derive(clap::Args)]
+struct McArgs {
+    #[command(flatten)]
+    common: Common,
+    /// 문제마다 보여줄 보기 수 (정답 포함, 2~8)
+    #[arg(long, value_name = "N", default_value_t = 4, value_parser = clap::value_parser!(u8).range(2..=8))]
+    options: u8,
+    /// 오답 보기를 고르는 시드. 같은 시드면 항상 같은 보기가 나옵니다
+    #[arg(long, value_name = "N")]
+    seed: Option<u64>,
+}
+
+#[derive(clap::Args)]
+struct ClozeArgs {
+    #[command(flatten)]
+    common: Common,
+}
+
+pub fn run(args: GenerateArgs) {
+    match args.command {
+        GenerateCommand::Mc(args) => mc(args),
+        GenerateCommand::Cloze(args) => cloze(args),
+    }
+}
+
+// 객관식으로 바꿀 문제 하나. 오답 보기는 같은 유형의 답 `pool` 에서 고릅니다
+struct Source<'a> {
+    question: String,
+    answer: &'a str,
+    tags: &'a [String],
+    status: Status,
+}
+
+fn sources(chapter: &Chapter) -> Vec<(Vec<Source<'_>>, Vec<&str>)> {
+    let blanks: Vec<_> = chapter
+        .fill_in_the_blanks
+        .iter()
+        .filter(|q| {
+            q.status != Status::Retired && q.blank_count() == 1 && !q.answer.primary().contains(',')
+        })
+        .collect();
+    let groups: Vec<_> = chapter
+        .matching
+        .iter()
+        .filter(|q| q.status != Status::Retired)
+        .collect();
+    vec![
+        (
+            blanks
+                .iter()
+                .map(|q| Source {
+                    question: q.question.clone(),
+                    answer: q.answer.primary().trim(),
+                    tags: &q.tags,
+                    status: q.status,
+                })
+                .collect(),
+            blanks.iter().map(|q| q.answer.primary().trim()).collect(),
+        ),
+        (
+            groups
+                .iter()
+                .flat_map(|q| {
+                    q.pairs.iter().map(|pair| Source {
+                        question: format!("다음 정의에 맞는 용어를 고르세요: {}", pair.definition),
+                        answer: pair.term.trim(),
+                        tags: &q.tags,
+                        status: q.status,
+                    })
+                })
+                .collect(),
+            groups
+                .iter()
+                .flat_map(|q| q.pairs.iter().map(|pair| pair.term.trim()))
+                .collect(),
+        ),
+    ]
+}
+
+// 정답과 길이가 비슷한 다른 답 몇 개 중에서 오답 보기 `count` 개를 고릅니다
+fn distractors(
+    answer: &str,
+    pool: &[&str],
+    count: usize,
+    rng: &mut impl Rng,
+) -> Option<Vec<String>> {
+    let mut candidates: Vec<&str> = Vec::new();
+    for candidate in pool {
+        if !candidate.eq_ignore_ascii_case(answer)
+            && !candidates.iter().any(|c| c.eq_ignore_ascii_case(candidate))
+        {
+            candidates.push(candidate);
+        }
+    }
+    if candidates.len() < count {
+        return None;
+    }
+    let length = answer.chars().count() as i64;
+    candidates.shuffle(rng);
+    candidates.sort_by_key(|c| (c.chars().count() as i64 - length).abs());
+    candidates.truncate(count * 2);
+    Some(
+        candidates
+            .choose_multiple(rng, count)
+            .map(|c| c.to_string())
+            .collect(),
+    )
+}
+
+fn open(common: &Common) -> Option<(String, Questions)> {
+    let data = match fs::read_to_string(&common.file) {
+        Ok(data) => data,
+        Err(e) => {
+            println!(
+                "{} {}",
+                styled(Color::Red).paint("파일을 읽을 수 없습니다:"),
+                e
+            );
+            return None;
+        }
+    };
+    match Questions::parse(&data) {
+        Ok(questions) => Some((data, questions)),
+        Err(e) => {
+            println!("{} {}", styled(Color::Red).paint("YAML 파싱 실패:"), e);
+            None
+        }
+    }
+}
+
+// 만든 문제를 챕터마다 `key` 목록에 덧붙여 저장하거나, `--dry-run` 이면 출력만 합니다
+fn save(common: &Common, data: String, key: &str, generated: &[(u32, impl Serialize)]) {
+    if common.dry_run {
+        let mut last = None;
+        for (chapter, question) in generated {
+            if last != Some(chapter) {
+                println!("# 챕터 {}", chapter);
+                last = Some(chapter);
+            }
+            print!("{}", serde_yaml::to_string(&[question]).unwrap());
+        }
+        return;
+    }
+    if generated.is_empty() {
+        return;
+    }
+    write(
+        &common.file,
+        data,
+        generated
+            .iter()
+            .map(|(chapter, question)| (*chapter, key, serde_yaml::to_value(question).unwrap())),
+    );
+}
+
+// 만든 문제를 챕터의 `key` 목록마다 한꺼번에 끼워 넣고 파일에 씁니다.
+// 끼워 넣은 결과는 한 번만 다시 읽어 확인하고, 맞지 않으면 파일 전체를 다시 씁니다
+pub fn write<'a>(
+    file: &Path,
+    data: String,
+    generated: impl IntoIterator<Item = (u32, &'a str, serde_yaml::Value)>,
+) {
+    let current = Questions::parse(&data).unwrap_or_else(|source| {
+        exit_with(LoadError::Parse {
+            file: file_name(Some(file)),
+            source,
+        })
+    });
+    let mut lists: BTreeMap<(u32, &str), Vec<serde_yaml::Value>> = BTreeMap::new();
+    for (chapter, key, value) in generated {
+        lists.entry((chapter, key)).or_default().push(value);
+    }
+
+    let spliced = lists
+        .iter()
+        .try_fold(data.clone(), |text, ((chapter, key), new)| {
+            insert(&text, *chapter, key, new)
+        })
+        .filter(|text| {
+            Questions::parse(text).is_ok_and(|after| {
+                lists.iter().all(|((chapter, key), new)| {
+                    count(&after, *chapter, key) == count(&current, *chapter, key) + new.len()
+                })
+            })
+        });
+    let data = match spliced {
+        Some(text) => text,
+        None => {
+            println!(
+                "{}",
+                styled(Color::Yellow)
+                    .paint("원래 서식을 유지할 수 없어 파일 전체를 다시 썼습니다.")
+            );
+            rewrite(
+                &data,
+                lists
+                    .iter()
+                    .map(|((chapter, key), new)| (*chapter, *key, new.as_slice())),
+            )
+        }
+    };
+    if let Err(e) = fs::write(file, &data) {
+        println!(
+            "{} {}",
+            styled(Color::Red).paint("파일을 쓸 수 없습니다:"),
+            e
+        );
+    }
+}
+
+fn mc(args: McArgs) {
+    let Some((data, questions)) = open(&args.common) else {
+        return;
+    };
+    let mut rng = StdRng::seed_from_u64(args.seed.unwrap_or_else(|| thread_rng().gen()));
+
+    let mut generated = Vec::new();
+    let (mut short, mut existing) = (0, 0);
+    for chapter in &questions.chapters {
+        if !args.common.includes(chapter) {
+            continue;
+        }
+        for (sources, pool) in sources(chapter) {
+            for source in sources {
+                // 이미 바꿔 둔 문제는 다시 만들지 않습니다
+                if chapter
+                    .multiple_choice
+                    .iter()
+                    .chain(
+                        generated
+                            .iter()
+                            .filter(|(c, _)| *c == chapter.chapter)
+                            .map(|(_, q)| q),
+                    )
+                    .any(|q| q.question == source.question)
+                {
+                    existing += 1;
+                    continue;
+                }
+                let Some(mut options) =
+                    distractors(source.answer, &pool, args.options as usize - 1, &mut rng)
+                else {
+                    short += 1;
+                    continue;
+                };
+                let index = rng.gen_range(0..=options.len());
+                options.insert(index, source.answer.to_string());
+                generated.push((
+                    chapter.chapter,
+                    MultipleChoiceQuestion {
+                        question: source.question,
+                        id: None,
+                        variants: Vec::new(),
+                        options,
+                        answer: ((b'a' + index as u8) as char).into(),
+                        tags: source.tags.to_vec(),
+                        status: args.common.status(source.status),
+                        points: None,
+                        weight: None,
+                        source: None,
+                        page: None,
+                        explanation: None,
+                        translations: BTreeMap::new(),
+                    },
+                ));
+            }
+        }
+    }
+
+    save(&args.common, data, "multiple_choice", &generated);
+
+    println!(
+        "객관식 문제 {}개를 만들었습니다.",
+        styled(Color::Green).paint(generated.len().to_string())
+    );
+    if existing > 0 {
+        println!(
+            "이미 객관식으로 바꿔 둔 문제 {}개는 건너뛰었습니다.",
+            existing
+        );
+    }
+    if short > 0 {
+        println!(
+            "{}",
+            styled(Color::Yellow).paint(format!(
+                "같은 챕터에 다른 답이 {}개보다 적어 {}개는 만들지 못했습니다.",
+                args.options - 1,
+                short
+            ))
+        );
+    }
+}
+
+// 용어가 나오는 곳(바이트 범위)을 영문 대소문자를 가리지 않고 모두 찾습니다.
+// "art" 가 "heart" 에 걸리지 않게 영문 단어 중간은 건너뛰지만, 한글은 조사가 붙으므로 그대로 찾습니다
+fn occurrences(text: &str, term: &str) -> Vec<(usize, usize)> {
+    let word = |c: Option<char>| c.is_some_and(|c| c.is_ascii_alphanumeric());
+    let mut found = Vec::new();
+    let mut start = 0;
+    while let Some(offset) = text[start..].char_indices().find_map(|(i, _)| {
+        let at = start + i;
+        let rest = &text[at..];
+        let matched = rest.len() >= term.len()
+            && rest.is_char_boundary(term.len())
+            && rest[..term.len()].eq_ignore_ascii_case(term);
+        let inside = || {
+            (word(term.chars().next()) && word(text[..at].chars().next_back()))
+                || (word(term.chars().next_back()) && word(rest[term.len()..].chars().next()))
+        };
+        (matched && !inside()).then_some(i)
+    }) {
+        found.push((start + offset, start + offset + term.len()));
+        start += offset + term.len();
+    }
+    found
+}
+
+// 문장 속의 용어를 빈칸으로 바꾼 문제와 그 답. 문장에 용어가 나오지 않으면 None
+pub fn blank_out(text: &str, term: &str) -> Option<(String, String)> {
+    // 빈 용어는 어디에나 맞아서 찾기가 끝나지 않습니다
+    let term = term.trim();
+    if term.is_empty() {
+        return None;
+    }
+    let found = occurrences(text, term);
+    if found.is_empty() {
+        return None;
+    }
+    let mut question = String::new();
+    let mut last = 0;
+    for (start, end) in &found {
+        question += &text[last..*start];
+        question += "_____";
+        last = *end;
+    }
+    question += &text[last..];
+    // 용어가 여러 번 나오면 빈칸마다 같은 답을 적습니다
+    let answer = found
+        .iter()
+        .map(|(start, end)| &text[*start..*end])
+        .collect::<Vec<_>>()
+        .join(", ");
+    Some((question, answer))
+}
+
+fn cloze(args: ClozeArgs) {
+    let Some((data, questions)) = open(&args.common) else {
+        return;
+    };
+
+    let mut generated: Vec<(u32, FillInTheBlankQuestion)> = Vec::new();
+    let (mut missing, mut existing) = (0, 0);
+    for chapter in &questions.chapters {
+        if !args.common.includes(chapter) {
+            continue;
+        }
+        for group in chapter
+            .matching
+            .iter()
+            .filter(|q| q.status != Status::Retired)
+        {
+            for pair in &group.pairs {
+                let text = pair.sentence.as_deref().unwrap_or(&pair.definition);
+                let Some((question, answer)) = blank_out(text, &pair.term) else {
+                    missing += 1;
+                    continue;
+                };
+
+                // 이미 만들어 둔 문제는 다시 만들지 않습니다
+                if chapter
+                    .fill_in_the_blanks
+                    .iter()
+                    .chain(
+                        generated
+                            .iter()
+                            .filter(|(c, _)| *c == chapter.chapter)
+                            .map(|(_, q)| q),
+                    )
+                    .any(|q| q.question == question)
+                {
+                    existing += 1;
+                    continue;
+                }
+                generated.push((
+                    chapter.chapter,
+                    FillInTheBlankQuestion {
+                        question,
+                        id: None,
+                        variants: Vec::new(),
+                        answer: answer.into(),
+                        tags: group.tags.clone(),
+                        status: args.common.status(group.status),
+                        points: None,
+                        weight: None,
+                        source: None,
+                        page: None,
+                        explanation: None,
+                        translations: BTreeMap::new(),
+                        normalize: group.normalize.clone(),
+                    },
+                ));
+            }
+        }
+    }
+
+    save(&args.common, data, "fill_in_the_blanks", &generated);
+
+    println!(
+        "빈칸 문제 {}개를 만들었습니다.",
+        styled(Color::Green).paint(generated.len().to_string())
+    );
+    if existing > 0 {
+        println!("이미 만들어 둔 문제 {}개는 건너뛰었습니다.", existing);
+    }
+    if missing > 0 {
+        println!(
+            "{}",
+            styled(Color::Yellow).paint(format!(
+                "정의에 용어가 나오지 않는 짝 {}개는 만들지 못했습니다. 용어가 들어간 예문을 `sentence` 로 적어 주세요.",
+                missing
+            ))
+        );
+    }
+}
@@ -0,0 +1,193 @@
+use crate::style::styled;
+use crate::{
+    config, format_duration, history, notes, suspend, ChapterSet, Prompter, QuizArgs, Source,
+};
+use ansi_term::Color;
+use rand::seq::SliceRandom;
+use rand::{thread_rng, Rng};
+use std::collections::HashSet;
+use std::time::Instant;
+use terms::{Askable, MatchingQuestion, SingleMatchingQuestion};
+
+// 용어마다 연달아 이만큼 맞혀야 끝납니다
+const STREAK: usize = 2;
+
+// 짝짓기 묶음 하나의 용어들을 무작위 방향(용어 → 정의, 정의 → 용어)으로 돌아가며 묻습니다
+pub fn drill(args: &QuizArgs, name: &str) {
+    let config = config::Config::load();
+    let source = Source::open(args);
+    let selected: HashSet<u32> = match &args.chapters {
+        Some(ChapterSet(chapters)) => chapters.iter().copied().collect(),
+        None => source.chapters().into_iter().collect(),
+    };
+    let chapters = source.load(&selected, args);
+    let found: Vec<(u32, &MatchingQuestion)> = chapters
+        .iter()
+        .flat_map(|c| {
+            c.matching
+                .iter()
+                .filter(|m| m.question.trim() == name.trim())
+                .map(move |m| (c.chapter, m))
+        })
+        .collect();
+    let (chapter, group) = match found[..] {
+        [found] => found,
+        [] => {
+            println!("'{}' 짝짓기 묶음이 없습니다. 있는 묶음:", name);
+            for c in &chapters {
+                for m in &c.matching {
+                    println!("- [챕터 {}] {}", c.chapter, m.question);
+                }
+            }
+            return;
+        }
+        _ => {
+            println!(
+                "여러 챕터에 '{}' 묶음이 있습니다. `--chapters` 로 하나만 고르세요.",
+                name
+            );
+            return;
+        }
+    };
+
+    let romanized = args.romanized.or(config.romanized);
+    let mut converting = romanized.is_some();
+    let mut notes = notes::load().unwrap_or_default();
+    let mut rng = thread_rng();
+    let terms = &group.pairs;
+    // 뺀 용어는 묻지 않고 끝낸 용어로도 세지 않습니다
+    let mut suspended = suspend::load().unwrap_or_default();
+    let forward = |i: usize| SingleMatchingQuestion::new(terms[i].term.clone(), group);
+    let mut muted: Vec<bool> = (0..terms.len())
+        .map(|i| suspended.contains_key(&forward(i).id()))
+        .collect();
+    let mut streaks = vec![0; terms.len()];
+    let mut recorded = vec![false; terms.len()];
+    let mut answers = Vec::new();
+    let mut asked = 0;
+    let started_at = chrono::Local::now();
+    let start = Instant::now();
+    'drill: loop {
+        let mut pending: Vec<usize> = (0..terms.len())
+            .filter(|&i| streaks[i] < STREAK && !muted[i])
+            .collect();
+        if pending.is_empty() {
+            break;
+        }
+        pending.shuffle(&mut rng);
+        for i in pending {
+            if muted[i] {
+                continue;
+            }
+            let (done, total) = progress(&streaks, &muted);
+            println!(
+                "{} {}",
+                styled(Color::Yellow.bold()).paint(&group.question),
+                styled(Color::Yellow).paint(format!("({}/{} 완료)", done, total))
+            );
+            let term = terms[i].term.clone();
+            let question: Box<dyn Askable> = match rng.gen_bool(0.5) {
+                true => Box::new(SingleMatchingQuestion::new(term, group)),
+                false => Box::new(SingleMatchingQuestion::reversed(term, group)),
+            };
+            let question = match args.recall {
+                true => question.recall().unwrap_or(question),
+                false => question,
+            };
+            let mut prompter = Prompter::new(false, args.tui, None, config.keys.clone());
+            prompter.typo = config.typo;
+            prompter.romanized = romanized;
+            prompter.converting = converting;
+            prompter.reference = question.reference();
+            prompter.explanation = question.explanation().map(str::to_string);
+            let asked_at = Instant::now();
+            let result = question.ask(&mut prompter, &mut rng);
+            converting = prompter.converting;
+            let question = (question, chapter);
+            prompter.save_notes(&mut notes, &args.bank_name(), &question);
+            if prompter.quit {
+                break 'drill;
+            }
+            if prompter.suspended {
+                let forward = forward(i);
+                if let Err(e) = suspend::add(&mut suspended, &forward, chapter, &args.bank_name()) {
+                    println!(
+                        "{} {}\n",
+                        styled(Color::Red).paint("뺀 문제 목록을 저장할 수 없습니다:"),
+                        e
+                    );
+                }
+                muted[i] = true;
+                continue;
+            }
+            asked += 1;
+            streaks[i] = if result.is_correct() {
+                streaks[i] + 1
+            } else {
+                0
+            };
+            if streaks[i] > 0 {
+                println!(
+                    "{}\n",
+                    styled(Color::Cyan).paint(format!("연속 {}/{}", streaks[i], STREAK))
+                );
+            }
+            // 기록에는 용어마다 처음 답한 것만 남깁니다
+            if !recorded[i] {
+                recorded[i] = true;
+                answers.push(history::Answer {
+                    id: question.0.id(),
+                    text: question.0.text().to_string(),
+                    chapter,
+                    kind: question.0.kind(),
+                    group: question.0.group().map(str::to_string),
+                    correct: result.is_correct(),
+                    score: Some(result),
+                    time_ms: asked_at.elapsed().as_millis() as u64,
+                    lifeline: false,
+                    options: history::options(question.0.as_ref()),
+                    given: history::given(question.0.as_ref(), &prompter.given),
+                    expected: question.0.correct_answer(),
+                });
+            }
+        }
+    }
+
+    let (done, total) = progress(&streaks, &muted);
+    if done == total {
+        println!(
+            "{} ({}번 답함, {})",
+            styled(Color::Green.bold())
+                .paint(format!("모든 용어를 {}번 연속으로 맞혔습니다!", STREAK)),
+            asked,
+            format_duration(start.elapsed())
+        );
+    } else {
+        println!("{}/{} 용어를 끝냈습니다.", done, total);
+    }
+    if answers.is_empty() {
+        return;
+    }
+    let record = history::Session {
+        bank: args.bank_name(),
+        started_at,
+        duration_secs: start.elapsed().as_secs_f64(),
+        answers,
+    };
+    if let Err(e) = history::append(record) {
+        println!(
+            "{} {}",
+            styled(Color::Red).paint("학습 기록을 저장할 수 없습니다:"),
+            e
+        );
+    }
+}
+
+// 뺀 용어를 빼고 끝낸 용어 수와 전체 용어 수
+fn progress(streaks: &[usize], muted: &[bool]) -> (usize, usize) {
+    let active = || streaks.iter().zip(muted).filter(|(_, &muted)| !muted);
+    (
+        active().filter(|(&streak, _)| streak >= STREAK).count(),
+        active().count(),
+    )
+}
@@ -0,0 +1,381 @@
+// 한글 음절을 자모로 나눠 비교합니다. 한 자모만 틀린 답을 완전히 틀린 답과 구별하려고 씁니다
+
+const BASE: u32 = 0xAC00;
+const LAST: u32 = 0xD7A3;
+const MEDIALS: u32 = 21;
+const FINALS: u32 = 28;
+
+// 음절의 (초성, 중성, 종성) 번호. 종성이 없으면 0
+fn components(c: char) -> Option<(u32, u32, u32)> {
+    let code = c as u32;
+    if !(BASE..=LAST).contains(&code) {
+        return None;
+    }
+    let index = code - BASE;
+    Some((
+        index / (MEDIALS * FINALS),
+        index % (MEDIALS * FINALS) / FINALS,
+        index % FINALS,
+    ))
+}
+
+// 음절을 첫가끝 자모로 풀어 씁니다. 한글이 아닌 글자는 그대로 둡니다
+pub fn decompose(text: &str) -> Vec<char> {
+    let mut jamo = Vec::new();
+    for c in text.chars() {
+        match components(c) {
+            Some((initial, medial, last)) => {
+                jamo.push(char::from_u32(0x1100 + initial).unwrap());
+                jamo.push(char::from_u32(0x1161 + medial).unwrap());
+                if last > 0 {
+                    jamo.push(char::from_u32(0x11A7 + last).unwrap());
+                }
+            }
+            None => jamo.push(c.to_ascii_lowercase()),
+        }
+    }
+    jamo
+}
+
+fn distance(a: &[char], b: &[char]) -> usize {
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, x) in a.iter().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, y) in b.iter().enumerate() {
+            let replaced = diagonal + usize::from(x != y);
+            diagonal = row[j + 1];
+            row[j + 1] = replaced.min(row[j] + 1).min(row[j + 1] + 1);
+        }
+    }
+    row[b.len()]
+}
+
+// 한 자모(한글이 아니면 한 글자)만 틀린 답이 어디를 틀렸는지
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Slip {
+    Initial,
+    Medial,
+    Final,
+    Other,
+}
+
+impl Slip {
+    pub fn message(self) -> &'static str {
+        match self {
+            Slip::Initial => "초성(첫소리)이 틀렸습니다",
+            Slip::Medial => "모음이 틀렸습니다",
+            Slip::Final => "받침이 틀렸습니다",
+            Slip::Other => "한 글자만 틀렸습니다",
+        }
+    }
+}
+
+// 자모 단위로 한 번만 고치면 정답이 되는 답이면 어디를 틀렸는지 돌려줍니다
+pub fn slip(typed: &str, correct: &str) -> Option<Slip> {
+    let (typed, correct) = (typed.trim(), correct.trim());
+    if distance(&decompose(typed), &decompose(correct)) != 1 {
+        return None;
+    }
+    let a: Vec<char> = typed.chars().collect();
+    let b: Vec<char> = correct.chars().collect();
+    if a.len() != b.len() {
+        return Some(Slip::Other);
+    }
+    let mut differ = a.iter().zip(&b).filter(|(x, y)| x != y);
+    let (Some((x, y)), None) = (differ.next(), differ.next()) else {
+        return Some(Slip::Other);
+    };
+    Some(match (components(*x), components(*y)) {
+        (Some(x), Some(y)) if x.0 != y.0 => Slip::Initial,
+        (Some(x), Some(y)) if x.1 != y.1 => Slip::Medial,
+        (Some(_), Some(_)) => Slip::Final,
+        _ => Slip::Other,
+    })
+}
+
+const INITIALS: &str = "ㄱㄲㄴㄷㄸㄹㅁㅂㅃㅅㅆㅇㅈㅉㅊㅋㅌㅍㅎ";
+const VOWELS: &str = "ㅏㅐㅑㅒㅓㅔㅕㅖㅗㅘㅙㅚㅛㅜㅝㅞㅟㅠㅡㅢㅣ";
+const LASTS: &str = " ㄱㄲㄳㄴㄵㄶㄷㄹㄺㄻㄼㄽㄾㄿㅀㅁㅂㅄㅅㅆㅇㅈㅊㅋㅌㅍㅎ";
+
+fn position(table: &str, jamo: char) -> Option<u32> {
+    table.chars().position(|c| c == jamo).map(|i| i as u32)
+}
+
+fn join(pair: (char, char), table: &[(char, char, char)]) -> Option<char> {
+    table
+        .iter()
+        .find(|(a, b, _)| (*a, *b) == pair)
+        .map(|(_, _, joined)| *joined)
+}
+
+const DOUBLE_VOWELS: [(char, char, char); 7] = [
+    ('ㅗ', 'ㅏ', 'ㅘ'),
+    ('ㅗ', 'ㅐ', 'ㅙ'),
+    ('ㅗ', 'ㅣ', 'ㅚ'),
+    ('ㅜ', 'ㅓ', 'ㅝ'),
+    ('ㅜ', 'ㅔ', 'ㅞ'),
+    ('ㅜ', 'ㅣ', 'ㅟ'),
+    ('ㅡ', 'ㅣ', 'ㅢ'),
+];
+
+const DOUBLE_LASTS: [(char, char, char); 11] = [
+    ('ㄱ', 'ㅅ', 'ㄳ'),
+    ('ㄴ', 'ㅈ', 'ㄵ'),
+    ('ㄴ', 'ㅎ', 'ㄶ'),
+    ('ㄹ', 'ㄱ', 'ㄺ'),
+    ('ㄹ', 'ㅁ', 'ㄻ'),
+    ('ㄹ', 'ㅂ', 'ㄼ'),
+    ('ㄹ', 'ㅅ', 'ㄽ'),
+    ('ㄹ', 'ㅌ', 'ㄾ'),
+    ('ㄹ', 'ㅍ', 'ㄿ'),
+    ('ㄹ', 'ㅎ', 'ㅀ'),
+    ('ㅂ', 'ㅅ', 'ㅄ'),
+];
+
+// 자모를 하나씩 받아 음절로 모읍니다 (두벌식 자판이 글자를 조합하는 방식)
+#[derive(Default)]
+struct Composer {
+    out: String,
+    initial: Option<char>,
+    vowel: Option<char>,
+    // 겹받침이면 두 자모를 따로 기억해 두었다가 모음이 오면 뒤의 것을 다음 음절로 넘깁니다
+    last: Vec<char>,
+}
+
+impl Composer {
+    fn flush(&mut self) {
+        let last = match self.last.as_slice() {
+            [] => None,
+            [one] => Some(*one),
+            [a, b] => join((*a, *b), &DOUBLE_LASTS),
+            _ => None,
+        };
+        match (self.initial, self.vowel) {
+            (Some(initial), Some(vowel)) => {
+                let code = BASE
+                    + (position(INITIALS, initial).unwrap() * MEDIALS
+                        + position(VOWELS, vowel).unwrap())
+                        * FINALS
+                    + last.and_then(|l| position(LASTS, l)).unwrap_or(0);
+                self.out.push(char::from_u32(code).unwrap());
+            }
+            (initial, vowel) => self
+                .out
+                .extend(initial.into_iter().chain(vowel).chain(last)),
+        }
+        *self = Self {
+            out: std::mem::take(&mut self.out),
+            ..Default::default()
+        };
+    }
+
+    fn consonant(&mut self, c: char) {
+        let can_end = position(LASTS, c).is_some();
+        match (self.initial, self.vowel, self.last.as_slice()) {
+            (Some(_), Some(_), []) if can_end => self.last.push(c),
+            (Some(_), Some(_), [a]) if join((*a, c), &DOUBLE_LASTS).is_some() => self.last.push(c),
+            _ => {
+                self.flush();
+                self.initial = Some(c);
+            }
+        }
+    }
+
+    fn vowel(&mut self, v: char) {
+        if let Some(moved) = self.last.pop() {
+            self.flush();
+            self.initial = Some(moved);
+            self.vowel = Some(v);
+            return;
+        }
+        match self.vowel {
+            None if self.initial.is_some() => self.vowel = Some(v),
+            Some(prev) if join((prev, v), &DOUBLE_VOWELS).is_some() => {
+                self.vowel = join((prev, v), &DOUBLE_VOWELS);
+            }
+            _ => {
+                self.flush();
+                self.vowel = Some(v);
+            }
+        }
+    }
+
+    fn push(&mut self, jamo: char) {
+        if position(VOWELS, jamo).is_some() {
+            self.vowel(jamo);
+        } else if position(INITIALS, jamo).is_some() || position(LASTS, jamo).is_some() {
+            self.consonant(jamo);
+        } else {
+            self.flush();
+            self.out.push(jamo);
+        }
+    }
+
+    fn finish(mut self) -> String {
+        self.flush();
+        self.out
+    }
+}
+
+// 한글 자판(두벌식)이 없는 터미널에서 영문 자판으로 친 글쇠를 한글로 바꿉니다 (예: tlawkd → 심장)
+pub fn from_dubeolsik(keys: &str) -> String {
+    const LOWER: &str = "ㅁㅠㅊㅇㄷㄹㅎㅗㅑㅓㅏㅣㅡㅜㅐㅔㅂㄱㄴㅅㅕㅍㅈㅌㅛㅋ";
+    let mut composer = Composer::default();
+    for key in keys.chars() {
+        let jamo = match key {
+            'Q' => 'ㅃ',
+            'W' => 'ㅉ',
+            'E' => 'ㄸ',
+            'R' => 'ㄲ',
+            'T' => 'ㅆ',
+            'O' => 'ㅒ',
+            'P' => 'ㅖ',
+            c if c.is_ascii_alphabetic() => LOWER
+                .chars()
+                .nth((c.to_ascii_lowercase() as u8 - b'a') as usize)
+                .unwrap(),
+            c => c,
+        };
+        composer.push(jamo);
+    }
+    composer.finish()
+}
+
+// 로마자(국어의 로마자 표기법, 매큔-라이샤워 일부)로 적은 말을 한글로 바꿉니다 (예: simjang → 심장).
+// 받침은 소리 나는 대로가 아니라 글자대로 적어야 합니다 (옷 → os). `-` 나 `'` 로 음절을 나눌 수 있습니다
+pub fn from_romanization(text: &str) -> String {
+    const INITIAL: [(&str, char); 22] = [
+        ("kk", 'ㄲ'),
+        ("tt", 'ㄸ'),
+        ("pp", 'ㅃ'),
+        ("ss", 'ㅆ'),
+        ("jj", 'ㅉ'),
+        ("ch", 'ㅊ'),
+        ("k'", 'ㅋ'),
+        ("t'", 'ㅌ'),
+        ("p'", 'ㅍ'),
+        ("g", 'ㄱ'),
+        ("k", 'ㅋ'),
+        ("n", 'ㄴ'),
+        ("d", 'ㄷ'),
+        ("t", 'ㅌ'),
+        ("r", 'ㄹ'),
+        ("l", 'ㄹ'),
+        ("m", 'ㅁ'),
+        ("b", 'ㅂ'),
+        ("p", 'ㅍ'),
+        ("s", 'ㅅ'),
+        ("j", 'ㅈ'),
+        ("h", 'ㅎ'),
+    ];
+    const VOWEL: [(&str, char); 23] = [
+        ("yae", 'ㅒ'),
+        ("yeo", 'ㅕ'),
+        ("wae", 'ㅙ'),
+        ("ae", 'ㅐ'),
+        ("ya", 'ㅑ'),
+        ("eo", 'ㅓ'),
+        ("ye", 'ㅖ'),
+        ("wa", 'ㅘ'),
+        ("oe", 'ㅚ'),
+        ("yo", 'ㅛ'),
+        ("wo", 'ㅝ'),
+        ("we", 'ㅞ'),
+        ("wi", 'ㅟ'),
+        ("yu", 'ㅠ'),
+        ("eu", 'ㅡ'),
+        ("ui", 'ㅢ'),
+        ("ŏ", 'ㅓ'),
+        ("ŭ", 'ㅡ'),
+        ("a", 'ㅏ'),
+        ("e", 'ㅔ'),
+        ("o", 'ㅗ'),
+        ("u", 'ㅜ'),
+        ("i", 'ㅣ'),
+    ];
+    const LAST: [(&str, char); 32] = [
+        ("lk", 'ㄺ'),
+        ("lg", 'ㄺ'),
+        ("lm", 'ㄻ'),
+        ("lb", 'ㄼ'),
+        ("lp", 'ㄼ'),
+        ("ls", 'ㄽ'),
+        ("lt", 'ㄾ'),
+        ("lh", 'ㅀ'),
+        ("gs", 'ㄳ'),
+        ("ks", 'ㄳ'),
+        ("nj", 'ㄵ'),
+        ("nh", 'ㄶ'),
+        ("bs", 'ㅄ'),
+        ("ps", 'ㅄ'),
+        ("ng", 'ㅇ'),
+        ("kk", 'ㄲ'),
+        ("ss", 'ㅆ'),
+        ("ch", 'ㅊ'),
+        ("p'", 'ㅍ'),
+        ("g", 'ㄱ'),
+        ("k", 'ㄱ'),
+        ("n", 'ㄴ'),
+        ("d", 'ㄷ'),
+        ("t", 'ㄷ'),
+        ("l", 'ㄹ'),
+        ("r", 'ㄹ'),
+        ("m", 'ㅁ'),
+        ("b", 'ㅂ'),
+        ("p", 'ㅂ'),
+        ("s", 'ㅅ'),
+        ("j", 'ㅈ'),
+        ("h", 'ㅎ'),
+    ];
+    let starts = |rest: &str, table: &[(&str, char)]| {
+        table
+            .iter()
+            .find(|(roman, _)| rest.starts_with(roman))
+            .map(|(roman, jamo)| (roman.len(), *jamo))
+    };
+
+    let lower = text.to_lowercase();
+    let mut out = String::new();
+    let mut rest = lower.as_str();
+    while !rest.is_empty() {
+        let (skip, initial) = starts(rest, &INITIAL).unwrap_or((0, 'ㅇ'));
+        let Some((length, vowel)) = starts(&rest[skip..], &VOWEL) else {
+            // 음절이 되지 않는 글자는 그대로 둡니다
+            let c = rest.chars().next().unwrap();
+            if c != '-' && c != '\'' {
+                out.push(c);
+            }
+            rest = &rest[c.len_utf8()..];
+            continue;
+        };
+        rest = &rest[skip + length..];
+        // 뒤에 모음이 오면 다음 음절의 첫소리로 넘깁니다
+        let last = LAST
+            .iter()
+            .filter(|(roman, _)| rest.starts_with(roman))
+            .map(|(roman, jamo)| (roman.len(), *jamo))
+            .find(|(length, _)| {
+                let after = &rest[*length..];
+                let next_initial = starts(after, &INITIAL).map_or(0, |(n, _)| n);
+                after.is_empty()
+                    || after.starts_with(['-', '\''])
+                    || starts(after, &VOWEL).is_none()
+                        && (next_initial == 0 || starts(&after[next_initial..], &VOWEL).is_some())
+            });
+        let last = match last {
+            Some((length, jamo)) => {
+                rest = &rest[length..];
+                position(LASTS, jamo).unwrap()
+            }
+            None => 0,
+        };
+        rest = rest.trim_start_matches(['-', '\'']);
+        let code = BASE
+            + (position(INITIALS, initial).unwrap() * MEDIALS + position(VOWELS, vowel).unwrap())
+                * FINALS
+            + last;
+        out.push(char::from_u32(code).unwrap());
+    }
+    out
+}
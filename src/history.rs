@@ -0,0 +1,169 @@
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use terms::score::Score;
+use terms::{Askable, Kind, Marker};
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Answer {
+    pub id: String,
+    #[serde(default)]
+    pub text: String,
+    pub chapter: u32,
+    pub kind: Kind,
+    // 짝짓기 문제면 그 용어가 들어 있던 묶음의 이름
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group: Option<String>,
+    pub correct: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub score: Option<Score>,
+    pub time_ms: u64,
+    // 50:50 으로 보기를 지우고 답했는지
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub lifeline: bool,
+    // `terms replay` 로 다시 볼 수 있도록 보여준 보기, 낸 답(다시 답한 것까지 차례로), 정답을 적어 둡니다
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub options: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub given: Vec<String>,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub expected: String,
+}
+
+// 보기는 화면에 보인 모양(`a. 보기`)으로 적어서 정답과 견줄 수 있게 합니다
+pub fn options(question: &dyn Askable) -> Vec<String> {
+    let Some((options, marker)) = question.choices() else {
+        return Vec::new();
+    };
+    options
+        .iter()
+        .enumerate()
+        .map(|(i, option)| shown(marker, i, option))
+        .collect()
+}
+
+// 보기 기호로 낸 답은 그 보기로 바꿔 적습니다
+pub fn given(question: &dyn Askable, answers: &[String]) -> Vec<String> {
+    let choices = question
+        .choices()
+        .filter(|(_, marker)| !matches!(marker, Marker::None));
+    answers
+        .iter()
+        .filter(|answer| !answer.trim().is_empty())
+        .map(|answer| {
+            let chosen = choices.and_then(|(options, marker)| {
+                (0..options.len()).find(|&i| marker.label(i).eq_ignore_ascii_case(answer.trim()))
+            });
+            match (chosen, choices) {
+                (Some(i), Some((options, marker))) => shown(marker, i, &options[i]),
+                _ => answer.clone(),
+            }
+        })
+        .collect()
+}
+
+fn shown(marker: Marker, index: usize, option: &str) -> String {
+    match marker {
+        Marker::None => option.to_string(),
+        _ => format!("{}. {}", marker.label(index), option),
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Session {
+    #[serde(default = "default_bank")]
+    pub bank: String,
+    pub started_at: DateTime<Local>,
+    pub duration_secs: f64,
+    pub answers: Vec<Answer>,
+}
+
+impl Session {
+    // `terms replay` 에서 세션을 가리키는 ID. 시작한 시각으로 만듭니다
+    pub fn id(&self) -> String {
+        self.started_at.format("%Y%m%d-%H%M%S").to_string()
+    }
+}
+
+fn default_bank() -> String {
+    crate::BUILTIN_BANK.to_string()
+}
+
+static PROFILE: OnceLock<String> = OnceLock::new();
+
+// `--profile` 로 고른 사람의 기록을 따로 둡니다
+pub fn set_profile(name: String) {
+    PROFILE.set(name).ok();
+}
+
+pub fn parse_profile(name: &str) -> Result<String, String> {
+    let name = name.trim();
+    if name.is_empty() || name.starts_with('.') || name.contains(['/', '\\']) {
+        return Err("프로필 이름에는 / 나 \\ 를 쓸 수 없고 . 으로 시작할 수 없습니다".to_string());
+    }
+    Ok(name.to_string())
+}
+
+// 설정과 서명 키처럼 모든 프로필이 같이 쓰는 파일을 두는 곳
+pub fn home_dir() -> PathBuf {
+    if let Some(dir) = std::env::var_os("TERMS_HOME") {
+        return PathBuf::from(dir);
+    }
+    dirs::data_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("terms")
+}
+
+pub fn data_dir() -> PathBuf {
+    match PROFILE.get() {
+        Some(name) => home_dir().join("profiles").join(name),
+        None => home_dir(),
+    }
+}
+
+// 기본 프로필(`None`)과 `profiles/` 아래의 프로필들
+pub fn profiles() -> Vec<(Option<String>, PathBuf)> {
+    let mut profiles = vec![(None, home_dir())];
+    if let Ok(entries) = fs::read_dir(home_dir().join("profiles")) {
+        let mut named: Vec<_> = entries
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.path().is_dir())
+            .map(|entry| {
+                let name = entry.file_name().to_string_lossy().into_owned();
+                (Some(name), entry.path())
+            })
+            .collect();
+        named.sort();
+        profiles.extend(named);
+    }
+    profiles
+}
+
+fn history_path() -> PathBuf {
+    data_dir().join("history.yaml")
+}
+
+pub fn load() -> io::Result<Vec<Session>> {
+    load_from(&history_path())
+}
+
+pub fn load_from(path: &Path) -> io::Result<Vec<Session>> {
+    let data = match fs::read_to_string(path) {
+        Ok(data) => data,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+    serde_yaml::from_str(&data).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+pub fn append(session: Session) -> io::Result<()> {
+    let mut sessions = load()?;
+    sessions.push(session);
+    fs::create_dir_all(data_dir())?;
+    let data = serde_yaml::to_string(&sessions)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    fs::write(history_path(), data)
+}
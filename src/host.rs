@@ -0,0 +1,340 @@
+use crate::style::styled;
+use crate::web::{self, Reply, Submission};
+use crate::{config, get_user_input, QuizArgs};
+use ansi_term::Color;
+use rand::{thread_rng, Rng};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+use terms::score::{self, Score};
+use terms::{weight, Askable, Marker};
+use tiny_http::{Method, Request, Response, Server};
+
+#[derive(clap::Args)]
+pub struct HostArgs {
+    /// 참가자의 접속을 받을 주소
+    #[arg(long, value_name = "ADDR", default_value = "0.0.0.0:8080")]
+    bind: String,
+    /// 문제마다 답할 수 있는 시간(초)
+    #[arg(long, value_name = "SECS", default_value_t = 20)]
+    window: u64,
+    #[command(flatten)]
+    quiz: QuizArgs,
+}
+
+#[derive(Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum Phase {
+    Lobby,
+    Question,
+    Reveal,
+    Done,
+}
+
+struct Player {
+    name: String,
+    points: Score,
+    correct: usize,
+    // 동점일 때는 맞힌 문제에 답하는 데 걸린 시간이 짧은 쪽이 앞섭니다
+    time_ms: u64,
+    submission: Option<(Submission, u64)>,
+    last: Option<Score>,
+}
+
+// 서버 스레드와 진행자 화면이 같이 보는 상태. 문제 자체는 진행자 쪽에만 있습니다
+struct Game {
+    phase: Phase,
+    question: Value,
+    correct_answer: String,
+    asked_at: Instant,
+    deadline: Instant,
+    players: Vec<Player>,
+    tokens: HashMap<String, usize>,
+}
+
+impl Game {
+    fn leaderboard(&self) -> Vec<&Player> {
+        let mut players: Vec<&Player> = self.players.iter().collect();
+        players.sort_by(|a, b| {
+            b.points
+                .earned
+                .total_cmp(&a.points.earned)
+                .then(a.time_ms.cmp(&b.time_ms))
+        });
+        players
+    }
+
+    fn state(&self, token: &str) -> Option<Value> {
+        let &me = self.tokens.get(token)?;
+        let player = &self.players[me];
+        let leaderboard: Vec<Value> = self
+            .leaderboard()
+            .iter()
+            .map(|p| {
+                json!({
+                    "name": p.name,
+                    "points": score::points(p.points.earned),
+                    "correct": p.correct,
+                })
+            })
+            .collect();
+        let last = player.last.map(|s| {
+            json!({ "correct": s.is_correct(), "partial": s.is_partial(), "skipped": s.skipped })
+        });
+        // 문제를 내는 동안에는 정답을 보내지 않습니다
+        let asking = self.phase == Phase::Question;
+        Some(json!({
+            "phase": self.phase,
+            "name": player.name,
+            "question": if asking { self.question.clone() } else { Value::Null },
+            "remaining_secs": self.deadline.saturating_duration_since(Instant::now()).as_secs(),
+            "answered": player.submission.is_some(),
+            "correct_answer": if asking { "" } else { &self.correct_answer },
+            "last": last,
+            "leaderboard": leaderboard,
+        }))
+    }
+}
+
+#[derive(Deserialize)]
+struct Join {
+    name: String,
+}
+
+pub fn run(args: HostArgs) {
+    let config = config::Config::load();
+    let (questions, bank_points) = crate::deal(&args.quiz);
+    if questions.is_empty() {
+        println!("풀 문제가 없습니다.");
+        return;
+    }
+
+    let server = match Server::http(&args.bind) {
+        Ok(server) => server,
+        Err(e) => {
+            println!(
+                "{} {}",
+                styled(Color::Red).paint("서버를 열 수 없습니다:"),
+                e
+            );
+            return;
+        }
+    };
+    let game = Arc::new(Mutex::new(Game {
+        phase: Phase::Lobby,
+        question: Value::Null,
+        correct_answer: String::new(),
+        asked_at: Instant::now(),
+        deadline: Instant::now(),
+        players: Vec::new(),
+        tokens: HashMap::new(),
+    }));
+    {
+        let game = Arc::clone(&game);
+        thread::spawn(move || {
+            for mut request in server.incoming_requests() {
+                let response = handle(&game, &mut request);
+                request.respond(response).ok();
+            }
+        });
+    }
+
+    println!(
+        "참가자는 브라우저에서 http://{} 에 접속하세요.",
+        styled(Color::Cyan).paint(&args.bind)
+    );
+    get_user_input("모두 들어왔으면 Enter 를 눌러 시작하세요...");
+
+    let window = Duration::from_secs(args.window.max(1));
+    let total = questions.len();
+    for (number, (question, chapter)) in questions.iter().enumerate() {
+        let generated = question.generate().map(|g| Box::new(g) as Box<dyn Askable>);
+        let question = generated.as_ref().unwrap_or(question);
+        let text = question.prompt(&mut thread_rng());
+        let prompt = web::describe(question.as_ref(), &text, *chapter, number + 1, total);
+        println!(
+            "\n{} ({}/{})",
+            styled(Color::Yellow.bold()).paint(format!("챕터 {}", chapter)),
+            number + 1,
+            total
+        );
+        println!("{}", prompt["text"].as_str().unwrap_or_default());
+        if let Some((options, marker)) = question.choices() {
+            for (i, option) in options.iter().enumerate() {
+                match marker {
+                    Marker::None => println!("- {}", option),
+                    _ => println!("{}. {}", marker.label(i), option),
+                }
+            }
+        }
+
+        {
+            let mut game = game.lock().unwrap();
+            game.phase = Phase::Question;
+            game.question = prompt;
+            game.correct_answer = question.correct_answer();
+            game.asked_at = Instant::now();
+            game.deadline = game.asked_at + window;
+            for player in &mut game.players {
+                player.submission = None;
+                player.last = None;
+            }
+        }
+        wait_for_answers(&game);
+
+        let mut game = game.lock().unwrap();
+        let weight = weight(question.as_ref(), &bank_points, &config.points);
+        for player in &mut game.players {
+            let result = match player.submission.take() {
+                Some((submission, time_ms)) => {
+                    let result = match submission.resolve(question.as_ref()) {
+                        Some(answer) => question.grade(answer.trim()),
+                        None => Score::skipped(),
+                    };
+                    if result.earned > 0.0 {
+                        player.time_ms += time_ms;
+                    }
+                    result
+                }
+                None => Score::skipped(),
+            }
+            .weighted(weight);
+            if result.is_correct() {
+                player.correct += 1;
+            }
+            player.points += result;
+            player.last = Some(result);
+        }
+        game.phase = Phase::Reveal;
+        println!(
+            "정답은 {}",
+            styled(Color::Green).paint(question.correct_answer())
+        );
+        print_leaderboard(&game);
+        drop(game);
+
+        if number + 1 < total {
+            get_user_input("다음 문제로 넘어가려면 Enter 를 누르세요...");
+        }
+    }
+
+    game.lock().unwrap().phase = Phase::Done;
+    println!("\n{}", styled(Color::Yellow.bold()).paint("최종 순위"));
+    print_leaderboard(&game.lock().unwrap());
+    get_user_input("끝내려면 Enter 를 누르세요...");
+}
+
+// 시간이 다 되거나 모든 참가자가 답할 때까지 기다립니다
+fn wait_for_answers(game: &Mutex<Game>) {
+    let mut reported = 0;
+    loop {
+        thread::sleep(Duration::from_millis(200));
+        let game = game.lock().unwrap();
+        let answered = game
+            .players
+            .iter()
+            .filter(|p| p.submission.is_some())
+            .count();
+        if answered != reported {
+            println!("응답 {}/{}", answered, game.players.len());
+            reported = answered;
+        }
+        let everyone = !game.players.is_empty() && answered == game.players.len();
+        if everyone || Instant::now() >= game.deadline {
+            return;
+        }
+    }
+}
+
+fn print_leaderboard(game: &Game) {
+    for (rank, player) in game.leaderboard().iter().enumerate() {
+        let mark = match player.last {
+            Some(s) if s.skipped => styled(Color::Cyan).paint("-"),
+            Some(s) if s.is_correct() => styled(Color::Green).paint("O"),
+            Some(s) if s.is_partial() => styled(Color::Yellow).paint("△"),
+            Some(_) => styled(Color::Red).paint("X"),
+            None => styled(Color::White).paint(" "),
+        };
+        println!(
+            "{:>2}. {} {} {}점 ({}개 정답)",
+            rank + 1,
+            mark,
+            player.name,
+            score::points(player.points.earned),
+            player.correct
+        );
+    }
+}
+
+fn handle(game: &Mutex<Game>, request: &mut Request) -> Reply {
+    let mut body = String::new();
+    if request.as_reader().read_to_string(&mut body).is_err() {
+        return web::error(400, "요청을 읽을 수 없습니다");
+    }
+    let path: Vec<&str> = request
+        .url()
+        .split('?')
+        .next()
+        .unwrap_or("")
+        .split('/')
+        .filter(|s| !s.is_empty())
+        .collect();
+
+    let mut game = game.lock().unwrap();
+    match (request.method(), path.as_slice()) {
+        (Method::Get, []) => Response::from_string(include_str!("host.html"))
+            .with_header(web::header("text/html; charset=utf-8")),
+        (Method::Post, ["api", "players"]) => {
+            let name = match serde_json::from_str::<Join>(&body) {
+                Ok(join) => join.name.trim().to_string(),
+                Err(e) => return web::error(400, &e.to_string()),
+            };
+            if name.is_empty() {
+                return web::error(400, "이름을 입력하세요");
+            }
+            if game.players.iter().any(|p| p.name == name) {
+                return web::error(409, "이미 쓰고 있는 이름입니다");
+            }
+            let token = format!("{:016x}", thread_rng().gen::<u64>());
+            println!("{} 님이 참가했습니다.", styled(Color::Cyan).paint(&name));
+            let index = game.players.len();
+            game.tokens.insert(token.clone(), index);
+            game.players.push(Player {
+                name,
+                points: Score::default(),
+                correct: 0,
+                time_ms: 0,
+                submission: None,
+                last: None,
+            });
+            web::reply(201, json!({ "token": token }))
+        }
+        (Method::Get, ["api", "players", token]) => match game.state(token) {
+            Some(state) => web::reply(200, state),
+            None => web::error(404, "참가자를 찾을 수 없습니다"),
+        },
+        (Method::Post, ["api", "players", token, "answer"]) => {
+            let Some(&index) = game.tokens.get(*token) else {
+                return web::error(404, "참가자를 찾을 수 없습니다");
+            };
+            if game.phase != Phase::Question || Instant::now() >= game.deadline {
+                return web::error(409, "지금은 답할 수 없습니다");
+            }
+            if game.players[index].submission.is_some() {
+                return web::error(409, "이미 답했습니다");
+            }
+            match serde_json::from_str::<Submission>(&body) {
+                Ok(submission) => {
+                    let time_ms = game.asked_at.elapsed().as_millis() as u64;
+                    game.players[index].submission = Some((submission, time_ms));
+                    web::reply(200, json!({ "ok": true }))
+                }
+                Err(e) => web::error(400, &e.to_string()),
+            }
+        }
+        _ => web::error(404, "없는 주소입니다"),
+    }
+}
@@ -0,0 +1,375 @@
+use crate::config::{Config, ImportRules, Synthesis};
+use crate::generate::{blank_out, write};
+use crate::style::styled;
+use ansi_term::Color;
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use terms::{
+    Chapter, FillInTheBlankQuestion, MatchingPair, MatchingQuestion, Questions, SpellingQuestion,
+    Status,
+};
+
+#[derive(clap::Args)]
+pub struct ImportArgs {
+    #[command(subcommand)]
+    command: ImportCommand,
+}
+
+#[derive(clap::Subcommand)]
+enum ImportCommand {
+    /// 한 줄에 `용어<TAB>정의[<TAB>예문]` 을 적은 단어 목록으로 짝짓기, 빈칸, 철자 문제를 만듭니다
+    Wordlist(WordlistArgs),
+}
+
+#[derive(clap::Args)]
+struct WordlistArgs {
+    /// 탭으로 나눈 단어 목록 파일
+    file: PathBuf,
+    /// 만든 문제를 넣을 챕터
+    #[arg(long)]
+    chapter: u32,
+    /// 만든 문제를 덧붙일 YAML 문제 파일 (없으면 새로 만듭니다). 생략하면 단어 목록과 같은 이름의 .yaml
+    #[arg(long, short = 'o', value_name = "FILE")]
+    into: Option<PathBuf>,
+    /// 만들 유형 (여러 번 지정 가능). 생략하면 설정의 import.kinds
+    #[arg(long = "kind", value_enum)]
+    kinds: Vec<Synthesis>,
+    /// 만든 문제에 붙일 태그 (여러 번 지정 가능)
+    #[arg(long = "tag")]
+    tags: Vec<String>,
+    /// 만든 문제를 작성 중(draft)으로 표시해서 검토하기 전에는 출제되지 않게 합니다
+    #[arg(long)]
+    draft: bool,
+    /// 파일을 고치지 않고 만든 문제만 출력합니다
+    #[arg(long)]
+    dry_run: bool,
+}
+
+struct Entry {
+    term: String,
+    definition: String,
+    sentence: Option<String>,
+}
+
+pub fn run(args: ImportArgs) {
+    match args.command {
+        ImportCommand::Wordlist(args) => wordlist(args),
+    }
+}
+
+fn wordlist(args: WordlistArgs) {
+    let text = match fs::read_to_string(&args.file) {
+        Ok(text) => text,
+        Err(e) => {
+            println!(
+                "{} {}",
+                styled(Color::Red).paint("단어 목록을 읽을 수 없습니다:"),
+                e
+            );
+            std::process::exit(1);
+        }
+    };
+    let (entries, bad) = parse(&text);
+    for line in &bad {
+        println!(
+            "{} {}번째 줄",
+            styled(Color::Yellow).paint("탭으로 나눈 용어와 정의가 없어 건너뜁니다:"),
+            line
+        );
+    }
+    if entries.is_empty() {
+        println!("가져올 단어가 없습니다.");
+        return;
+    }
+
+    let target = args
+        .into
+        .clone()
+        .unwrap_or_else(|| args.file.with_extension("yaml"));
+    let data = match fs::read_to_string(&target) {
+        Ok(data) => data,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => "chapters:\n".to_string(),
+        Err(e) => {
+            println!(
+                "{} {}",
+                styled(Color::Red).paint("파일을 읽을 수 없습니다:"),
+                e
+            );
+            std::process::exit(1);
+        }
+    };
+    let questions = match Questions::parse(&data) {
+        Ok(questions) => questions,
+        Err(e) => {
+            println!("{} {}", styled(Color::Red).paint("YAML 파싱 실패:"), e);
+            std::process::exit(1);
+        }
+    };
+    let existing = questions
+        .chapters
+        .iter()
+        .find(|c| c.chapter == args.chapter);
+
+    let rules = Config::load().import;
+    let kinds = if args.kinds.is_empty() {
+        rules.kinds.clone()
+    } else {
+        args.kinds.clone()
+    };
+    let status = if args.draft {
+        Status::Draft
+    } else {
+        Status::Active
+    };
+
+    let mut generated = Vec::new();
+    let mut counts = Vec::new();
+    if kinds.contains(&Synthesis::Matching) {
+        let made = matching(&entries, existing, &rules, &args.tags, status);
+        counts.push(("짝짓기", made.len()));
+        generated.extend(
+            made.iter()
+                .map(|q| ("matching", serde_yaml::to_value(q).unwrap())),
+        );
+    }
+    if kinds.contains(&Synthesis::Cloze) {
+        let (made, missing) = cloze(&entries, existing, &rules, &args.tags, status);
+        counts.push(("빈칸", made.len()));
+        generated.extend(
+            made.iter()
+                .map(|q| ("fill_in_the_blanks", serde_yaml::to_value(q).unwrap())),
+        );
+        if missing > 0 {
+            println!(
+                "{}",
+                styled(Color::Yellow).paint(format!(
+                    "예문(세 번째 칸)에 용어가 나오지 않는 단어 {}개는 빈칸 문제를 만들지 못했습니다.",
+                    missing
+                ))
+            );
+        }
+    }
+    if kinds.contains(&Synthesis::Spelling) {
+        let made = spelling(&entries, existing, &rules, &args.tags, status);
+        counts.push(("철자", made.len()));
+        generated.extend(
+            made.iter()
+                .map(|q| ("spelling", serde_yaml::to_value(q).unwrap())),
+        );
+    }
+
+    if args.dry_run {
+        println!("# 챕터 {}", args.chapter);
+        let mut last = None;
+        for (key, question) in &generated {
+            if last != Some(key) {
+                println!("# {}", key);
+                last = Some(key);
+            }
+            print!("{}", serde_yaml::to_string(&[question]).unwrap());
+        }
+    } else if !generated.is_empty() {
+        write(
+            &target,
+            data,
+            generated
+                .into_iter()
+                .map(|(key, question)| (args.chapter, key, question)),
+        );
+    }
+
+    let made = counts
+        .iter()
+        .map(|(label, count)| {
+            format!(
+                "{} {}개",
+                label,
+                styled(Color::Green).paint(count.to_string())
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    if args.dry_run {
+        println!("단어 {}개로 {} 문제를 만들었습니다.", entries.len(), made);
+    } else {
+        println!(
+            "단어 {}개로 {} 문제를 만들어 {} 챕터 {}에 넣었습니다.",
+            entries.len(),
+            made,
+            target.display(),
+            args.chapter
+        );
+    }
+}
+
+// 빈 줄과 `#` 으로 시작하는 줄은 건너뜁니다. 탭이 없는 줄은 줄 번호를 돌려줍니다
+fn parse(text: &str) -> (Vec<Entry>, Vec<usize>) {
+    let mut entries = Vec::new();
+    let mut bad = Vec::new();
+    for (i, line) in text.lines().enumerate() {
+        let line = line.trim_end_matches('\r');
+        if line.trim().is_empty() || line.trim_start().starts_with('#') {
+            continue;
+        }
+        let mut fields = line.split('\t').map(str::trim);
+        let (Some(term), Some(definition)) = (fields.next(), fields.next()) else {
+            bad.push(i + 1);
+            continue;
+        };
+        if term.is_empty() || definition.is_empty() {
+            bad.push(i + 1);
+            continue;
+        }
+        entries.push(Entry {
+            term: term.to_string(),
+            definition: definition.to_string(),
+            sentence: fields.next().filter(|s| !s.is_empty()).map(str::to_string),
+        });
+    }
+    (entries, bad)
+}
+
+// 이미 짝짓기에 있는 용어는 빼고 `group_size` 개씩 묶습니다
+fn matching(
+    entries: &[Entry],
+    existing: Option<&Chapter>,
+    rules: &ImportRules,
+    tags: &[String],
+    status: Status,
+) -> Vec<MatchingQuestion> {
+    let mut seen: HashSet<&str> = existing
+        .iter()
+        .flat_map(|c| &c.matching)
+        .flat_map(|q| &q.pairs)
+        .map(|p| p.term.as_str())
+        .collect();
+    let fresh: Vec<&Entry> = entries
+        .iter()
+        .filter(|e| seen.insert(e.term.as_str()))
+        .collect();
+    // 짝이 하나뿐인 짝짓기는 풀 수 없으므로 적어도 둘씩 묶습니다
+    let groups: Vec<&[&Entry]> = fresh.chunks(rules.group_size.max(2)).collect();
+    let numbered = groups.len() > 1;
+    groups
+        .iter()
+        .enumerate()
+        .filter(|(_, group)| group.len() > 1)
+        .map(|(i, group)| MatchingQuestion {
+            question: match numbered {
+                true => format!("{} ({})", rules.matching_question, i + 1),
+                false => rules.matching_question.clone(),
+            },
+            pairs: group
+                .iter()
+                .map(|e| MatchingPair {
+                    term: e.term.clone(),
+                    definition: e.definition.clone(),
+                    sentence: e.sentence.clone(),
+                    audio: None,
+                    base: None,
+                })
+                .collect(),
+            tags: tags.to_vec(),
+            status,
+            points: None,
+            weight: None,
+            source: None,
+            page: None,
+            explanation: None,
+            normalize: None,
+            round: false,
+            group: None,
+            translations: BTreeMap::new(),
+        })
+        .collect()
+}
+
+fn cloze(
+    entries: &[Entry],
+    existing: Option<&Chapter>,
+    rules: &ImportRules,
+    tags: &[String],
+    status: Status,
+) -> (Vec<FillInTheBlankQuestion>, usize) {
+    let mut seen: HashSet<String> = existing
+        .iter()
+        .flat_map(|c| &c.fill_in_the_blanks)
+        .map(|q| q.question.clone())
+        .collect();
+    let mut made = Vec::new();
+    let mut missing = 0;
+    for entry in entries {
+        let blanked = entry
+            .sentence
+            .as_deref()
+            .and_then(|sentence| blank_out(sentence, &entry.term))
+            .or_else(|| {
+                rules
+                    .cloze_from_definition
+                    .then(|| blank_out(&entry.definition, &entry.term))
+                    .flatten()
+            });
+        let Some((question, answer)) = blanked else {
+            missing += 1;
+            continue;
+        };
+        if !seen.insert(question.clone()) {
+            continue;
+        }
+        made.push(FillInTheBlankQuestion {
+            question,
+            id: None,
+            variants: Vec::new(),
+            answer: answer.into(),
+            tags: tags.to_vec(),
+            status,
+            points: None,
+            weight: None,
+            source: None,
+            page: None,
+            explanation: None,
+            translations: BTreeMap::new(),
+            normalize: None,
+        });
+    }
+    (made, missing)
+}
+
+// 정의를 보여주고 용어의 철자를 직접 쓰게 합니다
+fn spelling(
+    entries: &[Entry],
+    existing: Option<&Chapter>,
+    rules: &ImportRules,
+    tags: &[String],
+    status: Status,
+) -> Vec<SpellingQuestion> {
+    let mut seen: HashSet<String> = existing
+        .iter()
+        .flat_map(|c| &c.spelling)
+        .map(|q| q.question.clone())
+        .collect();
+    entries
+        .iter()
+        .filter(|e| e.term.split_whitespace().count() <= rules.spelling_max_words)
+        .filter(|e| seen.insert(e.definition.clone()))
+        .map(|e| SpellingQuestion {
+            question: e.definition.clone(),
+            id: None,
+            variants: Vec::new(),
+            options: Vec::new(),
+            answer: e.term.clone().into(),
+            audio: None,
+            tags: tags.to_vec(),
+            status,
+            points: None,
+            weight: None,
+            source: None,
+            page: None,
+            explanation: None,
+            normalize: None,
+            translations: BTreeMap::new(),
+        })
+        .collect()
+}
@@ -0,0 +1,215 @@
+use crate::stats::pad;
+use crate::style::styled;
+use crate::{history, BUILTIN_BANK};
+use ansi_term::Color;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::time::Duration;
+use terms::{Filter, Kind, Status};
+
+#[derive(clap::Args)]
+pub struct InfoArgs {
+    /// 살펴볼 YAML 문제 파일 (생략하면 내장된 문제)
+    file: Option<PathBuf>,
+    /// 작성 중(draft)인 문제도 함께 셉니다
+    #[arg(long)]
+    include_drafts: bool,
+}
+
+const CELL_WIDTH: usize = 8;
+const UNEXPLAINED_SHOWN: usize = 10;
+
+// 기록이 없을 때 쓰는 문제 하나당 대략의 풀이 시간(초)
+fn default_secs(kind: Kind) -> f64 {
+    match kind {
+        Kind::MultipleChoice => 15.0,
+        Kind::Matching | Kind::Spelling => 10.0,
+        Kind::FillInTheBlank | Kind::Custom => 20.0,
+    }
+}
+
+pub fn run(args: InfoArgs) {
+    let mut questions = crate::load_questions(args.file.as_deref());
+    let bank = match &args.file {
+        Some(path) => path
+            .file_name()
+            .unwrap_or(path.as_os_str())
+            .to_string_lossy()
+            .into_owned(),
+        None => BUILTIN_BANK.to_string(),
+    };
+
+    let mut statuses: BTreeMap<&str, usize> = BTreeMap::new();
+    let mut untagged = 0;
+    let mut unexplained = Vec::new();
+    let mut options = Vec::new();
+    for chapter in &questions.chapters {
+        let builtin = chapter
+            .multiple_choice
+            .iter()
+            .map(|q| {
+                (
+                    q.status,
+                    q.tags.is_empty(),
+                    q.explanation.is_none(),
+                    &q.question,
+                )
+            })
+            .chain(chapter.matching.iter().map(|q| {
+                (
+                    q.status,
+                    q.tags.is_empty(),
+                    q.explanation.is_none(),
+                    &q.question,
+                )
+            }))
+            .chain(chapter.fill_in_the_blanks.iter().map(|q| {
+                (
+                    q.status,
+                    q.tags.is_empty(),
+                    q.explanation.is_none(),
+                    &q.question,
+                )
+            }))
+            .chain(chapter.spelling.iter().map(|q| {
+                (
+                    q.status,
+                    q.tags.is_empty(),
+                    q.explanation.is_none(),
+                    &q.question,
+                )
+            }));
+        for (status, no_tags, no_explanation, text) in builtin {
+            match status {
+                Status::Draft => *statuses.entry("작성 중").or_default() += 1,
+                Status::Retired => *statuses.entry("은퇴").or_default() += 1,
+                Status::Active => {}
+            }
+            if no_tags && status != Status::Retired {
+                untagged += 1;
+            }
+            if no_explanation && status != Status::Retired {
+                unexplained.push((chapter.chapter, text.clone()));
+            }
+        }
+    }
+
+    let filter = Filter {
+        include_drafts: args.include_drafts,
+        ..Default::default()
+    };
+    for chapter in &mut questions.chapters {
+        chapter.retain(&filter);
+        options.extend(chapter.multiple_choice.iter().map(|q| q.options.len()));
+    }
+    let built = crate::build_questions(questions.chapters, Some(0), false);
+    if built.is_empty() {
+        println!("출제할 문제가 없습니다.");
+        return;
+    }
+
+    let mut grid: BTreeMap<u32, BTreeMap<Kind, usize>> = BTreeMap::new();
+    for (question, chapter) in &built {
+        *grid
+            .entry(*chapter)
+            .or_default()
+            .entry(question.kind())
+            .or_default() += 1;
+    }
+    println!(
+        "{} 챕터 {}개, 문제 {}개\n",
+        styled(Color::Yellow.bold()).paint(&bank),
+        grid.len(),
+        built.len()
+    );
+    print!("{}", pad("", CELL_WIDTH));
+    for kind in Kind::ALL {
+        print!("{}", pad(kind.label(), CELL_WIDTH));
+    }
+    println!("합계");
+    let mut totals: BTreeMap<Kind, usize> = BTreeMap::new();
+    for (chapter, counts) in &grid {
+        print!("{}", pad(&format!("챕터 {}", chapter), CELL_WIDTH));
+        for kind in Kind::ALL {
+            let count = counts.get(&kind).copied().unwrap_or_default();
+            *totals.entry(kind).or_default() += count;
+            print!("{}", pad(&count.to_string(), CELL_WIDTH));
+        }
+        println!("{}", counts.values().sum::<usize>());
+    }
+    print!("{}", pad("합계", CELL_WIDTH));
+    for kind in Kind::ALL {
+        print!("{}", pad(&totals[&kind].to_string(), CELL_WIDTH));
+    }
+    println!("{}\n", built.len());
+
+    if !options.is_empty() {
+        println!(
+            "객관식 평균 보기 수: {:.1}개",
+            options.iter().sum::<usize>() as f64 / options.len() as f64
+        );
+    }
+    for (label, count) in &statuses {
+        println!("{} 문제: {}개 (출제하지 않음)", label, count);
+    }
+    if untagged > 0 {
+        println!(
+            "{}",
+            styled(Color::Yellow).paint(format!("태그가 없는 문제: {}개", untagged))
+        );
+    }
+    if !unexplained.is_empty() {
+        println!(
+            "{}",
+            styled(Color::Yellow).paint(format!("해설이 없는 문제: {}개", unexplained.len()))
+        );
+        // 해설을 처음 쓰는 문제 파일이면 목록이 길어지므로 앞의 몇 개만 보여줍니다
+        for (chapter, text) in unexplained.iter().take(UNEXPLAINED_SHOWN) {
+            println!("  - [챕터 {}] {}", chapter, text);
+        }
+        if unexplained.len() > UNEXPLAINED_SHOWN {
+            println!("  … 외 {}개", unexplained.len() - UNEXPLAINED_SHOWN);
+        }
+    }
+    if let Some((question, chapter)) = built
+        .iter()
+        .max_by_key(|(question, _)| question.text().chars().count())
+    {
+        println!(
+            "가장 긴 문제: [챕터 {}] {} ({}자)",
+            chapter,
+            question.text(),
+            question.text().chars().count()
+        );
+    }
+
+    // 이 문제 파일을 푼 기록이 있으면 유형별 평균 풀이 시간으로, 없으면 대략의 시간으로 어림합니다
+    let mut measured: BTreeMap<Kind, (u64, u64)> = BTreeMap::new();
+    for answer in history::load()
+        .unwrap_or_default()
+        .iter()
+        .filter(|s| s.bank == bank)
+        .flat_map(|s| &s.answers)
+        .filter(|a| a.time_ms > 0)
+    {
+        let entry = measured.entry(answer.kind).or_default();
+        entry.0 += answer.time_ms;
+        entry.1 += 1;
+    }
+    let secs: f64 = built
+        .iter()
+        .map(|(question, _)| match measured.get(&question.kind()) {
+            Some((total, count)) => *total as f64 / *count as f64 / 1000.0,
+            None => default_secs(question.kind()),
+        })
+        .sum();
+    println!(
+        "전부 풀면 약 {} 걸립니다 ({})",
+        styled(Color::Cyan).paint(crate::format_duration(Duration::from_secs_f64(secs))),
+        if measured.is_empty() {
+            "푼 기록이 없어 대략 어림한 시간"
+        } else {
+            "지난 풀이 시간 기준"
+        }
+    );
+}
@@ -0,0 +1,208 @@
+use std::io;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::sync::{Condvar, Mutex, OnceLock};
+use std::thread;
+use std::time::{Duration, Instant};
+
+pub enum Line {
+    Text(String),
+    Timeout,
+    Closed,
+    // 기다리는 동안 Ctrl-C 를 눌렀습니다
+    Interrupted,
+    // 터미널 크기가 바뀌었습니다 (`read_line_resizable` 로 기다릴 때만)
+    Resized,
+}
+
+enum Event {
+    Line(String),
+    Closed,
+    Interrupted,
+}
+
+// 표준 입력은 별도 스레드에서 줄 단위로 읽어, 기다리는 쪽에서 시간 제한이나 Ctrl-C 로 그만 기다릴 수 있게 합니다.
+// 전체 화면 모드가 키 입력을 직접 읽을 수 있도록 읽기를 원할 때만 읽고, `release` 로 읽기를 거둘 수 있습니다.
+struct Reader {
+    events: Mutex<Receiver<Event>>,
+    wanted: Mutex<bool>,
+    changed: Condvar,
+    // 읽는 스레드가 입력을 기다리거나 읽는 동안 잡고 있습니다
+    busy: Mutex<()>,
+    // Ctrl-C 처리기도 보내는 쪽을 들고 있어서 채널이 닫히지 않으므로 입력이 끝난 것을 따로 적어 둡니다
+    closed: AtomicBool,
+}
+
+// 줄을 기다리는 동안만 Ctrl-C 를 그 줄을 그만 기다리는 것으로 받습니다
+static WAITING: AtomicBool = AtomicBool::new(false);
+
+fn reader() -> &'static Reader {
+    static READER: OnceLock<Reader> = OnceLock::new();
+    READER.get_or_init(|| {
+        let (tx, events) = mpsc::channel();
+        let interrupts = tx.clone();
+        let _ = ctrlc::set_handler(move || {
+            if !WAITING.load(Ordering::SeqCst) || interrupts.send(Event::Interrupted).is_err() {
+                std::process::exit(130);
+            }
+        });
+        thread::spawn(move || read_lines(tx));
+        Reader {
+            events: Mutex::new(events),
+            wanted: Mutex::new(false),
+            changed: Condvar::new(),
+            busy: Mutex::new(()),
+            closed: AtomicBool::new(false),
+        }
+    })
+}
+
+fn read_lines(tx: Sender<Event>) {
+    let reader = reader();
+    let mut buffer = Vec::new();
+    loop {
+        {
+            let mut wanted = reader.wanted.lock().unwrap();
+            while !*wanted {
+                wanted = reader.changed.wait(wanted).unwrap();
+            }
+        }
+        let busy = reader.busy.lock().unwrap();
+        if !*reader.wanted.lock().unwrap() {
+            continue;
+        }
+        let read = fill(&mut buffer);
+        drop(busy);
+        match read {
+            Ok(None) => continue,
+            Ok(Some(0)) | Err(_) => {
+                if !buffer.is_empty() {
+                    let end = buffer.len();
+                    let _ = tx.send(Event::Line(take_line(&mut buffer, end)));
+                }
+                let _ = tx.send(Event::Closed);
+                return;
+            }
+            Ok(Some(_)) => {}
+        }
+        while let Some(end) = buffer.iter().position(|&b| b == b'\n') {
+            *reader.wanted.lock().unwrap() = false;
+            if tx
+                .send(Event::Line(take_line(&mut buffer, end + 1)))
+                .is_err()
+            {
+                return;
+            }
+        }
+    }
+}
+
+fn take_line(buffer: &mut Vec<u8>, end: usize) -> String {
+    let line: Vec<u8> = buffer.drain(..end).collect();
+    String::from_utf8_lossy(&line)
+        .trim_end_matches(['\n', '\r'])
+        .to_string()
+}
+
+// 잠깐 기다려서 들어온 입력을 덧붙입니다. 들어온 것이 없으면 `None`, 입력이 끝났으면 `Some(0)`
+#[cfg(unix)]
+fn fill(buffer: &mut Vec<u8>) -> io::Result<Option<usize>> {
+    let mut fd = libc::pollfd {
+        fd: libc::STDIN_FILENO,
+        events: libc::POLLIN,
+        revents: 0,
+    };
+    // 읽기를 거둘 때 오래 기다리지 않도록 짧게 끊어서 기다립니다
+    match unsafe { libc::poll(&mut fd, 1, 50) } {
+        -1 => {
+            let error = io::Error::last_os_error();
+            return match error.kind() {
+                io::ErrorKind::Interrupted => Ok(None),
+                _ => Err(error),
+            };
+        }
+        0 => return Ok(None),
+        _ => {}
+    }
+    let mut chunk = [0u8; 4096];
+    let read = unsafe { libc::read(libc::STDIN_FILENO, chunk.as_mut_ptr().cast(), chunk.len()) };
+    match read {
+        -1 => {
+            let error = io::Error::last_os_error();
+            match error.kind() {
+                io::ErrorKind::Interrupted | io::ErrorKind::WouldBlock => Ok(None),
+                _ => Err(error),
+            }
+        }
+        read => {
+            buffer.extend_from_slice(&chunk[..read as usize]);
+            Ok(Some(read as usize))
+        }
+    }
+}
+
+// 입력을 기다리다 끊을 방법이 없으므로 한 줄을 다 읽을 때까지 기다립니다
+#[cfg(not(unix))]
+fn fill(buffer: &mut Vec<u8>) -> io::Result<Option<usize>> {
+    use std::io::BufRead;
+    io::stdin().lock().read_until(b'\n', buffer).map(Some)
+}
+
+// 아직 받지 않은 읽기를 거둡니다. 전체 화면 모드에 들어가기 전에 불러서 키 입력을 빼앗기지 않게 합니다
+pub fn release() {
+    let reader = reader();
+    *reader.wanted.lock().unwrap() = false;
+    drop(reader.busy.lock().unwrap());
+}
+
+fn wait(deadline: Option<Instant>, resizable: bool) -> Line {
+    let reader = reader();
+    let events = reader.events.lock().unwrap();
+    if reader.closed.load(Ordering::SeqCst) {
+        return Line::Closed;
+    }
+    *reader.wanted.lock().unwrap() = true;
+    reader.changed.notify_one();
+    let size = resizable
+        .then(|| crossterm::terminal::size().ok())
+        .flatten();
+    WAITING.store(true, Ordering::SeqCst);
+    let line = loop {
+        // 크기가 바뀌었는지 볼 수 있도록 조금씩 끊어서 기다립니다
+        let mut slice = Duration::from_millis(200);
+        if let Some(deadline) = deadline {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                break Line::Timeout;
+            }
+            slice = slice.min(remaining);
+        }
+        match events.recv_timeout(slice) {
+            Ok(Event::Line(line)) => break Line::Text(line),
+            Ok(Event::Closed) | Err(RecvTimeoutError::Disconnected) => {
+                reader.closed.store(true, Ordering::SeqCst);
+                break Line::Closed;
+            }
+            Ok(Event::Interrupted) => break Line::Interrupted,
+            Err(RecvTimeoutError::Timeout) => {}
+        }
+        if size.is_some() && crossterm::terminal::size().ok() != size {
+            break Line::Resized;
+        }
+    };
+    WAITING.store(false, Ordering::SeqCst);
+    line
+}
+
+pub fn read_line() -> Line {
+    wait(None, false)
+}
+
+pub fn read_line_timeout(timeout: Duration) -> Line {
+    wait(Some(Instant::now() + timeout), false)
+}
+
+// 터미널 크기가 바뀌어도 돌아와서 다시 그릴 수 있게 합니다
+pub fn read_line_resizable(timeout: Duration) -> Line {
+    wait(Some(Instant::now() + timeout), true)
+}
@@ -0,0 +1,96 @@
+use chrono::{DateTime, Local};
+use std::fmt::Write as _;
+use std::fs::OpenOptions;
+use std::io::{self, Write};
+use std::path::Path;
+use std::time::Duration;
+use terms::score::Score;
+use terms::Askable;
+
+pub struct Entry {
+    pub chapter: u32,
+    pub text: String,
+    pub answer: String,
+    pub reference: Option<String>,
+}
+
+impl Entry {
+    pub fn new(question: &dyn Askable, chapter: u32) -> Self {
+        Self {
+            chapter,
+            text: question.text().to_string(),
+            answer: question.correct_answer(),
+            reference: question.reference(),
+        }
+    }
+}
+
+pub struct Session {
+    pub started_at: DateTime<Local>,
+    pub duration: Duration,
+    pub chapters: Vec<u32>,
+    pub score: usize,
+    pub points: Score,
+    pub grade: Option<String>,
+    pub passed: Option<bool>,
+    pub total: usize,
+    pub missed: Vec<Entry>,
+    pub flagged: Vec<Entry>,
+    pub notes: Vec<String>,
+}
+
+pub fn append(path: &Path, session: &Session) -> io::Result<()> {
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    file.write_all(render(session).as_bytes())
+}
+
+fn render(session: &Session) -> String {
+    let mut out = String::new();
+    let secs = session.duration.as_secs();
+    let chapters: Vec<String> = session.chapters.iter().map(|c| c.to_string()).collect();
+
+    writeln!(out, "## {}\n", session.started_at.format("%Y-%m-%d %H:%M")).unwrap();
+    writeln!(out, "- 맞힌 문제: {} / {}", session.score, session.total).unwrap();
+    writeln!(out, "- 점수: {}", session.points).unwrap();
+    if let Some(grade) = &session.grade {
+        writeln!(out, "- 성적: {}", grade).unwrap();
+    }
+    match session.passed {
+        Some(true) => writeln!(out, "- 합격").unwrap(),
+        Some(false) => writeln!(out, "- 불합격").unwrap(),
+        None => {}
+    }
+    writeln!(out, "- 소요 시간: {}분 {}초", secs / 60, secs % 60).unwrap();
+    writeln!(out, "- 챕터: {}", chapters.join(", ")).unwrap();
+
+    write_entries(&mut out, "틀린 문제", &session.missed);
+    write_entries(&mut out, "표시한 문제", &session.flagged);
+
+    if !session.notes.is_empty() {
+        writeln!(out, "\n### 메모\n").unwrap();
+        for note in &session.notes {
+            writeln!(out, "{}", note).unwrap();
+        }
+    }
+    out.push('\n');
+    out
+}
+
+fn write_entries(out: &mut String, title: &str, entries: &[Entry]) {
+    if entries.is_empty() {
+        return;
+    }
+    writeln!(out, "\n### {}\n", title).unwrap();
+    for entry in entries {
+        write!(
+            out,
+            "- [챕터 {}] {} (정답: {})",
+            entry.chapter, entry.text, entry.answer
+        )
+        .unwrap();
+        match &entry.reference {
+            Some(reference) => writeln!(out, " — 출처: {}", reference).unwrap(),
+            None => out.push('\n'),
+        }
+    }
+}
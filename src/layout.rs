@@ -0,0 +1,94 @@
+use crate::stats::pad;
+use std::io::{self, IsTerminal};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+// 이보다 좁은 터미널에서는 짧은 번호 보기를 한 줄에 여러 개씩 늘어놓습니다
+pub const COMPACT: usize = 60;
+
+// 여러 열로 늘어놓은 보기 사이의 빈칸
+const GAP: usize = 3;
+
+// 출력이 터미널이 아니면(파이프, 파일) 줄을 바꾸지 않도록 None. 매번 다시 재므로 창 크기를 바꾸면 바로 따릅니다
+pub fn width() -> Option<usize> {
+    if !io::stdout().is_terminal() {
+        return None;
+    }
+    crossterm::terminal::size()
+        .ok()
+        .map(|(cols, _)| (cols as usize).max(1))
+}
+
+// 낱말 단위로 `width` 칸에 맞춰 줄을 나누고, 둘째 줄부터는 `indent` 칸 들여씁니다. 한 줄보다 긴 낱말은 글자 단위로 자릅니다
+pub fn wrap(text: &str, width: usize, indent: usize) -> Vec<String> {
+    let width = width.max(1);
+    let indent = if indent * 2 > width { 0 } else { indent };
+    let mut lines = Vec::new();
+    for paragraph in text.lines() {
+        let mut line = String::new();
+        let mut fresh = true;
+        for word in paragraph.split(' ') {
+            if !fresh && line.width() + 1 + word.width() > width {
+                lines.push(std::mem::take(&mut line));
+                line = " ".repeat(indent);
+                fresh = true;
+            }
+            if !fresh {
+                line.push(' ');
+            }
+            for c in word.chars() {
+                if line.width() + c.width().unwrap_or(0) > width && line.width() > indent {
+                    lines.push(std::mem::take(&mut line));
+                    line = " ".repeat(indent);
+                }
+                line.push(c);
+            }
+            fresh = false;
+        }
+        lines.push(line);
+    }
+    if lines.is_empty() {
+        lines.push(String::new());
+    }
+    lines
+}
+
+pub fn print(text: &str) {
+    match width() {
+        Some(width) => wrap(text, width, 0)
+            .iter()
+            .for_each(|line| println!("{}", line)),
+        None => println!("{}", text),
+    }
+}
+
+// (보기 기호, 보기)를 터미널 너비에 맞춰 보여줍니다. 긴 보기의 둘째 줄은 기호 뒤에 맞춰 들여쓰고,
+// 좁은 터미널에서 짝짓기처럼 번호를 붙인 짧은 보기는 여러 열로 늘어놓습니다
+pub fn print_options(options: &[(&str, &str)]) {
+    let lines: Vec<String> = options
+        .iter()
+        .map(|(marker, option)| format!("{}{}", marker, option))
+        .collect();
+    let Some(width) = width() else {
+        for line in &lines {
+            println!("{}", line);
+        }
+        return;
+    };
+    let numbered = options
+        .iter()
+        .all(|(marker, _)| marker.starts_with(|c: char| c.is_ascii_digit()));
+    let widest = lines.iter().map(|line| line.width()).max().unwrap_or(0);
+    if numbered && width < COMPACT && widest * 2 + GAP <= width {
+        let columns = (width + GAP) / (widest + GAP);
+        for row in lines.chunks(columns) {
+            let cells: Vec<String> = row.iter().map(|line| pad(line, widest)).collect();
+            println!("{}", cells.join(&" ".repeat(GAP)).trim_end());
+        }
+        return;
+    }
+    for ((marker, _), line) in options.iter().zip(&lines) {
+        for wrapped in wrap(line, width, marker.width()) {
+            println!("{}", wrapped);
+        }
+    }
+}
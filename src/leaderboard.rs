@@ -0,0 +1,234 @@
+use crate::history;
+use crate::stats::pad;
+use crate::style::styled;
+use ansi_term::Color;
+use chrono::{Local, NaiveDate, TimeDelta};
+use std::collections::BTreeSet;
+use terms::score::{self, Score};
+use unicode_width::UnicodeWidthStr;
+
+#[derive(clap::Args)]
+pub struct LeaderboardArgs {
+    /// 이 문제 파일(파일 이름)의 기록만 셉니다
+    #[arg(long, value_name = "NAME")]
+    bank: Option<String>,
+    /// 이 날(YYYY-MM-DD)부터의 기록만 셉니다
+    #[arg(long, value_name = "DATE", value_parser = parse_date)]
+    since: Option<NaiveDate>,
+    /// 이 날(YYYY-MM-DD)까지의 기록만 셉니다
+    #[arg(long, value_name = "DATE", value_parser = parse_date)]
+    until: Option<NaiveDate>,
+    /// `terms serve --collect` 서버에 제출된 학생들의 순위도 보여줍니다
+    #[cfg(feature = "web")]
+    #[arg(long, value_name = "URL")]
+    server: Option<String>,
+}
+
+fn parse_date(text: &str) -> Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(text, "%Y-%m-%d")
+        .map_err(|_| "2024-03-01 처럼 적어 주세요".to_string())
+}
+
+impl LeaderboardArgs {
+    fn includes(&self, bank: &str, date: NaiveDate) -> bool {
+        self.bank.as_deref().is_none_or(|b| b == bank)
+            && self.since.is_none_or(|since| since <= date)
+            && self.until.is_none_or(|until| date <= until)
+    }
+}
+
+struct Row {
+    name: String,
+    points: Score,
+    correct: usize,
+    answered: usize,
+    sessions: usize,
+    // 지금까지 이어 온 날 수와 가장 길게 이어 간 날 수. 수업 서버의 결과에는 없습니다
+    streaks: Option<(usize, usize)>,
+}
+
+impl Row {
+    fn accuracy(&self) -> f64 {
+        if self.answered == 0 {
+            return 0.0;
+        }
+        self.correct as f64 / self.answered as f64 * 100.0
+    }
+}
+
+// 오늘(아직 안 풀었으면 어제)까지 하루도 빠짐없이 푼 날 수와 가장 길게 이어 간 날 수
+fn streaks(days: &BTreeSet<NaiveDate>) -> (usize, usize) {
+    let today = Local::now().date_naive();
+    let mut day = if days.contains(&today) {
+        today
+    } else {
+        today - TimeDelta::days(1)
+    };
+    let mut current = 0;
+    while days.contains(&day) {
+        current += 1;
+        day -= TimeDelta::days(1);
+    }
+    let (mut best, mut run, mut previous) = (0, 0, None);
+    for &day in days {
+        run = match previous {
+            Some(previous) if day - previous == TimeDelta::days(1) => run + 1,
+            _ => 1,
+        };
+        best = usize::max(best, run);
+        previous = Some(day);
+    }
+    (current, best)
+}
+
+fn profile_row(name: String, sessions: &[history::Session], args: &LeaderboardArgs) -> Row {
+    let mut row = Row {
+        name,
+        points: Score::default(),
+        correct: 0,
+        answered: 0,
+        sessions: 0,
+        streaks: None,
+    };
+    let mut days = BTreeSet::new();
+    for session in sessions {
+        let date = session.started_at.date_naive();
+        if !args.includes(&session.bank, date) {
+            continue;
+        }
+        row.sessions += 1;
+        days.insert(date);
+        for answer in &session.answers {
+            row.answered += 1;
+            if answer.correct {
+                row.correct += 1;
+            }
+            row.points += answer
+                .score
+                .unwrap_or_else(|| Score::from_correct(answer.correct));
+        }
+    }
+    row.streaks = Some(streaks(&days));
+    row
+}
+
+// 한글이 섞여도 열이 맞도록 화면에 보이는 너비로 오른쪽에 붙입니다
+fn right(text: &str, width: usize) -> String {
+    let fill = width.saturating_sub(UnicodeWidthStr::width(text));
+    format!("{}{}", " ".repeat(fill), text)
+}
+
+fn print_rows(mut rows: Vec<Row>) {
+    rows.sort_by(|a, b| {
+        b.points
+            .earned
+            .total_cmp(&a.points.earned)
+            .then(b.accuracy().total_cmp(&a.accuracy()))
+    });
+    let width = rows
+        .iter()
+        .map(|row| UnicodeWidthStr::width(row.name.as_str()))
+        .max()
+        .unwrap_or_default()
+        .max(4)
+        + 2;
+    let line = |cells: [&str; 8]| {
+        let [rank, name, rest @ ..] = cells;
+        let rest: Vec<String> = rest.iter().map(|cell| right(cell, 8)).collect();
+        format!("{}{}{}", pad(rank, 5), pad(name, width), rest.join(""))
+    };
+    println!(
+        "{}",
+        line([
+            "순위",
+            "이름",
+            "점수",
+            "정답률",
+            "푼 문제",
+            "세션",
+            "연속",
+            "최장"
+        ])
+    );
+    for (rank, row) in rows.iter().enumerate() {
+        let (current, best) = match row.streaks {
+            Some((current, best)) => (format!("{}일", current), format!("{}일", best)),
+            None => ("-".to_string(), "-".to_string()),
+        };
+        let text = line([
+            &format!("{}.", rank + 1),
+            &row.name,
+            &score::points(row.points.earned),
+            &format!("{:.0}%", row.accuracy()),
+            &row.answered.to_string(),
+            &row.sessions.to_string(),
+            &current,
+            &best,
+        ]);
+        match rank {
+            0 => println!("{}", styled(Color::Yellow.bold()).paint(text)),
+            _ => println!("{}", text),
+        }
+    }
+}
+
+#[cfg(feature = "web")]
+fn class_rows(url: &str, args: &LeaderboardArgs) -> Result<Vec<Row>, String> {
+    let standings: Vec<crate::classroom::Standing> =
+        ureq::get(format!("{}/api/leaderboard", url.trim_end_matches('/')))
+            .call()
+            .and_then(|mut response| response.body_mut().read_json())
+            .map_err(|e| e.to_string())?;
+    Ok(standings
+        .into_iter()
+        .filter(|s| args.includes(&s.bank, s.started_at.date_naive()))
+        .map(|s| Row {
+            name: s.student,
+            points: s.points,
+            correct: s.correct,
+            answered: s.total,
+            sessions: 1,
+            streaks: None,
+        })
+        .collect())
+}
+
+pub fn run(args: LeaderboardArgs) {
+    let mut rows = Vec::new();
+    for (name, dir) in history::profiles() {
+        let name = name.unwrap_or_else(|| "(기본)".to_string());
+        match history::load_from(&dir.join("history.yaml")) {
+            Ok(sessions) if !sessions.is_empty() => rows.push(profile_row(name, &sessions, &args)),
+            Ok(_) => {}
+            Err(e) => println!(
+                "{} {}: {}",
+                styled(Color::Red).paint("학습 기록을 읽을 수 없습니다:"),
+                name,
+                e
+            ),
+        }
+    }
+    rows.retain(|row| row.sessions > 0);
+    if rows.is_empty() {
+        println!("순위를 매길 기록이 없습니다. `--profile 이름` 을 붙여 풀면 프로필마다 기록이 따로 남습니다.");
+    } else {
+        print_rows(rows);
+    }
+
+    #[cfg(feature = "web")]
+    if let Some(url) = &args.server {
+        println!(
+            "\n{}",
+            styled(Color::Cyan.bold()).paint(format!("수업 ({})", url))
+        );
+        match class_rows(url, &args) {
+            Ok(rows) if rows.is_empty() => println!("제출된 답안이 없습니다."),
+            Ok(rows) => print_rows(rows),
+            Err(e) => println!(
+                "{} {}",
+                styled(Color::Red).paint("서버에서 순위를 받아 올 수 없습니다:"),
+                e
+            ),
+        }
+    }
+}
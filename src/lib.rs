@@ -0,0 +1,28 @@
+//! 용어 퀴즈의 핵심: 문제 모델, 채점, 문제 구성.
+//!
+//! 입출력은 [`Frontend`] 를 구현한 쪽이 맡고, 난수는 호출하는 쪽에서 넘겨주므로
+//! `wasm32-unknown-unknown` 으로도 빌드할 수 있습니다.
+
+pub mod bank;
+pub mod error;
+pub mod frontend;
+pub mod hangul;
+pub mod normalize;
+pub mod question;
+pub mod registry;
+pub mod score;
+#[cfg(feature = "script")]
+pub mod script;
+pub mod template;
+
+pub use bank::Bank;
+pub use error::ParseError;
+pub use frontend::Frontend;
+pub use normalize::Normalize;
+pub use question::{
+    build_questions, weight, weighted_shuffle, Answers, Askable, Chapter, FillInTheBlankQuestion,
+    Filter, GeneratedQuestion, Kind, Letters, Marker, MatchingPair, MatchingQuestion,
+    MatchingRoundQuestion, MultipleChoiceQuestion, Questions, RecallQuestion,
+    SingleMatchingQuestion, SpellingQuestion, Status, Translation,
+};
+pub use registry::{typed, BuildError, BuiltQuestions, QuestionType, Registry};
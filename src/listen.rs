@@ -0,0 +1,247 @@
+use crate::style::{self, styled};
+use crate::{audio, config, history, load_questions, ChapterSet, Prompter};
+use ansi_term::Color;
+use chrono::Local;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+use terms::score::Score;
+use terms::{Answers, Askable, Filter, Frontend, Kind, Normalize, SingleMatchingQuestion};
+
+#[derive(clap::Args)]
+pub struct ListenArgs {
+    /// 녹음(`audio`)을 붙인 YAML 문제 파일
+    #[arg(long, value_name = "FILE")]
+    bank: PathBuf,
+    /// 이 챕터들의 녹음만 듣습니다 (예: 1-3,5). 생략하면 전부
+    #[arg(long)]
+    chapters: Option<ChapterSet>,
+    /// 이 태그가 붙은 문제의 녹음만 듣습니다 (여러 번 지정 가능)
+    #[arg(long = "tag", value_name = "TAG")]
+    tags: Vec<String>,
+    /// 들을 낱말 수 (생략하면 전부)
+    #[arg(long)]
+    count: Option<usize>,
+    /// 낱말을 섞는 시드. 같은 시드면 같은 순서로 들려줍니다
+    #[arg(long)]
+    seed: Option<u64>,
+    /// 답한 뒤 내 발음을 SECS초 동안 녹음해서 원래 녹음에 이어 들려줍니다
+    #[cfg(feature = "record")]
+    #[arg(long, value_name = "SECS", num_args = 0..=1, default_missing_value = "3")]
+    record: Option<f64>,
+}
+
+// 녹음이 붙은 짝짓기 용어나 철자 문제 하나
+struct Clip {
+    id: String,
+    chapter: u32,
+    kind: Kind,
+    group: Option<String>,
+    meaning: String,
+    answer: Answers,
+    normalize: Normalize,
+    audio: PathBuf,
+}
+
+// 녹음을 듣고 들은 낱말의 철자를 씁니다
+pub fn run(args: ListenArgs) {
+    let config = config::Config::load();
+    let mut clips = clips(&args);
+    if clips.is_empty() {
+        println!(
+            "녹음이 붙은 짝짓기 용어나 철자 문제가 없습니다. 문제에 `audio: 파일` 을 적으세요."
+        );
+        return;
+    }
+    let mut rng = match args.seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+    };
+    clips.shuffle(&mut rng);
+    clips.truncate(args.count.unwrap_or(clips.len()));
+
+    println!(
+        "녹음을 듣고 들은 낱말을 쓰세요. 아무것도 쓰지 않고 Enter 를 누르면 다시 들려줍니다 ({}개)",
+        clips.len()
+    );
+    let started_at = Local::now();
+    let start = Instant::now();
+    let mut answers = Vec::new();
+    for (i, clip) in clips.iter().enumerate() {
+        println!(
+            "{} {}",
+            styled(Color::Yellow.bold()).paint(format!("[{}/{}]", i + 1, clips.len())),
+            styled(Color::Yellow).paint(format!("챕터 {}", clip.chapter))
+        );
+        if let Err(e) = audio::play(&clip.audio, config.audio_player.as_deref()) {
+            println!(
+                "{} {}\n",
+                styled(Color::Red).paint("녹음을 틀 수 없어 건너뜁니다:"),
+                e
+            );
+            continue;
+        }
+
+        let mut prompter = Prompter::new(false, false, None, config.keys.clone());
+        prompter.typo = config.typo;
+        prompter.romanized = config.romanized;
+        prompter.converting = config.romanized.is_some();
+        let expected = clip.answer.primary().to_string();
+        let asked_at = Instant::now();
+        let typed = loop {
+            match prompter.command("철자: ") {
+                Some(typed) if typed.is_empty() => {
+                    if let Err(e) = audio::play(&clip.audio, config.audio_player.as_deref()) {
+                        println!(
+                            "{} {}",
+                            styled(Color::Red).paint("녹음을 틀 수 없습니다:"),
+                            e
+                        );
+                    }
+                }
+                typed => break typed.map(|typed| prompter.hangulize(typed)),
+            }
+        };
+        if prompter.quit {
+            break;
+        }
+        println!("{} {}", styled(Color::Cyan).paint("뜻:"), clip.meaning);
+        let score = match typed {
+            Some(typed) => {
+                prompter.typed = Some(typed.clone());
+                prompter.given.push(typed.clone());
+                let score = Score::from_correct(clip.answer.accepts(&typed, &clip.normalize));
+                let score = prompter.regrade(&typed, &expected, score);
+                prompter.result(score, &expected);
+                score
+            }
+            None => prompter.unanswered(&expected),
+        };
+        #[cfg(feature = "record")]
+        if let Some(secs) = args.record {
+            compare(&clip.audio, secs, config.audio_player.as_deref());
+            println!();
+        }
+
+        answers.push(history::Answer {
+            id: clip.id.clone(),
+            text: clip.meaning.clone(),
+            chapter: clip.chapter,
+            kind: clip.kind,
+            group: clip.group.clone(),
+            correct: score.is_correct(),
+            score: Some(score),
+            time_ms: asked_at.elapsed().as_millis() as u64,
+            lifeline: false,
+            options: Vec::new(),
+            given: prompter.given.clone(),
+            expected,
+        });
+    }
+    if answers.is_empty() {
+        return;
+    }
+
+    let correct = answers.iter().filter(|a| a.correct).count();
+    println!(
+        "{} 낱말 중 {} 개를 바르게 썼습니다.",
+        styled(Color::Yellow).paint(answers.len().to_string()),
+        style::emphasis(Color::Yellow).paint(correct.to_string())
+    );
+    let record = history::Session {
+        bank: args
+            .bank
+            .file_name()
+            .unwrap_or(args.bank.as_os_str())
+            .to_string_lossy()
+            .into_owned(),
+        started_at,
+        duration_secs: start.elapsed().as_secs_f64(),
+        answers,
+    };
+    if let Err(e) = history::append(record) {
+        println!(
+            "{} {}",
+            styled(Color::Red).paint("학습 기록을 저장할 수 없습니다:"),
+            e
+        );
+    }
+}
+
+// 녹음 파일 경로는 문제 파일이 있는 폴더를 기준으로 합니다
+fn clips(args: &ListenArgs) -> Vec<Clip> {
+    let questions = load_questions(Some(&args.bank));
+    let base = args.bank.parent().unwrap_or(Path::new(""));
+    let filter = Filter {
+        tags: args.tags.clone(),
+        kinds: vec![Kind::Matching, Kind::Spelling],
+        include_drafts: false,
+    };
+    let mut clips = Vec::new();
+    for mut chapter in questions.chapters {
+        if args
+            .chapters
+            .as_ref()
+            .is_some_and(|set| !set.0.contains(&chapter.chapter))
+        {
+            continue;
+        }
+        chapter.retain(&filter);
+        if let Some(normalize) = &questions.normalize {
+            chapter.default_normalize(normalize);
+        }
+        for q in &chapter.matching {
+            for pair in &q.pairs {
+                let Some(audio) = &pair.audio else {
+                    continue;
+                };
+                clips.push(Clip {
+                    id: SingleMatchingQuestion::new(pair.term.clone(), q).id(),
+                    chapter: chapter.chapter,
+                    kind: Kind::Matching,
+                    group: Some(q.group().to_string()),
+                    meaning: pair.definition.clone(),
+                    answer: Answers::One(pair.term.clone()),
+                    normalize: q.normalize.clone().unwrap_or_default(),
+                    audio: base.join(audio),
+                });
+            }
+        }
+        for q in &chapter.spelling {
+            let Some(audio) = &q.audio else {
+                continue;
+            };
+            clips.push(Clip {
+                id: q.id(),
+                chapter: chapter.chapter,
+                kind: Kind::Spelling,
+                group: None,
+                meaning: q.question.clone(),
+                answer: q.answer.clone(),
+                normalize: q.normalize.clone().unwrap_or_default(),
+                audio: base.join(audio),
+            });
+        }
+    }
+    clips
+}
+
+// 내 발음을 녹음해서 원래 녹음 다음에 들려줍니다
+#[cfg(feature = "record")]
+fn compare(original: &Path, secs: f64, player: Option<&str>) {
+    println!(
+        "{}",
+        styled(Color::Cyan).paint(format!("{}초 동안 따라 말해 보세요...", secs))
+    );
+    let recorded = audio::record(secs).and_then(|(samples, rate)| {
+        println!("{}", styled(Color::Cyan).paint("원래 녹음"));
+        audio::play(original, player)?;
+        println!("{}", styled(Color::Cyan).paint("내 발음"));
+        audio::play_samples(&samples, rate)
+    });
+    if let Err(e) = recorded {
+        println!("{} {}", styled(Color::Red).paint("녹음할 수 없습니다:"), e);
+    }
+}
@@ -1,140 +1,1058 @@
+mod add;
+mod audio;
+mod blueprint;
+mod cards;
+#[cfg(feature = "web")]
+mod classroom;
+mod clipboard;
+mod config;
+#[cfg(feature = "sqlite")]
+mod db;
+mod diff;
+#[cfg(feature = "discord")]
+mod discord;
+#[cfg(feature = "encrypt")]
+mod encrypt;
+mod generate;
+mod groups;
+mod history;
+#[cfg(feature = "web")]
+mod host;
+mod import;
+mod info;
+mod input;
+mod journal;
+mod layout;
+mod leaderboard;
+mod listen;
+mod notes;
+mod present;
+mod progress;
+mod remind;
+mod replay;
+mod report;
+#[cfg(feature = "sign")]
+mod sign;
+mod snapshot;
+mod stats;
+mod style;
+mod suspend;
+mod sync;
+#[cfg(feature = "telegram")]
+mod telegram;
+mod tui;
+mod validate;
+mod versus;
+mod watch;
+#[cfg(feature = "web")]
+mod web;
+
 use ansi_term::Color;
+use clap::{Parser, Subcommand};
+use rand::rngs::StdRng;
 use rand::seq::SliceRandom;
-use rand::thread_rng;
-use serde::Deserialize;
-use std::collections::HashSet;
-use std::io::{self, Write};
+use rand::{thread_rng, Rng, SeedableRng};
+use std::collections::{BTreeMap, HashSet, VecDeque};
+use std::fs;
+use std::io::{self, IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, Instant};
+use style::{styled, Verdict};
+use terms::frontend;
+use terms::hangul::{self, Slip};
+use terms::score::Score;
+use terms::{
+    weight, weighted_shuffle, Askable, Bank, BuiltQuestions, Chapter, Filter, Frontend, Kind,
+    Marker, Normalize, ParseError, Questions, Registry,
+};
 
-#[derive(Deserialize)]
-struct MultipleChoiceQuestion {
-    question: String,
-    options: [String; 4],
-    answer: char,
+#[derive(Parser)]
+#[command(version, about = "용어 암기용 퀴즈")]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+    /// 색, 화면 지우기 없이 화면 낭독기에서 읽기 쉬운 일반 텍스트로만 출력합니다
+    #[arg(long, global = true)]
+    plain: bool,
+    /// 정답/오답을 색에만 의존하지 않고 기호, 굵게, 밑줄로 강조합니다
+    #[arg(long, global = true)]
+    high_contrast: bool,
+    /// 정답/오답 판정을 큰 글자 배너로 보여줍니다
+    #[arg(long, global = true)]
+    banner: bool,
+    /// 문제 파일의 서명이나 체크섬이 맞지 않아도 엽니다
+    #[cfg(feature = "sign")]
+    #[arg(long, global = true)]
+    insecure: bool,
+    /// 이 이름의 프로필로 기록을 따로 남깁니다 (한 컴퓨터를 여럿이 쓸 때)
+    #[arg(long, global = true, value_name = "NAME", value_parser = history::parse_profile)]
+    profile: Option<String>,
 }
 
-#[derive(Deserialize)]
-struct MatchingPair {
-    term: String,
-    definition: String,
+#[derive(Subcommand)]
+enum Command {
+    /// 퀴즈를 시작합니다 (기본 명령)
+    Quiz(Box<QuizArgs>),
+    /// 지금까지 푼 문제의 통계를 보여줍니다
+    Stats(stats::StatsArgs),
+    /// 문제를 푸는 중에 쓸 수 있는 키를 보여줍니다
+    Keys,
+    /// 문제 파일의 챕터, 유형별 문제 수와 예상 풀이 시간을 보여줍니다
+    Info(info::InfoArgs),
+    /// 이미 쓴 문제로 다른 유형의 문제를 만들어 문제 파일에 덧붙입니다
+    Generate(generate::GenerateArgs),
+    /// 단어 목록 같은 다른 형식의 파일로 문제를 만들어 문제 파일에 덧붙입니다
+    Import(import::ImportArgs),
+    /// 문제 파일에 틀린 곳이 없는지 검사합니다
+    Validate(validate::ValidateArgs),
+    /// 묻는 대로 답해서 문제 파일에 새 문제를 덧붙입니다
+    Add(add::AddArgs),
+    /// 문제에 붙인 발음 녹음을 듣고 들은 낱말의 철자를 씁니다
+    Listen(listen::ListenArgs),
+    /// `:report` 로 신고된 문제를 하나씩 보며 고치거나 은퇴시킵니다
+    Triage(report::TriageArgs),
+    /// 복습할 때가 된 문제 수를 알려 줍니다. `--daily 21:00` 으로 매일 알릴 시각을 정합니다
+    Remind(remind::RemindArgs),
+    /// 이 컴퓨터의 프로필들(과 수업 서버의 학생들)의 점수, 정답률, 연속 학습일 순위를 보여줍니다
+    Leaderboard(leaderboard::LeaderboardArgs),
+    /// 설정에 적은 챕터, 태그별 목표를 얼마나 이뤘는지 보여줍니다
+    Progress,
+    /// 이 ID 의 문제를 고치지 않고 내 세션에서만 뺍니다 (프로필마다 따로)
+    Suspend(suspend::SuspendArgs),
+    /// 뺀 문제를 보여주고 골라서 다시 내게 합니다
+    Suspended(suspend::SuspendedArgs),
+    /// 지난 세션을 문제마다 낸 답, 정답, 걸린 시간과 함께 한 문제씩 다시 봅니다
+    Replay(replay::ReplayArgs),
+    /// 고른 문제와 순서를 스냅숏 파일로 얼려서 어느 컴퓨터에서나 같은 모의고사를 풀게 합니다
+    Snapshot(snapshot::SnapshotArgs),
+    /// 시험 설계 파일에 적은 실제 시험의 형식(부분별 문제 수, 배점, 시간, 감점)대로 모의시험을 봅니다
+    Exam(blueprint::ExamArgs),
+    /// 짝짓기 문제의 용어와 정의로 양면 인쇄용 카드 HTML 을 만듭니다
+    PrintCards(cards::PrintCardsArgs),
+    /// 교실 화면에 문제를 크게 띄우고 보기와 정답을 하나씩 보여줍니다 (학생은 종이에 답합니다)
+    Present(present::PresentArgs),
+    /// 설정한 Git 저장소, WebDAV, S3 호환 저장소와 학습 기록을 주고받아 여러 기기의 진도를 맞춥니다
+    Sync(sync::SyncArgs),
+    /// SQLite 문제 데이터베이스를 관리합니다
+    #[cfg(feature = "sqlite")]
+    Db(db::DbArgs),
+    /// 문제 파일에 암호를 걸어 `.enc` 파일로 만듭니다
+    #[cfg(feature = "encrypt")]
+    Encrypt(encrypt::EncryptArgs),
+    /// 나눠 줄 문제 파일에 서명(`.sig`)이나 체크섬(`.sha256`)을 만듭니다
+    #[cfg(feature = "sign")]
+    Sign(sign::SignArgs),
+    /// 브라우저에서 풀 수 있도록 웹 서버를 엽니다
+    #[cfg(feature = "web")]
+    Serve(web::ServeArgs),
+    /// 여러 사람이 각자의 브라우저로 같은 문제를 동시에 푸는 대결을 엽니다
+    #[cfg(feature = "web")]
+    Host(host::HostArgs),
+    /// 디스코드 채널에 문제를 올리고 반응이나 답장으로 받은 답을 사람마다 채점하는 봇을 띄웁니다
+    #[cfg(feature = "discord")]
+    Discord(discord::DiscordArgs),
+    /// 텔레그램 대화방마다 따로 문제를 내는 봇을 띄웁니다
+    #[cfg(feature = "telegram")]
+    Telegram(telegram::TelegramArgs),
 }
 
-#[derive(Deserialize)]
-struct MatchingQuestion {
-    question: String,
-    pairs: Vec<MatchingPair>,
+#[derive(clap::Args, Default)]
+struct QuizArgs {
+    /// 내장된 문제 대신 사용할 YAML 문제 파일
+    #[arg(long, value_name = "FILE")]
+    bank: Option<PathBuf>,
+    /// YAML 대신 `terms db import`로 만든 SQLite 데이터베이스에서 문제를 읽습니다
+    #[cfg(feature = "sqlite")]
+    #[arg(long, value_name = "FILE", conflicts_with = "bank")]
+    db: Option<PathBuf>,
+    /// 챕터를 묻지 않고 이 챕터들의 문제를 풉니다 (예: 1-3,5)
+    #[arg(long)]
+    chapters: Option<ChapterSet>,
+    /// 이 태그가 붙은 문제만 풉니다 (여러 번 지정 가능)
+    #[arg(long = "tag", value_name = "TAG")]
+    tags: Vec<String>,
+    /// 이 유형의 문제만 풉니다 (여러 번 지정 가능)
+    #[arg(long = "kind", value_name = "KIND")]
+    kinds: Vec<Kind>,
+    /// 작성 중(draft)인 문제도 함께 풉니다
+    #[arg(long)]
+    include_drafts: bool,
+    /// 시험 모드: 문제마다 정답을 알려주지 않고 끝난 뒤에 한꺼번에 보여줍니다
+    #[arg(long)]
+    exam: bool,
+    /// 시험 모드에서 틀린 문제마다 배점의 이 비율만큼 감점합니다 (건너뛴 문제는 0점)
+    #[arg(long, value_name = "FRACTION", requires = "exam")]
+    negative_marking: Option<f64>,
+    /// 시험 모드에서 답마다 확정을 받지 않고, 끝난 뒤 답안지를 보여주지 않습니다
+    #[arg(long, requires = "exam")]
+    no_confirm: bool,
+    /// 시험 모드의 전체 제한 시간 (예: 30m, 1h30m, 90s). 시간이 지나면 남은 문제는 빈 답안으로 채점합니다
+    #[arg(long, value_name = "DURATION", requires = "exam", value_parser = parse_duration)]
+    time_limit: Option<Duration>,
+    /// 보기가 있는 문제를 전체 화면에서 j/k 등으로 골라 답합니다
+    #[arg(long)]
+    tui: bool,
+    /// 문제를 풀다가 `--bank` 파일이 바뀌면 다시 읽어 남은 문제에 반영합니다
+    #[arg(long, requires = "bank")]
+    watch: bool,
+    /// 세션이 끝나면 결과를 마크다운으로 이 파일에 덧붙입니다
+    #[arg(long, value_name = "FILE")]
+    journal: Option<PathBuf>,
+    /// 반복 연습: 틀리거나 건너뛴 문제를 뒤에 다시 내고, 모두 한 번씩 맞혀야 끝납니다 (점수는 처음 답으로 매깁니다)
+    #[arg(long, conflicts_with = "exam")]
+    drill: bool,
+    /// 이 이름의 짝짓기 묶음만 무작위 방향으로 돌아가며 묻고, 모든 용어를 두 번 연속 맞히면 끝냅니다
+    #[arg(long, value_name = "NAME", conflicts_with_all = ["exam", "drill", "count", "from_snapshot"])]
+    group: Option<String>,
+    /// 틀리면 정답을 보여주기 전에 N번 더 답할 수 있습니다 (점수는 첫 번째 답으로 매깁니다)
+    #[arg(long, value_name = "N", conflicts_with = "exam")]
+    retries: Option<usize>,
+    /// 한글을 칠 수 없을 때 로마자(roman)나 두벌식 글쇠(dubeolsik)로 쓴 답을 한글로 바꿔 채점합니다
+    #[arg(long, value_name = "MODE")]
+    romanized: Option<config::Romanized>,
+    /// 문제의 `translations` 중 이 언어(예: en, en-US)로 옮긴 것을 풉니다. 옮기지 않은 문제는 그대로 냅니다
+    #[arg(long, value_name = "LANG")]
+    lang: Option<String>,
+    /// 문제를 이 시간(초) 동안 먼저 읽게 한 뒤에 보기와 답 입력을 보여줍니다 (빨리 읽고 떠올리는 연습)
+    #[arg(long, value_name = "SECS")]
+    preview: Option<u64>,
+    /// 연습 모드에서 `:50` 으로 틀린 보기의 절반을 지울 수 있는 횟수
+    #[arg(long, value_name = "N", default_value_t = 0)]
+    lifelines: usize,
+    /// 철자 문제와 짝짓기 문제를 보기 없이 뜻만 보고 용어를 직접 써서 풉니다
+    #[arg(long)]
+    recall: bool,
+    /// 문제 수를 묻지 않고 N문제를 무작위로 풉니다
+    #[arg(long, value_name = "N")]
+    count: Option<usize>,
+    /// 문제 순서를 정하는 시드. 같은 시드면 항상 같은 순서로 나옵니다
+    #[arg(long, value_name = "N")]
+    seed: Option<u64>,
+    /// 한 터미널에서 이 사람들이 번갈아 문제를 풀며 겨룹니다 (예: 민수,지연)
+    #[arg(long, value_name = "NAMES", value_delimiter = ',', conflicts_with_all = ["exam", "drill", "group", "from_snapshot", "watch", "journal"])]
+    versus: Vec<String>,
+    /// `--versus` 에서 번갈아 풀지 않고 모두 같은 문제를 차례로 풀어 먼저 맞힌 사람을 가립니다
+    #[arg(long, requires = "versus")]
+    race: bool,
+    /// 지난 세션에서 틀리거나 건너뛴 문제를 N개까지 먼저 풀고 시작합니다 (0이면 끕니다. 생략하면 설정의 warm_up)
+    #[arg(long, value_name = "N", conflicts_with = "from_snapshot")]
+    warm_up: Option<usize>,
+    /// `terms snapshot` 으로 만든 스냅숏의 문제를 그 순서대로 풉니다
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["chapters", "count", "seed", "tags", "kinds"])]
+    from_snapshot: Option<PathBuf>,
+    /// `terms serve --collect` 서버에서 시험을 받아 풀고 답안을 제출합니다
+    #[cfg(feature = "web")]
+    #[arg(long, value_name = "URL")]
+    submit_to: Option<String>,
+    /// 답안을 제출할 때 쓸 이름 (생략하면 묻습니다)
+    #[cfg(feature = "web")]
+    #[arg(long, value_name = "NAME", requires = "submit_to")]
+    student: Option<String>,
+    // `terms exam` 이 정한 시험 설계. 명령줄로는 받지 않습니다
+    #[arg(skip)]
+    blueprint: Option<Box<blueprint::Blueprint>>,
 }
 
-#[derive(Deserialize)]
-struct FillInTheBlankQuestion {
-    question: String,
-    answer: String,
+enum Source {
+    // 어느 파일인지는 고른 챕터를 읽다 틀린 곳을 만났을 때 알려주려고 들고 있습니다
+    Yaml {
+        bank: Bank,
+        file: String,
+    },
+    #[cfg(feature = "sqlite")]
+    Db(db::Database),
 }
 
-#[derive(Deserialize)]
-struct SpellingQuestion {
-    question: String,
-    options: [String; 3],
-    answer: String,
-}
+impl Source {
+    fn open(args: &QuizArgs) -> Self {
+        #[cfg(feature = "sqlite")]
+        if let Some(path) = &args.db {
+            return Source::Db(db::Database::open(path).unwrap_or_else(|e| {
+                exit_with(LoadError::Database {
+                    file: path.display().to_string(),
+                    message: e.to_string(),
+                })
+            }));
+        }
+        let path = args.bank.as_deref();
+        Source::Yaml {
+            bank: load_bank(path),
+            file: file_name(path),
+        }
+    }
+
+    fn points(&self) -> BTreeMap<Kind, f64> {
+        match self {
+            Source::Yaml { bank, .. } => bank.points.clone(),
+            #[cfg(feature = "sqlite")]
+            Source::Db(db) => db.points().unwrap_or_else(|e| db_failed(e)),
+        }
+    }
 
-#[derive(Deserialize)]
-struct Chapter {
-    chapter: u32,
-    #[serde(default)]
-    multiple_choice: Vec<MultipleChoiceQuestion>,
-    #[serde(default)]
-    matching: Vec<MatchingQuestion>,
-    #[serde(default)]
-    fill_in_the_blanks: Vec<FillInTheBlankQuestion>,
-    #[serde(default)]
-    spelling: Vec<SpellingQuestion>,
+    fn chapters(&self) -> Vec<u32> {
+        match self {
+            Source::Yaml { bank, .. } => bank.chapters().to_vec(),
+            #[cfg(feature = "sqlite")]
+            Source::Db(db) => db.chapters().unwrap_or_else(|e| db_failed(e)),
+        }
+    }
+
+    fn load(self, selected: &HashSet<u32>, args: &QuizArgs) -> Vec<Chapter> {
+        let (chapters, normalize) = match self {
+            Source::Yaml { bank, file } => (
+                bank.load(|c| selected.contains(&c))
+                    .unwrap_or_else(|source| exit_with(LoadError::Parse { file, source })),
+                bank.normalize,
+            ),
+            #[cfg(feature = "sqlite")]
+            Source::Db(db) => {
+                let mut chapters: Vec<u32> = selected.iter().copied().collect();
+                chapters.sort_unstable();
+                (
+                    db.load(&chapters, &args.tags, &args.kinds)
+                        .unwrap_or_else(|e| db_failed(e)),
+                    db.normalize().unwrap_or_else(|e| db_failed(e)),
+                )
+            }
+        };
+        prepare(chapters, normalize.as_ref(), args)
+    }
 }
 
-#[derive(Deserialize)]
-struct Questions {
-    chapters: Vec<Chapter>,
+// 읽은 챕터에서 풀 문제만 남기고, 파일 전체의 답 비교 방식과 언어를 적용합니다
+fn prepare(chapters: Vec<Chapter>, normalize: Option<&Normalize>, args: &QuizArgs) -> Vec<Chapter> {
+    chapters
+        .into_iter()
+        .map(|mut c| {
+            c.retain(&args.filter());
+            if let Some(normalize) = normalize {
+                c.default_normalize(normalize);
+            }
+            if let Some(lang) = &args.lang {
+                c.localize(lang);
+            }
+            c
+        })
+        .collect()
 }
 
-trait Askable {
-    fn ask(&self) -> bool;
+#[derive(Default)]
+struct Prompter {
+    exam: bool,
+    tui: bool,
+    deadline: Option<Instant>,
+    // 보기와 답 입력을 보여주기 전에 문제만 읽게 하는 시간
+    preview: Option<Duration>,
+    keys: config::Keys,
+    flagged: bool,
+    reports: Vec<String>,
+    // `:note` 로 남긴 메모. 빈 문자열이면 지웁니다
+    note: Option<String>,
+    // 보기 없이 직접 쓴 답. 틀렸을 때 정답과 어디가 다른지 보여줍니다
+    typed: Option<String>,
+    // 다시 답한 것까지 차례로 낸 답
+    given: Vec<String>,
+    typo: config::Typo,
+    slip: Option<Slip>,
+    romanized: Option<config::Romanized>,
+    // `?` 로 힌트를 볼 정답과 지금까지 본 글자 수
+    hint: Option<String>,
+    hints: usize,
+    // 50:50 으로 지울 수 있는 틀린 보기, 세션에 남은 횟수, 이 문제에서 지운 보기
+    distractors: Vec<usize>,
+    lifelines: usize,
+    eliminated: Vec<usize>,
+    // 50:50 을 쓴 뒤 남은 보기를 다시 보여주도록 (보기 기호, 보기)를 적어 둡니다
+    lines: Vec<(String, String)>,
+    // 문제의 출처와 쪽수, 해설
+    reference: Option<String>,
+    explanation: Option<String>,
+    // 틀렸을 때 다시 답할 수 있는 횟수와 쓴 횟수, 마지막으로 채점한 답의 점수
+    retries: usize,
+    attempts: usize,
+    last: Option<Score>,
+    // `:hangul` 로 끌 수 있습니다
+    converting: bool,
+    revealed: bool,
+    copied: bool,
+    // `:suspend` 로 앞으로 내지 않기로 했습니다
+    suspended: bool,
+    // 시험 모드에서 낼 답을 보여주고 확정받는지, `:back` 으로 앞 문제로 돌아가기로 했는지
+    confirm: bool,
+    back: bool,
+    quit: bool,
+    timed_out: bool,
 }
 
-impl Askable for MultipleChoiceQuestion {
-    fn ask(&self) -> bool {
-        println!("{}", self.question);
-        for (i, option) in self.options.iter().enumerate() {
-            println!("{}. {}", (b'a' + i as u8) as char, option);
+const TIME_WARNINGS: [u64; 3] = [300, 60, 10];
+
+impl Prompter {
+    fn new(exam: bool, tui: bool, deadline: Option<Instant>, keys: config::Keys) -> Self {
+        Self {
+            exam,
+            tui,
+            deadline,
+            keys,
+            ..Default::default()
         }
+    }
 
-        let answer = get_user_input("당신의 답변: ");
-        let is_correct = answer == self.answer.to_string();
+    // 키 설정에 공백만 있는 값도 쓸 수 있도록 앞뒤 공백을 지우지 않은 줄을 돌려줍니다
+    fn read(&mut self, prompt: &str) -> Option<String> {
+        if self.quit {
+            return None;
+        }
+        print!("{}", prompt);
+        io::stdout().flush().unwrap();
+        let Some(deadline) = self.deadline else {
+            return match input::read_line() {
+                input::Line::Text(line) => Some(line),
+                input::Line::Interrupted => self.interrupted(),
+                _ => Some(String::new()),
+            };
+        };
 
-        print_result(is_correct, &self.answer.to_string());
-        is_correct
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                println!(
+                    "\n{}",
+                    styled(Color::Red.bold()).paint("시간이 다 되었습니다!")
+                );
+                self.timed_out = true;
+                return None;
+            }
+            // 다음 경고 시점이나 마감 시각까지만 기다립니다
+            let warning = TIME_WARNINGS
+                .iter()
+                .map(|&secs| Duration::from_secs(secs))
+                .find(|&w| w < remaining);
+            let wait = warning.map_or(remaining, |w| remaining - w);
+            match input::read_line_timeout(wait) {
+                input::Line::Text(line) => return Some(line),
+                input::Line::Closed => return Some(String::new()),
+                input::Line::Interrupted => return self.interrupted(),
+                input::Line::Resized => {}
+                input::Line::Timeout => {
+                    if let Some(w) = warning {
+                        println!(
+                            "\n{}",
+                            styled(Color::Red).paint(format!("남은 시간 {}!", format_duration(w)))
+                        );
+                        print!("{}", prompt);
+                        io::stdout().flush().unwrap();
+                    }
+                }
+            }
+        }
     }
-}
 
-struct SingleMatchingQuestion {
-    term: String,
-    definition: Vec<String>,
-    correct_answer: String,
-}
+    // Ctrl-C 는 `:quit` 처럼 세션을 끝내고 그때까지의 기록을 저장합니다
+    fn interrupted(&mut self) -> Option<String> {
+        println!("\n{}", styled(Color::Yellow).paint("Ctrl-C: 그만둡니다."));
+        self.quit = true;
+        None
+    }
 
-impl SingleMatchingQuestion {
-    fn new(term: String, pairs: &[MatchingPair]) -> Self {
-        let correct_pair = pairs.iter().find(|p| p.term == term).unwrap();
-        let definition = pairs.iter().map(|p| p.definition.clone()).collect();
-        Self {
-            term: correct_pair.term.clone(),
-            definition,
-            correct_answer: correct_pair.definition.clone(),
+    // 문제를 푸는 동안 `:note` 로 남긴 메모와 `:report` 로 남긴 신고를 저장합니다
+    fn save_notes(
+        &self,
+        notes: &mut BTreeMap<String, notes::Note>,
+        bank: &str,
+        question: &(Box<dyn Askable>, u32),
+    ) {
+        if let Some(note) = &self.note {
+            if let Err(e) = notes::set(notes, question.0.as_ref(), note) {
+                println!(
+                    "{} {}\n",
+                    styled(Color::Red).paint("메모를 저장할 수 없습니다:"),
+                    e
+                );
+            }
+        }
+        for note in &self.reports {
+            if let Err(e) = report::append(bank, question.0.as_ref(), question.1, note) {
+                println!(
+                    "{} {}\n",
+                    styled(Color::Red).paint("신고를 저장할 수 없습니다:"),
+                    e
+                );
+            }
         }
     }
-}
 
-impl Askable for SingleMatchingQuestion {
-    fn ask(&self) -> bool {
-        println!("다음 용어에 맞는 정의를 고르세요: {}", self.term);
-        for (i, definition) in self.definition.iter().enumerate() {
-            println!("{}. {}", (i + 1), definition);
+    // 읽는 시간이 끝날 때까지 남은 초를 보여주고, 그동안 입력한 줄은 답으로 받지 않고 버립니다
+    fn countdown(&mut self, preview: Duration) {
+        let end = match self.deadline {
+            Some(deadline) => deadline.min(Instant::now() + preview),
+            None => Instant::now() + preview,
+        };
+        let live = !style::is_plain() && io::stdout().is_terminal();
+        if !live {
+            println!("{}초 동안 문제를 읽으세요.", preview.as_secs());
+        }
+        loop {
+            let remaining = end.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                break;
+            }
+            if live {
+                print!(
+                    "\r\x1b[2K{}",
+                    styled(Color::Cyan).paint(format!(
+                        "읽는 시간 {}초",
+                        remaining.as_millis().div_ceil(1000)
+                    ))
+                );
+                io::stdout().flush().unwrap();
+            }
+            // 남은 초가 바뀔 때마다 다시 그립니다
+            let tick = Duration::from_nanos((remaining.as_nanos() % 1_000_000_000) as u64);
+            let wait = if tick.is_zero() {
+                Duration::from_secs(1)
+            } else {
+                tick
+            };
+            match input::read_line_resizable(wait) {
+                input::Line::Text(_) => {
+                    if live {
+                        print!("\x1b[1A");
+                    }
+                }
+                input::Line::Interrupted => {
+                    self.interrupted();
+                    break;
+                }
+                input::Line::Timeout | input::Line::Resized => {}
+                // 입력이 끝났으면 남은 시간만큼 기다리기만 합니다
+                input::Line::Closed => thread::sleep(remaining),
+            }
         }
+        if live {
+            print!("\r\x1b[2K");
+            io::stdout().flush().unwrap();
+        }
+    }
 
-        let answer: usize = get_user_input("당신의 답변 (정답 번호를 입력하세요): ")
-            .trim()
-            .parse()
-            .unwrap_or(0);
+    // 50:50 으로 지운 보기는 빼고 보여줍니다
+    fn print_options(&self) {
+        let shown: Vec<(&str, &str)> = self
+            .lines
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| !self.eliminated.contains(i))
+            .map(|(_, (marker, option))| (marker.as_str(), option.as_str()))
+            .collect();
+        layout::print_options(&shown);
+    }
 
-        let is_correct =
-            answer != 0 && self.definition.get(answer - 1) == Some(&self.correct_answer);
-        print_result(is_correct, &self.correct_answer);
-        is_correct
+    // 틀린 보기의 절반(올림)을 무작위로 지웁니다. 쓸 수 없으면 그 까닭을 돌려줍니다
+    fn eliminate(&mut self) -> Result<(), &'static str> {
+        if self.exam {
+            return Err("시험 모드에서는 50:50 을 쓸 수 없습니다.");
+        }
+        if self.distractors.is_empty() {
+            return Err("보기가 있는 문제에서만 50:50 을 쓸 수 있습니다.");
+        }
+        if !self.eliminated.is_empty() {
+            return Err("이 문제에서는 이미 50:50 을 썼습니다.");
+        }
+        if self.lifelines == 0 {
+            return Err("남은 50:50 이 없습니다.");
+        }
+        if self.distractors.len() < 2 {
+            return Err("지울 보기가 모자랍니다.");
+        }
+        let count = self.distractors.len().div_ceil(2);
+        self.eliminated = self
+            .distractors
+            .choose_multiple(&mut thread_rng(), count)
+            .copied()
+            .collect();
+        self.lifelines -= 1;
+        println!(
+            "{}",
+            styled(Color::Cyan).paint(format!(
+                "틀린 보기 {}개를 지웠습니다 (남은 50:50 {}번).",
+                count, self.lifelines
+            ))
+        );
+        Ok(())
+    }
+
+    // 명령이 아닌 줄이 들어올 때까지 명령을 처리하고, 들어온 답을 앞뒤 공백 없이 돌려줍니다
+    fn command(&mut self, prompt: &str) -> Option<String> {
+        loop {
+            let input = self.read(prompt)?;
+            let keys = &self.keys;
+            if keys.skip.matches(&input) {
+                return None;
+            } else if keys.flag.matches(&input) {
+                self.flagged = !self.flagged;
+                if self.flagged {
+                    println!("{}", styled(Color::Cyan).paint("이 문제를 표시했습니다."));
+                } else {
+                    println!("{}", styled(Color::Cyan).paint("표시를 해제했습니다."));
+                }
+            } else if let Some(note) = keys.report.strip(&input) {
+                let note = match note {
+                    "" => self.read("무엇이 잘못되었나요? ")?.trim().to_string(),
+                    note => note.to_string(),
+                };
+                self.reports.push(note);
+                println!(
+                    "{}",
+                    styled(Color::Cyan)
+                        .paint("신고를 남겼습니다. `terms triage` 로 모아 볼 수 있습니다.")
+                );
+            } else if keys.suspend.matches(&input) {
+                if self.exam {
+                    println!(
+                        "{}",
+                        styled(Color::Cyan).paint("시험 모드에서는 문제를 뺄 수 없습니다.")
+                    );
+                } else {
+                    self.suspended = true;
+                    return None;
+                }
+            } else if let Some(note) = keys.note.strip(&input) {
+                let note = match note {
+                    "" => self.read("메모 (비우면 지웁니다): ")?.trim().to_string(),
+                    note => note.to_string(),
+                };
+                let message = if note.is_empty() {
+                    "메모를 지웠습니다."
+                } else {
+                    "메모를 남겼습니다. 다음에 이 문제가 나오면 보여줍니다."
+                };
+                println!("{}", styled(Color::Cyan).paint(message));
+                self.note = Some(note);
+            } else if keys.hint.matches(&input) {
+                let message = match &self.hint {
+                    _ if self.exam => "시험 모드에서는 힌트를 볼 수 없습니다.".to_string(),
+                    None => "이 문제에는 힌트가 없습니다.".to_string(),
+                    // 마지막 글자는 직접 써야 합니다
+                    Some(answer) if self.hints + 1 >= frontend::hint_length(answer) => {
+                        "더 보여줄 글자가 없습니다.".to_string()
+                    }
+                    Some(answer) => {
+                        self.hints += 1;
+                        format!("힌트: {}", frontend::hint(answer, self.hints))
+                    }
+                };
+                println!("{}", styled(Color::Cyan).paint(message));
+            } else if keys.fifty.matches(&input) {
+                match self.eliminate() {
+                    Ok(()) => self.print_options(),
+                    Err(message) => println!("{}", styled(Color::Cyan).paint(message)),
+                }
+            } else if keys.hangul.matches(&input) {
+                match self.romanized {
+                    Some(_) => {
+                        self.converting = !self.converting;
+                        let state = if self.converting {
+                            "켰습니다"
+                        } else {
+                            "껐습니다"
+                        };
+                        println!(
+                            "{}",
+                            styled(Color::Cyan).paint(format!("한글로 바꾸기를 {}.", state))
+                        );
+                    }
+                    None => println!(
+                        "{}",
+                        styled(Color::Cyan).paint(
+                            "`--romanized` 나 설정의 `romanized` 로 입력 방식을 먼저 정하세요."
+                        )
+                    ),
+                }
+            } else if keys.reveal.matches(&input) {
+                if self.exam {
+                    println!(
+                        "{}",
+                        styled(Color::Cyan).paint("시험 모드에서는 정답을 볼 수 없습니다.")
+                    );
+                } else {
+                    self.revealed = true;
+                    return None;
+                }
+            } else if keys.copy.matches(&input) {
+                if self.exam {
+                    println!(
+                        "{}",
+                        styled(Color::Cyan).paint("시험 모드에서는 정답을 복사할 수 없습니다.")
+                    );
+                } else {
+                    self.revealed = true;
+                    self.copied = true;
+                    return None;
+                }
+            } else if keys.back.matches(&input) {
+                if self.exam {
+                    self.back = true;
+                    return None;
+                }
+                println!(
+                    "{}",
+                    styled(Color::Cyan)
+                        .paint("시험 모드에서만 앞 문제로 돌아가 답을 고칠 수 있습니다.")
+                );
+            } else if keys.quit.matches(&input) {
+                let prompt = format!("정말 그만둘까요? ({} 입력 시 종료): ", keys.confirm);
+                let confirm = self.read(&prompt)?;
+                if self.keys.confirm.matches(&confirm) {
+                    self.quit = true;
+                    return None;
+                }
+            } else {
+                return Some(input.trim().to_string());
+            }
+        }
     }
-}
 
-impl Askable for FillInTheBlankQuestion {
-    fn ask(&self) -> bool {
-        println!("{}", self.question);
+    // 답한 뒤에 해설과 다시 읽을 곳을 보여주고 문제 사이를 한 줄 띄웁니다
+    fn print_reference(&self) {
+        if let Some(explanation) = &self.explanation {
+            println!("  {} {}", styled(Color::Cyan).paint("해설:"), explanation);
+        }
+        if let Some(reference) = &self.reference {
+            println!("  {} {}", styled(Color::Cyan).paint("출처:"), reference);
+        }
+        println!();
+    }
 
-        let answer = get_user_input("당신의 답변: ");
-        let is_correct = answer.eq_ignore_ascii_case(&self.answer);
+    // 낼 답을 어떻게 알아들었는지 보여주고 Enter 로 확정받습니다. 다른 답을 쓰면 그 줄을 돌려줍니다
+    fn confirmed(&mut self, shown: &str) -> Option<Option<String>> {
+        let shown = match shown.trim() {
+            "" => styled(Color::Yellow).paint("(빈 답안)"),
+            shown => styled(Color::Green).paint(shown),
+        };
+        println!("낼 답: {}", shown);
+        let prompt = format!(
+            "Enter 로 확정, 고치려면 새 답을 쓰세요 ({} 앞 문제로): ",
+            self.keys.back
+        );
+        let line = self.command(&prompt)?;
+        Some((!line.is_empty()).then_some(line))
+    }
 
-        print_result(is_correct, &self.answer);
-        is_correct
+    // 보기 기호나 번호로 낸 답을 돌려줄 답과 보여줄 보기로 바꿉니다
+    fn interpret(
+        &self,
+        answer: String,
+        markers: &[String],
+        options: &[String],
+        numbered: bool,
+    ) -> (String, String) {
+        // 보기 기호(a, b, …)로 답했으면 바꾸지 않습니다
+        if let Some(i) = markers
+            .iter()
+            .position(|m| m.trim_end_matches(". ").eq_ignore_ascii_case(&answer) && !m.is_empty())
+        {
+            return (answer, format!("{}{}", markers[i], options[i]));
+        }
+        let answer = self.hangulize(answer);
+        if numbered {
+            let index = answer.parse::<usize>().ok().and_then(|n| n.checked_sub(1));
+            if let Some((i, option)) = index.and_then(|i| options.get(i).map(|o| (i, o))) {
+                return (option.clone(), format!("{}. {}", i + 1, option));
+            }
+        }
+        (answer.clone(), answer)
+    }
+
+    // 줄 입력으로 받은 답을 알아듣고, 확정을 받으면 고쳐 쓴 답도 같은 식으로 다시 받습니다
+    fn settle(
+        &mut self,
+        mut line: String,
+        markers: &[String],
+        options: &[String],
+        numbered: bool,
+    ) -> Option<String> {
+        let answer = loop {
+            let (answer, shown) = self.interpret(line, markers, options, numbered);
+            if !self.confirm {
+                break answer;
+            }
+            match self.confirmed(&shown)? {
+                Some(next) => line = next,
+                None => break answer,
+            }
+        };
+        self.given.push(answer.clone());
+        Some(answer)
+    }
+
+    // 전체 화면에서 보기를 고르게 합니다. 고른 뒤의 확정과 `:back` 은 줄 입력과 똑같이 받습니다
+    fn pick(
+        &mut self,
+        question: &str,
+        options: &[String],
+        marker: Marker,
+        markers: &[String],
+        prompt: &str,
+    ) -> io::Result<Option<String>> {
+        loop {
+            // 50:50 으로 지운 보기는 빼고 보여줍니다
+            let shown: Vec<usize> = (0..options.len())
+                .filter(|i| !self.eliminated.contains(i))
+                .collect();
+            let outcome = tui::pick(
+                question,
+                &shown
+                    .iter()
+                    .map(|&i| options[i].clone())
+                    .collect::<Vec<_>>(),
+                &shown
+                    .iter()
+                    .map(|&i| markers[i].clone())
+                    .collect::<Vec<_>>(),
+                &self.keys,
+                self.deadline,
+                &mut self.flagged,
+            )?;
+            match outcome {
+                tui::Outcome::Chosen(index) => {
+                    let index = shown[index];
+                    let chosen = format!("{}{}", markers[index], options[index]);
+                    println!("{}{}", prompt, chosen);
+                    let answer = marker.answer(options, index);
+                    if !self.confirm {
+                        self.given.push(answer.clone());
+                        return Ok(Some(answer));
+                    }
+                    return Ok(match self.confirmed(&chosen) {
+                        None => None,
+                        Some(None) => {
+                            self.given.push(answer.clone());
+                            Some(answer)
+                        }
+                        Some(Some(line)) => self.settle(line, markers, options, false),
+                    });
+                }
+                tui::Outcome::Skip => return Ok(None),
+                tui::Outcome::Reveal | tui::Outcome::Copy if self.exam => continue,
+                tui::Outcome::Reveal => {
+                    self.revealed = true;
+                    return Ok(None);
+                }
+                tui::Outcome::Copy => {
+                    self.revealed = true;
+                    self.copied = true;
+                    return Ok(None);
+                }
+                tui::Outcome::Eliminate => {
+                    // 쓸 수 없으면 아무 일도 하지 않습니다
+                    self.eliminate().ok();
+                }
+                // 시험 모드에서만 앞 문제로 돌아갈 수 있습니다
+                tui::Outcome::Back if self.exam => {
+                    self.back = true;
+                    return Ok(None);
+                }
+                tui::Outcome::Back => continue,
+                tui::Outcome::Quit => {
+                    self.quit = true;
+                    return Ok(None);
+                }
+                tui::Outcome::Timeout => {
+                    println!(
+                        "{}",
+                        styled(Color::Red.bold()).paint("시간이 다 되었습니다!")
+                    );
+                    self.timed_out = true;
+                    return Ok(None);
+                }
+            }
+        }
+    }
+
+    // 로마자나 두벌식 글쇠로 쓴 답을 한글로 바꾸고, 바뀐 답을 보여줍니다.
+    // 이미 한글이 들어 있으면 한글을 칠 수 있는 것이므로 그대로 둡니다
+    fn hangulize(&self, input: String) -> String {
+        let Some(mode) = self.romanized.filter(|_| self.converting) else {
+            return input;
+        };
+        if input
+            .chars()
+            .any(|c| matches!(c, '가'..='힣' | 'ㄱ'..='ㅣ'))
+        {
+            return input;
+        }
+        let converted = mode.convert(&input);
+        if converted != input {
+            println!("  → {}", styled(Color::Cyan).paint(&converted));
+        }
+        converted
     }
 }
 
-impl Askable for SpellingQuestion {
-    fn ask(&self) -> bool {
-        println!("{}", self.question);
-        for option in &self.options {
-            println!("{}", option);
+impl Frontend for Prompter {
+    fn show(&mut self, question: &str) {
+        layout::print(question);
+        if let Some(preview) = self.preview.filter(|p| !p.is_zero()) {
+            self.countdown(preview);
+        }
+    }
+
+    fn answer(&mut self, prompt: &str) -> Option<String> {
+        let mut answer = self.command(prompt)?;
+        answer = self.hangulize(answer);
+        while self.confirm {
+            match self.confirmed(&answer)? {
+                Some(line) => answer = self.hangulize(line),
+                None => break,
+            }
+        }
+        self.typed = Some(answer.clone());
+        self.given.push(answer.clone());
+        Some(answer)
+    }
+
+    fn choose(
+        &mut self,
+        question: &str,
+        options: &[String],
+        marker: Marker,
+        prompt: &str,
+    ) -> Option<String> {
+        let markers: Vec<String> = (0..options.len())
+            .map(|i| match marker {
+                Marker::None => String::new(),
+                _ => format!("{}. ", marker.label(i)),
+            })
+            .collect();
+
+        if self.tui && !style::is_plain() && tui::available() {
+            match self.pick(question, options, marker, &markers, prompt) {
+                Ok(answer) => return answer,
+                // 전체 화면을 쓸 수 없는 터미널이면 이번 세션은 줄 입력으로 답합니다
+                Err(e) => {
+                    println!(
+                        "{}",
+                        styled(Color::Yellow).paint(format!(
+                            "터미널을 제어할 수 없어 줄 입력으로 답합니다: {}",
+                            e
+                        ))
+                    );
+                    self.tui = false;
+                }
+            }
+        }
+
+        // 일반 텍스트 모드에서는 번호 없는 보기에도 번호를 붙이고 번호로 답할 수 있게 합니다
+        let numbered = matches!(marker, Marker::None) && style::is_plain();
+        self.lines = markers
+            .iter()
+            .zip(options)
+            .enumerate()
+            .map(|(i, (marker, option))| match numbered {
+                true => (format!("{}. ", i + 1), option.clone()),
+                false => (marker.clone(), option.clone()),
+            })
+            .collect();
+        self.print_options();
+        let line = self.command(prompt)?;
+        self.settle(line, &markers, options, numbered)
+    }
+
+    fn offer_hints(&mut self, correct_answer: &str) {
+        self.hint = Some(correct_answer.to_string());
+    }
+
+    fn hints_taken(&self) -> usize {
+        self.hints
+    }
+
+    fn offer_elimination(&mut self, wrong: &[usize]) {
+        self.distractors = wrong.to_vec();
+    }
+
+    fn regrade(&mut self, answer: &str, correct_answer: &str, score: Score) -> Score {
+        self.slip = hangul::slip(answer, correct_answer);
+        match (self.slip, self.typo) {
+            (Some(_), config::Typo::Partial) => Score::fraction(0.5),
+            (Some(_), config::Typo::Correct) => Score::from_correct(true),
+            _ => score,
+        }
+    }
+
+    fn try_again(&mut self, score: Score) -> bool {
+        self.last = Some(score);
+        if self.exam || score.is_correct() || self.attempts >= self.retries {
+            return false;
+        }
+        self.attempts += 1;
+        let mut message = format!(
+            "틀렸습니다. 다시 해 보세요 ({}/{})",
+            self.attempts, self.retries
+        );
+        if let Some(slip) = self.slip.take() {
+            message = format!("{} {}.", message, slip.message());
         }
+        println!("{}", styled(Color::Red).paint(message));
+        true
+    }
 
-        let answer = get_user_input("당신의 답변: ");
-        let is_correct = answer.eq_ignore_ascii_case(&self.answer);
+    fn result(&mut self, score: Score, correct_answer: &str) {
+        if self.exam {
+            println!();
+            return;
+        }
+        if self.attempts > 0 && self.last.is_some_and(|s| s.is_correct()) {
+            println!(
+                "{} 점수는 첫 번째 답으로 매깁니다 ({})",
+                style::emphasis(Color::Green).paint("다시 풀어 맞혔습니다!"),
+                score
+            );
+        } else {
+            if self.hints > 0 {
+                println!(
+                    "{}",
+                    styled(Color::Cyan).paint(format!("힌트로 {}글자를 보았습니다.", self.hints))
+                );
+            }
+            print_result(score, correct_answer, self.typed.as_deref(), self.slip);
+        }
+        self.print_reference();
+    }
 
-        print_result(is_correct, &self.answer);
-        is_correct
+    fn unanswered(&mut self, correct_answer: &str) -> Score {
+        if self.quit || self.timed_out || self.back {
+            return Score::skipped();
+        }
+        if self.suspended {
+            println!(
+                "{}\n",
+                styled(Color::Cyan).paint(
+                    "이 문제를 앞으로 내지 않습니다. `terms suspended` 로 되돌릴 수 있습니다."
+                )
+            );
+            return Score::skipped();
+        }
+        if self.exam {
+            println!();
+            return Score::skipped();
+        }
+        if self.revealed {
+            println!("정답은 {}", styled(Color::Green).paint(correct_answer));
+        } else if style::is_plain() {
+            println!("건너뜀: 정답은 {}", correct_answer);
+        } else {
+            println!(
+                "{} 정답은 {}",
+                style::emphasis(Color::Cyan).paint("건너뛰었습니다."),
+                styled(Color::Green).paint(correct_answer)
+            );
+        }
+        self.print_reference();
+        if self.revealed {
+            Score::from_correct(false)
+        } else {
+            Score::skipped()
+        }
     }
 }
 
@@ -142,36 +1060,490 @@ fn get_user_input(prompt: &str) -> String {
     print!("{}", prompt);
     io::stdout().flush().unwrap();
 
-    let mut input = String::new();
-    io::stdin().read_line(&mut input).unwrap();
-    input.trim().to_string()
+    match input::read_line() {
+        input::Line::Text(line) => line.trim().to_string(),
+        // 문제를 풀기 전이라 저장할 것이 없으므로 바로 끝냅니다
+        input::Line::Interrupted => std::process::exit(130),
+        _ => String::new(),
+    }
 }
 
-fn print_result(is_correct: bool, correct_answer: &str) {
-    if is_correct {
-        println!("{}", Color::Green.paint("정답!\n"));
+fn parse_duration(s: &str) -> Result<Duration, String> {
+    let s = s.trim();
+    if let Ok(minutes) = s.parse::<u64>() {
+        return Ok(Duration::from_secs(minutes * 60));
+    }
+
+    let mut total = 0;
+    let mut number = String::new();
+    for c in s.chars() {
+        if c.is_ascii_digit() {
+            number.push(c);
+            continue;
+        }
+        let unit = match c {
+            'h' => 3600,
+            'm' => 60,
+            's' => 1,
+            _ => return Err(format!("알 수 없는 시간 단위: {}", c)),
+        };
+        let value: u64 = number.parse().map_err(|_| format!("잘못된 시간: {}", s))?;
+        total += value * unit;
+        number.clear();
+    }
+    if !number.is_empty() || total == 0 {
+        return Err(format!("잘못된 시간: {}", s));
+    }
+    Ok(Duration::from_secs(total))
+}
+
+fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    if secs >= 60 {
+        format!("{}분 {}초", secs / 60, secs % 60)
     } else {
-        println!(
-            "{} 정답은 {}\n",
-            Color::Red.paint("오답입니다!"),
-            Color::Green.paint(correct_answer)
-        );
+        format!("{}초", secs)
+    }
+}
+
+// 반복 연습에서 한 번에 맞히지 못한 문제를 몇 번 만에 맞혔는지 보여줍니다
+fn print_drill(drilled: &BTreeMap<(String, String), (u32, usize, bool)>) {
+    let mut repeated: Vec<_> = drilled
+        .iter()
+        .filter(|(_, &(_, attempts, mastered))| attempts > 1 || !mastered)
+        .collect();
+    if repeated.is_empty() {
+        return;
+    }
+    repeated.sort_by_key(|((_, text), &(chapter, attempts, _))| {
+        (std::cmp::Reverse(attempts), chapter, text.clone())
+    });
+    println!("{}", styled(Color::Yellow).paint("반복 연습 결과"));
+    for ((_, text), &(chapter, attempts, mastered)) in repeated {
+        if mastered {
+            println!("- [챕터 {}] {}: {}번 만에 맞힘", chapter, text, attempts);
+        } else {
+            println!(
+                "- [챕터 {}] {}: {}번 풀었지만 {}",
+                chapter,
+                text,
+                attempts,
+                styled(Color::Red).paint("아직 못 맞힘")
+            );
+        }
+    }
+    println!(
+        "한 번에 맞힌 문제 {}개\n",
+        drilled
+            .values()
+            .filter(|&&(_, attempts, mastered)| attempts == 1 && mastered)
+            .count()
+    );
+}
+
+// `typed` 는 직접 쓴 답, `slip` 은 그 답이 한 자모만 틀렸을 때 틀린 곳입니다
+fn print_result(score: Score, correct_answer: &str, typed: Option<&str>, slip: Option<Slip>) {
+    if style::is_plain() {
+        if score.is_correct() {
+            println!("정답: 맞았습니다.");
+        } else if score.is_partial() {
+            println!("부분 정답: {}. 정답은 {}", score, correct_answer);
+        } else {
+            println!("오답: 정답은 {}", correct_answer);
+        }
+    } else {
+        let (verdict, color) = if score.is_correct() {
+            (Verdict::Correct, Color::Green)
+        } else if score.is_partial() {
+            (Verdict::Partial, Color::Yellow)
+        } else {
+            (Verdict::Wrong, Color::Red)
+        };
+        verdict.print_banner(color.normal());
+        let label = match verdict {
+            Verdict::Correct => "정답!",
+            Verdict::Partial => "부분 정답!",
+            Verdict::Wrong => "오답입니다!",
+        };
+        let label = if style::is_high_contrast() {
+            format!("[{}] {}", verdict.symbol(), label)
+        } else {
+            label.to_string()
+        };
+
+        match verdict {
+            Verdict::Correct => println!("{}", style::emphasis(color).paint(label)),
+            Verdict::Partial => println!(
+                "{} ({}) 정답은 {}",
+                style::emphasis(color).paint(label),
+                score,
+                styled(Color::Green).paint(correct_answer)
+            ),
+            Verdict::Wrong => println!(
+                "{} 정답은 {}",
+                style::emphasis(color).paint(label),
+                styled(Color::Green).paint(correct_answer)
+            ),
+        }
+    }
+
+    if let Some(slip) = slip {
+        let message = styled(Color::Yellow).paint(format!("{}.", slip.message()));
+        if score.is_correct() {
+            println!("  {} 정답은 {}", message, correct_answer);
+        } else {
+            println!("  {}", message);
+        }
+    }
+    if let Some(typed) = typed.filter(|_| !score.is_correct()) {
+        diff::print(typed, correct_answer);
     }
 }
 
 fn main() {
     #[cfg(windows)]
     ansi_term::enable_ansi_support().ok();
-    // let data = fs::read_to_string("questions.yaml").expect("파일을 읽을 수 없습니다");
-    let data = include_str!("../data.yaml");
-    let questions: Questions = serde_yaml::from_str(&data).expect("YAML 파싱 실패");
 
-    let available_chapters: HashSet<u32> = questions.chapters.iter().map(|c| c.chapter).collect();
+    let cli = Cli::parse();
+    style::set_plain(cli.plain);
+    style::set_high_contrast(cli.high_contrast, cli.banner);
+    #[cfg(feature = "sign")]
+    sign::set_insecure(cli.insecure);
+    if let Some(profile) = cli.profile {
+        history::set_profile(profile);
+    }
+    match cli.command {
+        Some(Command::Quiz(args)) => run_quiz(*args),
+        Some(Command::Stats(args)) => stats::run(args),
+        Some(Command::Keys) => config::Config::load().keys.print(),
+        Some(Command::Validate(args)) => validate::run(args),
+        Some(Command::Info(args)) => info::run(args),
+        Some(Command::Add(args)) => add::run(args),
+        Some(Command::Listen(args)) => listen::run(args),
+        Some(Command::Generate(args)) => generate::run(args),
+        Some(Command::Import(args)) => import::run(args),
+        Some(Command::Triage(args)) => report::triage(args),
+        Some(Command::Remind(args)) => remind::run(args),
+        Some(Command::Leaderboard(args)) => leaderboard::run(args),
+        Some(Command::Progress) => progress::run(),
+        Some(Command::Replay(args)) => replay::run(args),
+        Some(Command::Suspend(args)) => suspend::suspend(args),
+        Some(Command::Suspended(args)) => suspend::list(args),
+        Some(Command::Snapshot(args)) => snapshot::run(args),
+        Some(Command::Exam(args)) => blueprint::run(args),
+        Some(Command::PrintCards(args)) => cards::run(args),
+        Some(Command::Present(args)) => present::run(args),
+        Some(Command::Sync(args)) => sync::run(args),
+        #[cfg(feature = "sqlite")]
+        Some(Command::Db(args)) => db::run(args),
+        #[cfg(feature = "encrypt")]
+        Some(Command::Encrypt(args)) => encrypt::run(args),
+        #[cfg(feature = "sign")]
+        Some(Command::Sign(args)) => sign::run(args),
+        #[cfg(feature = "web")]
+        Some(Command::Serve(args)) => web::run(args),
+        #[cfg(feature = "web")]
+        Some(Command::Host(args)) => host::run(args),
+        #[cfg(feature = "discord")]
+        Some(Command::Discord(args)) => discord::run(args),
+        #[cfg(feature = "telegram")]
+        Some(Command::Telegram(args)) => telegram::run(args),
+        None => run_quiz(QuizArgs::default()),
+    }
+}
+
+#[derive(Clone)]
+struct ChapterSet(Vec<u32>);
+
+impl std::str::FromStr for ChapterSet {
+    type Err = String;
+
+    // "1-3,5" 처럼 콤마로 구분한 챕터 번호와 범위를 받습니다
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chapters = Vec::new();
+        for part in s.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+            let parse = |n: &str| {
+                n.trim()
+                    .parse::<u32>()
+                    .map_err(|_| format!("잘못된 챕터 번호: {}", n))
+            };
+            match part.split_once('-') {
+                Some((start, end)) => {
+                    let (start, end) = (parse(start)?, parse(end)?);
+                    if start > end {
+                        return Err(format!("잘못된 챕터 범위: {}", part));
+                    }
+                    chapters.extend(start..=end);
+                }
+                None => chapters.push(parse(part)?),
+            }
+        }
+        chapters.sort_unstable();
+        chapters.dedup();
+        Ok(ChapterSet(chapters))
+    }
+}
+
+const BUILTIN_BANK: &str = "data.yaml";
 
-    let selected_chapters = loop {
+impl QuizArgs {
+    fn filter(&self) -> Filter {
+        Filter {
+            tags: self.tags.clone(),
+            kinds: self.kinds.clone(),
+            include_drafts: self.include_drafts,
+        }
+    }
+
+    fn bank_name(&self) -> String {
+        #[cfg(feature = "sqlite")]
+        let path = self.db.as_deref().or(self.bank.as_deref());
+        #[cfg(not(feature = "sqlite"))]
+        let path = self.bank.as_deref();
+        match path {
+            Some(path) => path
+                .file_name()
+                .unwrap_or(path.as_os_str())
+                .to_string_lossy()
+                .into_owned(),
+            None => BUILTIN_BANK.to_string(),
+        }
+    }
+}
+
+// 문제를 불러오지 못한 까닭. 어느 파일인지 함께 보여줍니다
+#[derive(Debug, thiserror::Error)]
+enum LoadError {
+    #[error("{file}: {source}")]
+    Read { file: String, source: io::Error },
+    #[cfg(feature = "encrypt")]
+    #[error("{file}: {message}")]
+    Decrypt { file: String, message: String },
+    #[error("{file}: {source}")]
+    Parse { file: String, source: ParseError },
+    #[cfg(feature = "sqlite")]
+    #[error("{file}: {message}")]
+    Database { file: String, message: String },
+}
+
+fn exit_with(error: LoadError) -> ! {
+    println!(
+        "{} {}",
+        styled(Color::Red).paint("문제를 불러올 수 없습니다:"),
+        error
+    );
+    std::process::exit(1)
+}
+
+#[cfg(feature = "sqlite")]
+fn db_failed(e: impl std::fmt::Display) -> ! {
+    exit_with(LoadError::Database {
+        file: "데이터베이스".to_string(),
+        message: e.to_string(),
+    })
+}
+
+fn file_name(path: Option<&Path>) -> String {
+    path.map_or(BUILTIN_BANK.to_string(), |p| p.display().to_string())
+}
+
+fn read_text(path: Option<&Path>) -> Result<String, LoadError> {
+    #[cfg(feature = "sign")]
+    if let Some(path) = path {
+        sign::check(path);
+    }
+    let file = file_name(path);
+    Ok(match path {
+        #[cfg(feature = "encrypt")]
+        Some(path) if encrypt::is_encrypted(path) => {
+            encrypt::read(path).map_err(|message| LoadError::Decrypt {
+                file: file.clone(),
+                message,
+            })?
+        }
+        Some(path) => fs::read_to_string(path).map_err(|source| LoadError::Read {
+            file: file.clone(),
+            source,
+        })?,
+        None => include_str!("../data.yaml").to_string(),
+    })
+}
+
+fn load_questions(path: Option<&Path>) -> Questions {
+    read_text(path)
+        .and_then(|data| {
+            Questions::parse(&data).map_err(|source| LoadError::Parse {
+                file: file_name(path),
+                source,
+            })
+        })
+        .unwrap_or_else(|e| exit_with(e))
+}
+
+// 퀴즈처럼 고른 챕터만 풀 때는 챕터 번호만 먼저 읽어 둡니다 ([`Bank`])
+fn load_bank(path: Option<&Path>) -> Bank {
+    read_text(path)
+        .and_then(|data| {
+            Bank::parse(data).map_err(|source| LoadError::Parse {
+                file: file_name(path),
+                source,
+            })
+        })
+        .unwrap_or_else(|e| exit_with(e))
+}
+
+// 템플릿, 스크립트 문제는 시드가 있으면 그 시드로 만들어서 매번(또는 학생마다) 같은 문제가 나오게 합니다
+fn registry(seed: Option<u64>) -> Registry {
+    let seed = seed.unwrap_or_else(|| thread_rng().gen());
+    let mut registry = Registry::new();
+    registry.register(terms::template::Templated::new(seed));
+    #[cfg(feature = "script")]
+    registry.register(terms::script::Scripted::new(seed));
+    registry
+}
+
+// `recall` 이면 보기 없이 떠올려서 쓰는 문제로 바꿀 수 있는 것은 모두 바꿉니다
+fn build_questions(chapters: Vec<Chapter>, seed: Option<u64>, recall: bool) -> BuiltQuestions {
+    let questions = registry(seed).build(chapters).unwrap_or_else(|e| {
+        println!(
+            "{} {}",
+            styled(Color::Red).paint("문제를 만들 수 없습니다:"),
+            e
+        );
+        std::process::exit(1)
+    });
+    if !recall {
+        return questions;
+    }
+    questions
+        .into_iter()
+        .map(|(question, chapter)| (question.recall().unwrap_or(question), chapter))
+        .collect()
+}
+
+// 문제를 뽑는 가중치. 지금은 문제 파일의 `weight` 만 쓰지만 기록에 따른 가중치도 여기에 곱하면 됩니다
+fn frequency(question: &(Box<dyn Askable>, u32)) -> f64 {
+    question.0.frequency()
+}
+
+// 문제 수를 묻지 않고 풀 문제를 골라 섞습니다. 대결, 봇 모드와 스냅숏이 같이 씁니다
+fn deal(quiz: &QuizArgs) -> (BuiltQuestions, BTreeMap<Kind, f64>) {
+    let source = Source::open(quiz);
+    let points = source.points();
+    let selected: HashSet<u32> = match &quiz.chapters {
+        Some(ChapterSet(chapters)) => chapters.iter().copied().collect(),
+        None => source.chapters().into_iter().collect(),
+    };
+    let mut questions = build_questions(source.load(&selected, quiz), quiz.seed, quiz.recall);
+    match quiz.seed {
+        Some(seed) => weighted_shuffle(&mut questions, frequency, &mut StdRng::seed_from_u64(seed)),
+        None => weighted_shuffle(&mut questions, frequency, &mut thread_rng()),
+    }
+    questions.truncate(quiz.count.unwrap_or(questions.len()));
+    (questions, points)
+}
+
+// 답안지의 한 줄: 답한 문제와 그 문제를 표시했는지
+type Sheet = ((Box<dyn Askable>, u32), bool);
+
+// 시험 모드에서 답안을 내기 전에 답안지를 보여주고 고칠 문제를 고르게 합니다. 그대로 내면 None
+fn review(answers: &[history::Answer], deadline: Option<Instant>) -> Option<usize> {
+    if answers.is_empty() {
+        return None;
+    }
+    println!("{}", styled(Color::Yellow.bold()).paint("답안지"));
+    for (i, answer) in answers.iter().enumerate() {
+        let given = match answer.given.last() {
+            Some(given) => styled(Color::Green).paint(given.as_str()),
+            None => styled(Color::Yellow).paint("(빈 답안)"),
+        };
+        println!(
+            "{}. [챕터 {}] {} {} {}",
+            i + 1,
+            answer.chapter,
+            answer.text,
+            styled(Color::Cyan).paint("→"),
+            given
+        );
+    }
+    if let Some(deadline) = deadline {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        println!(
+            "남은 시간 {}",
+            styled(Color::Cyan).paint(format_duration(remaining))
+        );
+    }
+    loop {
+        let input = get_user_input("고칠 문제 번호 (Enter 를 누르면 답안을 냅니다): ");
+        if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            println!(
+                "{}",
+                styled(Color::Red.bold()).paint("시간이 다 되어 이대로 답안을 냅니다.")
+            );
+            return None;
+        }
+        match input.trim() {
+            "" => return None,
+            input => match input.parse::<usize>() {
+                Ok(n) if (1..=answers.len()).contains(&n) => return Some(n - 1),
+                _ => println!(
+                    "{}",
+                    styled(Color::Cyan)
+                        .paint(format!("1부터 {} 사이의 번호를 입력하세요.", answers.len()))
+                ),
+            },
+        }
+    }
+}
+
+// 답안지의 `index` 번째 답을 기록에서 지웁니다. `sheet` 는 그 답을 빼고 남은 답안지입니다
+fn unrecord(
+    index: usize,
+    sheet: &[Sheet],
+    was_flagged: bool,
+    answers: &mut Vec<history::Answer>,
+    missed: &mut Vec<journal::Entry>,
+    flagged: &mut Vec<journal::Entry>,
+) {
+    let answer = answers.remove(index);
+    if !answer.correct {
+        let before = answers[..index].iter().filter(|a| !a.correct).count();
+        missed.remove(before);
+    }
+    if was_flagged {
+        let before = sheet[..index].iter().filter(|(_, f)| *f).count();
+        flagged.remove(before);
+    }
+}
+
+// 이 문제 파일로 푼 지난 세션의 틀리거나 건너뛴 문제를 `limit` 개까지 `questions` 에서 꺼냅니다
+fn warm_up(questions: &mut BuiltQuestions, bank: &str, limit: usize) -> BuiltQuestions {
+    let sessions = history::load().unwrap_or_default();
+    let Some(last) = sessions
+        .iter()
+        .rev()
+        .find(|s| s.bank == bank && !s.answers.is_empty())
+    else {
+        return Vec::new();
+    };
+    let mut warm = Vec::new();
+    for answer in last.answers.iter().filter(|a| !a.correct) {
+        if warm.len() >= limit {
+            break;
+        }
+        if let Some(i) = questions.iter().position(|(q, _)| q.id() == answer.id) {
+            warm.push(questions.remove(i));
+        }
+    }
+    warm
+}
+
+fn select_chapters(chapter_list: &[u32]) -> HashSet<u32> {
+    let available_chapters: HashSet<u32> = chapter_list.iter().copied().collect();
+    loop {
         println!("다음 챕터 목록에서 하나 이상의 챕터를 선택하세요 (콤마로 구분, a를 입력하면 전부 선택):");
-        for chapter in &questions.chapters {
-            println!("챕터 {}", chapter.chapter);
+        for chapter in chapter_list {
+            println!("챕터 {}", chapter);
         }
 
         let input = get_user_input("선택한 챕터: ");
@@ -190,78 +1562,526 @@ fn main() {
             continue;
         };
         break selected_chapters;
-    };
+    }
+}
 
-    println!("풀 문제의 개수를 입력하세요(a를 입력하면 모든 문제를 선택합니다):");
-    let input = get_user_input("");
+fn run_quiz(mut args: QuizArgs) {
+    if let Some(name) = args.group.clone() {
+        return groups::drill(&args, &name);
+    }
+    if !args.versus.is_empty() {
+        return versus::run(&args);
+    }
+    #[cfg(feature = "web")]
+    if let Some(url) = args.submit_to.clone() {
+        return classroom::take(&url, &args);
+    }
+    let config = config::Config::load();
+    let snapshot = args
+        .from_snapshot
+        .clone()
+        .map(|path| snapshot::prepare(&path, &mut args));
+    let mut blueprint = args.blueprint.take();
+    // 스냅숏이나 시험 설계로 정한 시험은 문제를 고르지 않고 정해진 대로 냅니다
+    let fixed = snapshot.is_some() || blueprint.is_some();
+    let source = Source::open(&args);
+    let bank_points = source.points();
+    let chapter_list = source.chapters();
+    let available_chapters: HashSet<u32> = chapter_list.iter().copied().collect();
 
-    let mut all_questions = Vec::new();
+    let selected_chapters = match &args.chapters {
+        Some(ChapterSet(chapters)) => {
+            let chapters: HashSet<u32> = chapters.iter().copied().collect();
+            if !chapters.is_subset(&available_chapters) {
+                println!("문제 파일에 없는 챕터가 포함되어 있습니다.");
+                return;
+            }
+            chapters
+        }
+        None => select_chapters(&chapter_list),
+    };
 
-    for chapter in questions.chapters {
-        if !selected_chapters.contains(&chapter.chapter) {
-            continue;
+    let input = match args.count {
+        _ if fixed => "a".to_string(),
+        Some(count) => count.to_string(),
+        None => {
+            println!("풀 문제의 개수를 입력하세요(a를 입력하면 모든 문제를 선택합니다):");
+            get_user_input("")
         }
+    };
 
-        all_questions.extend(
-            chapter
-                .multiple_choice
-                .into_iter()
-                .map(|q| (Box::new(q) as Box<dyn Askable>, chapter.chapter)),
-        );
-        for matching in &chapter.matching {
-            all_questions.extend(matching.pairs.iter().map(|pair| {
-                (
-                    Box::new(SingleMatchingQuestion::new(
-                        pair.term.clone(),
-                        &matching.pairs,
-                    )) as Box<dyn Askable>,
-                    chapter.chapter,
-                )
-            }));
+    let mut all_questions = build_questions(
+        source.load(&selected_chapters, &args),
+        args.seed,
+        args.recall,
+    );
+    if let Some(snapshot) = &snapshot {
+        all_questions = snapshot.arrange(all_questions).unwrap_or_else(|missing| {
+            println!(
+                "{} 스냅숏을 만든 것과 같은 문제 파일인지 확인하세요.",
+                styled(Color::Red).paint(format!(
+                    "스냅숏의 문제 {}개를 문제 파일에서 찾을 수 없습니다.",
+                    missing
+                ))
+            );
+            std::process::exit(1)
+        });
+    }
+    if let Some(blueprint) = &mut blueprint {
+        all_questions = blueprint
+            .assemble(all_questions, args.seed.unwrap_or_default())
+            .unwrap_or_else(|short| {
+                println!(
+                    "{} {}",
+                    styled(Color::Red).paint("설계대로 낼 문제가 모자랍니다:"),
+                    short
+                );
+                std::process::exit(1)
+            });
+        blueprint.announce();
+    }
+
+    // 스냅숏이나 시험 설계로 정한 시험은 실제 시험과 같은 형식이어야 하므로 빼지 않습니다
+    let mut suspended = suspend::load().unwrap_or_default();
+    if !fixed {
+        let before = all_questions.len();
+        all_questions.retain(|(q, _)| !suspended.contains_key(&q.id()));
+        if all_questions.len() < before {
+            println!(
+                "{}",
+                styled(Color::Cyan).paint(format!(
+                    "뺀 문제 {}개는 내지 않습니다 (`terms suspended`).",
+                    before - all_questions.len()
+                ))
+            );
         }
-        all_questions.extend(
-            chapter
-                .fill_in_the_blanks
-                .into_iter()
-                .map(|q| (Box::new(q) as Box<dyn Askable>, chapter.chapter)),
-        );
-        all_questions.extend(
-            chapter
-                .spelling
-                .into_iter()
-                .map(|q| (Box::new(q) as Box<dyn Askable>, chapter.chapter)),
+    }
+
+    let warm_up = match args.warm_up.or(config.warm_up) {
+        Some(limit) if !fixed => warm_up(&mut all_questions, &args.bank_name(), limit),
+        _ => Vec::new(),
+    };
+    if !warm_up.is_empty() {
+        println!(
+            "{}",
+            styled(Color::Cyan).paint(format!(
+                "지난 세션에서 틀린 문제 {}개를 먼저 풉니다.",
+                warm_up.len()
+            ))
         );
     }
 
     let range = if input == "a" {
         0..all_questions.len()
     } else {
-        let mut rng = thread_rng();
-        all_questions.shuffle(&mut rng);
+        match args.seed {
+            Some(seed) => weighted_shuffle(
+                &mut all_questions,
+                frequency,
+                &mut StdRng::seed_from_u64(seed),
+            ),
+            None => weighted_shuffle(&mut all_questions, frequency, &mut thread_rng()),
+        }
         let num_questions: usize = input.trim().parse().unwrap_or(5);
         0..num_questions.min(all_questions.len())
     };
 
-    let mut score = 0;
+    let negative_marking = args.negative_marking.or(if args.exam {
+        config.negative_marking
+    } else {
+        None
+    });
+
+    let romanized = args.romanized.or(config.romanized);
+    let mut converting = romanized.is_some();
+    let mut lifelines = if args.exam { 0 } else { args.lifelines };
+    let mut lifelines_used = 0;
+    // 시드가 있으면 여러 표현 중 고르는 문장도 매번 같게 합니다
+    let mut phrasing = match args.seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+    };
+    let started_at = chrono::Local::now();
+    let start = Instant::now();
+    let deadline = args.time_limit.map(|limit| start + limit);
+    let mut timed_out = false;
     let mut question_count = 0;
-    for question in all_questions[range.clone()].iter() {
-        question_count += 1;
-        println!(
-            "챕터 {} ({}/{})",
-            Color::Yellow.bold().paint(question.1.to_string()),
-            Color::Yellow.paint(question_count.to_string()),
-            Color::Yellow.paint(range.len().to_string())
+    let mut missed = Vec::new();
+    let mut flagged = Vec::new();
+    let mut answers = Vec::new();
+    let warm_ups = warm_up.len();
+    let mut queue: VecDeque<_> = warm_up
+        .into_iter()
+        .chain(all_questions.drain(range))
+        .collect();
+    // 반복 연습에서 문제마다 (챕터, 답한 횟수, 마지막에 맞혔는지)
+    let mut drilled: BTreeMap<(String, String), (u32, usize, bool)> = BTreeMap::new();
+    let mut notes = notes::load().unwrap_or_default();
+    // 시험 모드에서 답안을 내기 전까지 고칠 수 있도록 답한 문제를 답한 차례로 (문제, 표시했는지) 남겨 둡니다
+    let confirm = args.exam && !args.no_confirm && io::stdin().is_terminal();
+    let mut sheet: Vec<Sheet> = Vec::new();
+    let mut revising: Option<(String, usize)> = None;
+    let mut watcher = match (&args.bank, args.watch) {
+        (Some(path), true) => Some(watch::Watcher::new(path.clone())),
+        _ => None,
+    };
+    loop {
+        if let Some(watcher) = &mut watcher {
+            watcher.poll(&mut queue, &answers, &suspended, &selected_chapters, &args);
+        }
+        let Some(mut popped) = queue.pop_front() else {
+            let chosen = match confirm && !timed_out {
+                true => review(&answers, deadline),
+                false => None,
+            };
+            let Some(index) = chosen else {
+                break;
+            };
+            let (question, was_flagged) = sheet.remove(index);
+            unrecord(
+                index,
+                &sheet,
+                was_flagged,
+                &mut answers,
+                &mut missed,
+                &mut flagged,
+            );
+            drilled.remove(&(question.0.id(), question.0.text().to_string()));
+            question_count -= 1;
+            revising = Some((question.0.id(), index));
+            queue.push_back(question);
+            continue;
+        };
+        // 물을 때마다 새로 만드는 문제는 만든 것으로 바꿔 두어 다시 내거나 고칠 때도 같은 문제가 나옵니다
+        if let Some(generated) = popped.0.generate() {
+            popped.0 = Box::new(generated);
+        }
+        let question = &popped;
+        // 반복 연습에서 다시 나온 문제는 점수와 기록에 넣지 않습니다
+        let key = (question.0.id(), question.0.text().to_string());
+        let tries = drilled.entry(key.clone()).or_insert((question.1, 0, false));
+        tries.1 += 1;
+        let attempt = tries.1;
+        if attempt == 1 {
+            question_count += 1;
+        }
+        let mut prompter = Prompter::new(args.exam, args.tui, deadline, config.keys.clone());
+        prompter.typo = config.typo;
+        prompter.romanized = romanized;
+        prompter.converting = converting;
+        prompter.lifelines = lifelines;
+        prompter.preview = args.preview.map(Duration::from_secs);
+        prompter.reference = question.0.reference();
+        prompter.explanation = question.0.explanation().map(str::to_string);
+        prompter.confirm = confirm;
+        prompter.retries = if args.exam {
+            0
+        } else {
+            args.retries.or(config.retries).unwrap_or(0)
+        };
+        if attempt > 1 {
+            println!(
+                "챕터 {} {}",
+                styled(Color::Yellow.bold()).paint(question.1.to_string()),
+                styled(Color::Yellow).paint(format!("(다시, {}번째)", attempt))
+            );
+        } else if !timed_out {
+            let waiting = queue
+                .iter()
+                .filter(|(q, _)| !drilled.contains_key(&(q.id(), q.text().to_string())))
+                .count();
+            print!(
+                "챕터 {} ({}/{})",
+                styled(Color::Yellow.bold()).paint(question.1.to_string()),
+                styled(Color::Yellow).paint(question_count.to_string()),
+                styled(Color::Yellow).paint((question_count + waiting).to_string())
+            );
+            if question_count <= warm_ups {
+                print!(" {}", styled(Color::Cyan).paint("지난번에 틀린 문제"));
+            }
+            if let Some(deadline) = deadline {
+                let remaining = deadline.saturating_duration_since(Instant::now());
+                print!(
+                    " 남은 시간 {}",
+                    styled(Color::Cyan).paint(format_duration(remaining))
+                );
+            }
+            println!();
+        }
+        // 시험 모드에서는 메모를 보여주지 않습니다
+        if let Some(note) = notes
+            .get(&question.0.id())
+            .filter(|_| !args.exam && !timed_out)
+        {
+            println!("{} {}", styled(Color::Cyan).paint("메모:"), note.note);
+        }
+        let asked_at = Instant::now();
+        let weight = match blueprint.as_ref().and_then(|b| b.marks(&question.0.id())) {
+            Some(marks) => marks,
+            None => weight(question.0.as_ref(), &bank_points, &config.points),
+        };
+        let mut result = if timed_out {
+            Score::skipped()
+        } else {
+            question.0.ask(&mut prompter, &mut phrasing)
+        }
+        .weighted(weight);
+        timed_out |= prompter.timed_out;
+        converting = prompter.converting;
+        lifelines = prompter.lifelines;
+        let lifeline = !prompter.eliminated.is_empty();
+        if lifeline {
+            lifelines_used += 1;
+        }
+        if prompter.copied {
+            let mut text = format!(
+                "{}\n정답: {}",
+                question.0.text(),
+                question.0.correct_answer()
+            );
+            if let Some(explanation) = question.0.explanation() {
+                text += &format!("\n해설: {}", explanation);
+            }
+            match clipboard::copy(&text) {
+                Ok(()) => println!(
+                    "{}\n",
+                    styled(Color::Cyan).paint("정답을 클립보드에 복사했습니다.")
+                ),
+                Err(e) => println!(
+                    "{} {}\n",
+                    styled(Color::Red).paint("클립보드에 복사할 수 없습니다:"),
+                    e
+                ),
+            }
+        }
+        prompter.save_notes(&mut notes, &args.bank_name(), question);
+        if prompter.suspended {
+            let id = question.0.id();
+            if let Err(e) = suspend::add(
+                &mut suspended,
+                question.0.as_ref(),
+                question.1,
+                &args.bank_name(),
+            ) {
+                println!(
+                    "{} {}\n",
+                    styled(Color::Red).paint("뺀 문제 목록을 저장할 수 없습니다:"),
+                    e
+                );
+            }
+            queue.retain(|(q, _)| q.id() != id);
+        }
+        if prompter.quit || prompter.suspended || prompter.back {
+            // 그만두거나 뺀 문제, 앞 문제로 돌아간 문제는 답한 것으로 세지 않습니다
+            if attempt == 1 {
+                question_count -= 1;
+                drilled.remove(&key);
+            } else if let Some(tries) = drilled.get_mut(&key) {
+                tries.1 -= 1;
+            }
+            if prompter.quit {
+                break;
+            }
+            if prompter.back {
+                queue.push_front(popped);
+                match sheet.pop() {
+                    Some((previous, was_flagged)) => {
+                        unrecord(
+                            sheet.len(),
+                            &sheet,
+                            was_flagged,
+                            &mut answers,
+                            &mut missed,
+                            &mut flagged,
+                        );
+                        drilled.remove(&(previous.0.id(), previous.0.text().to_string()));
+                        question_count -= 1;
+                        queue.push_front(previous);
+                    }
+                    None => println!("{}", styled(Color::Cyan).paint("첫 문제입니다.")),
+                }
+            }
+            continue;
+        }
+        if let Some(tries) = drilled.get_mut(&key) {
+            tries.2 = result.is_correct();
+        }
+        if attempt > 1 {
+            if !result.is_correct() {
+                queue.push_back(popped);
+            }
+            continue;
+        }
+        if let Some(fraction) = negative_marking {
+            result = result.penalized(fraction);
+        }
+        let is_correct = result.is_correct();
+        // 답안지에서 고른 문제는 고친 답을 원래 자리에 다시 넣습니다
+        let at = match revising.take() {
+            Some((id, at)) if id == question.0.id() => at,
+            _ => answers.len(),
+        };
+        answers.insert(
+            at,
+            history::Answer {
+                id: question.0.id(),
+                text: question.0.text().to_string(),
+                chapter: question.1,
+                kind: question.0.kind(),
+                group: question.0.group().map(str::to_string),
+                correct: is_correct,
+                score: Some(result),
+                time_ms: asked_at.elapsed().as_millis() as u64,
+                lifeline,
+                options: history::options(question.0.as_ref()),
+                given: history::given(question.0.as_ref(), &prompter.given),
+                expected: question.0.correct_answer(),
+            },
         );
-        if question.0.ask() {
-            score += 1;
+        if !is_correct {
+            let before = answers[..at].iter().filter(|a| !a.correct).count();
+            missed.insert(before, journal::Entry::new(question.0.as_ref(), question.1));
+        }
+        if prompter.flagged {
+            let entry = journal::Entry::new(question.0.as_ref(), question.1);
+            // 답안지는 시험 모드에서만 채우므로, 다른 모드에서는 푼 차례대로 붙입니다
+            if args.exam {
+                let before = sheet[..at.min(sheet.len())]
+                    .iter()
+                    .filter(|(_, f)| *f)
+                    .count();
+                flagged.insert(before, entry);
+            } else {
+                flagged.push(entry);
+            }
+        }
+        if args.drill && !is_correct {
+            queue.push_back(popped);
+        } else if args.exam {
+            sheet.insert(at, (popped, prompter.flagged));
+        }
+    }
+    // 시험 모드에서 고친 답은 원래 답을 지우고 다시 기록했으므로 남은 기록으로 점수를 셉니다
+    let points = answers
+        .iter()
+        .filter_map(|a| a.score)
+        .fold(Score::default(), |mut total, s| {
+            total += s;
+            total
+        });
+    let points = match &blueprint {
+        Some(blueprint) => blueprint.scale(points),
+        None => points,
+    };
+    let score = answers.iter().filter(|a| a.correct).count();
+    let duration = start.elapsed();
+
+    if args.drill {
+        print_drill(&drilled);
+    }
+
+    if args.exam && !missed.is_empty() {
+        println!("{}", styled(Color::Red).paint("틀리거나 건너뛴 문제"));
+        for entry in &missed {
+            println!(
+                "- [챕터 {}] {} {} {}",
+                entry.chapter,
+                entry.text,
+                styled(Color::Cyan).paint("→"),
+                styled(Color::Green).paint(&entry.answer)
+            );
+            if let Some(reference) = &entry.reference {
+                println!("  {} {}", styled(Color::Cyan).paint("출처:"), reference);
+            }
         }
+        println!();
     }
 
     println!(
         "총 {} 문제 중 {} 개 맞췄습니다!",
-        Color::Yellow.paint(question_count.to_string()),
-        Color::Yellow.paint(score.to_string())
+        styled(Color::Yellow).paint(question_count.to_string()),
+        styled(Color::Yellow).paint(score.to_string())
     );
+    println!(
+        "점수: {}",
+        styled(Color::Yellow.bold()).paint(points.to_string())
+    );
+    if lifelines_used > 0 {
+        println!(
+            "50:50 을 {}번 썼습니다.",
+            styled(Color::Cyan).paint(lifelines_used.to_string())
+        );
+    }
+    if let Some(blueprint) = &blueprint {
+        blueprint.report(&answers);
+    }
+    let grading = blueprint.as_ref().and_then(|b| b.grading());
+    let grading = grading.as_ref().unwrap_or(&config.grading);
+    let grade = grading.grade(&points);
+    let passed = grading.passed(&points);
+    if let Some(grade) = grade {
+        println!(
+            "성적: {} ({:.1}%)",
+            styled(Color::Yellow.bold()).paint(grade),
+            points.percent()
+        );
+    }
+    match passed {
+        Some(true) => println!("{}", styled(Color::Green.bold()).paint("합격")),
+        Some(false) => println!("{}", styled(Color::Red.bold()).paint("불합격")),
+        None => {}
+    }
+
+    let record = history::Session {
+        bank: args.bank_name(),
+        started_at,
+        duration_secs: duration.as_secs_f64(),
+        answers,
+    };
+    if let Err(e) = history::append(record) {
+        println!(
+            "{} {}",
+            styled(Color::Red).paint("학습 기록을 저장할 수 없습니다:"),
+            e
+        );
+    }
+
+    if let Some(path) = &args.journal {
+        let mut chapters: Vec<u32> = selected_chapters.into_iter().collect();
+        chapters.sort_unstable();
+        println!("이번 세션에 대한 메모를 남기세요 (빈 줄을 입력하면 끝납니다):");
+        let mut notes = Vec::new();
+        loop {
+            let line = get_user_input("");
+            if line.is_empty() {
+                break;
+            }
+            notes.push(line);
+        }
+
+        let session = journal::Session {
+            started_at,
+            duration,
+            chapters,
+            score,
+            points,
+            grade: grade.map(str::to_string),
+            passed,
+            total: question_count,
+            missed,
+            flagged,
+            notes,
+        };
+        match journal::append(path, &session) {
+            Ok(()) => println!("학습 일지에 기록했습니다: {}", path.display()),
+            Err(e) => println!(
+                "{} {}",
+                styled(Color::Red).paint("학습 일지를 쓸 수 없습니다:"),
+                e
+            ),
+        }
+    }
+
     println!("나가려면 아무 키나 누르세요...");
     get_user_input("");
 }
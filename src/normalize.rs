@@ -0,0 +1,84 @@
+//! 직접 쓴 답을 정답과 비교하기 전에 거치는 정규화 단계들.
+
+use serde::{Deserialize, Serialize};
+use unicode_normalization::UnicodeNormalization;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Step {
+    // 앞뒤 공백 지우기
+    Trim,
+    // 대소문자 무시
+    Case,
+    // 유니코드 정규화(NFKC). 자모가 풀려서 들어온 한글이나 전각 문자를 맞춥니다
+    Unicode,
+    // 글자, 숫자, 공백이 아닌 문장 부호 지우기
+    Punctuation,
+    // 여러 칸의 공백을 한 칸으로
+    Whitespace,
+    // 낱말 끝의 조사(은/는/이/가 등)를 붙여 써도 맞게 봅니다
+    Particles,
+}
+
+// 뒤에서부터 긴 것을 먼저 떼어 봅니다
+const PARTICLES: [&str; 17] = [
+    "에서", "으로", "에게", "한테", "은", "는", "이", "가", "을", "를", "의", "에", "로", "와",
+    "과", "도", "만",
+];
+
+// 예전처럼 앞뒤 공백과 대소문자만 무시하는 것이 기본입니다
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Normalize(pub Vec<Step>);
+
+impl Default for Normalize {
+    fn default() -> Self {
+        Normalize(vec![Step::Trim, Step::Case])
+    }
+}
+
+impl Normalize {
+    pub fn has(&self, step: Step) -> bool {
+        self.0.contains(&step)
+    }
+
+    // 조사를 뺀 나머지 단계를 정한 순서대로 적용합니다
+    pub fn apply(&self, text: &str) -> String {
+        let mut text = text.to_string();
+        for step in &self.0 {
+            text = match step {
+                Step::Trim => text.trim().to_string(),
+                Step::Case => text.to_lowercase(),
+                Step::Unicode => text.nfkc().collect(),
+                Step::Punctuation => text
+                    .chars()
+                    .filter(|c| c.is_alphanumeric() || c.is_whitespace())
+                    .collect(),
+                Step::Whitespace => text.split_whitespace().collect::<Vec<_>>().join(" "),
+                Step::Particles => text,
+            };
+        }
+        text
+    }
+
+    // 정답 쪽에는 조사를 떼지 않으므로 `고양이` 가 `고양` 이 되지 않습니다
+    pub fn matches(&self, expected: &str, answer: &str) -> bool {
+        let expected = self.apply(expected);
+        let answer = self.apply(answer);
+        if expected == answer {
+            return true;
+        }
+        if !self.has(Step::Particles) {
+            return false;
+        }
+        let expected: Vec<&str> = expected.split_whitespace().collect();
+        let answer: Vec<&str> = answer.split_whitespace().collect();
+        expected.len() == answer.len()
+            && expected.iter().zip(&answer).all(|(e, a)| {
+                e == a
+                    || PARTICLES
+                        .iter()
+                        .any(|p| a.strip_suffix(p).is_some_and(|stem| stem == *e))
+            })
+    }
+}
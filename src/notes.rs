@@ -0,0 +1,54 @@
+use crate::history;
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use terms::Askable;
+
+// 공유하는 문제 파일 대신 내 데이터 디렉토리에 문제 ID 별로 남기는 메모
+#[derive(Serialize, Deserialize)]
+pub struct Note {
+    // 문제 파일이 바뀌어도 어느 문제의 메모인지 알 수 있도록 문제를 같이 남깁니다
+    pub question: String,
+    pub note: String,
+    pub at: DateTime<Local>,
+}
+
+fn notes_path() -> PathBuf {
+    history::data_dir().join("notes.yaml")
+}
+
+pub fn load() -> io::Result<BTreeMap<String, Note>> {
+    let data = match fs::read_to_string(notes_path()) {
+        Ok(data) => data,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(BTreeMap::new()),
+        Err(e) => return Err(e),
+    };
+    serde_yaml::from_str(&data).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+// 빈 메모는 지웁니다
+pub fn set(
+    notes: &mut BTreeMap<String, Note>,
+    question: &dyn Askable,
+    note: &str,
+) -> io::Result<()> {
+    if note.is_empty() {
+        notes.remove(&question.id());
+    } else {
+        notes.insert(
+            question.id(),
+            Note {
+                question: question.text().to_string(),
+                note: note.to_string(),
+                at: Local::now(),
+            },
+        );
+    }
+    fs::create_dir_all(history::data_dir())?;
+    let data =
+        serde_yaml::to_string(notes).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    fs::write(notes_path(), data)
+}
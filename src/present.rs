@@ -0,0 +1,372 @@
+use crate::layout::wrap;
+use crate::style::styled;
+use crate::tui::{self, truncate, Screen};
+use crate::{deal, ChapterSet, QuizArgs};
+use ansi_term::Color;
+use crossterm::cursor::MoveTo;
+use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use crossterm::queue;
+use crossterm::style::{Attribute, Color as Ink, Print, SetAttribute, SetForegroundColor};
+use crossterm::terminal::{self, Clear, ClearType};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+use terms::{Askable, Kind};
+use unicode_width::UnicodeWidthStr;
+
+#[derive(clap::Args)]
+pub struct PresentArgs {
+    /// 내장된 문제 대신 사용할 YAML 문제 파일
+    #[arg(long, value_name = "FILE")]
+    bank: Option<PathBuf>,
+    /// YAML 대신 `terms db import`로 만든 SQLite 데이터베이스에서 문제를 읽습니다
+    #[cfg(feature = "sqlite")]
+    #[arg(long, value_name = "FILE", conflicts_with = "bank")]
+    db: Option<PathBuf>,
+    /// 이 챕터들의 문제를 냅니다 (예: 1-3,5). 생략하면 전부
+    #[arg(long)]
+    chapters: Option<ChapterSet>,
+    /// 이 태그가 붙은 문제만 냅니다 (여러 번 지정 가능)
+    #[arg(long = "tag", value_name = "TAG")]
+    tags: Vec<String>,
+    /// 이 유형의 문제만 냅니다 (여러 번 지정 가능)
+    #[arg(long = "kind", value_name = "KIND")]
+    kinds: Vec<Kind>,
+    /// 낼 문제 수 (생략하면 전부)
+    #[arg(short = 'n', long = "count", value_name = "N")]
+    count: Option<usize>,
+    /// 문제를 고르고 섞는 시드. 같은 시드면 같은 문제가 같은 순서로 나옵니다
+    #[arg(long, value_name = "N")]
+    seed: Option<u64>,
+    /// 문제마다 보여줄 제한 시간(초). 0이면 재지 않습니다
+    #[arg(long, value_name = "SECS", default_value_t = 30)]
+    time: u64,
+}
+
+// 화면에 띄울 문제 하나
+struct Slide {
+    chapter: u32,
+    question: String,
+    options: Vec<(String, String)>,
+    answer: String,
+    reference: Option<String>,
+}
+
+// 지금 문제에서 어디까지 보여주었는지
+struct Stage {
+    options: usize,
+    answer: bool,
+    deadline: Option<Instant>,
+    // 멈춘 동안 남아 있던 시간
+    paused: Option<Duration>,
+}
+
+impl Stage {
+    fn new(time: Option<Duration>) -> Self {
+        Self {
+            options: 0,
+            answer: false,
+            deadline: time.map(|time| Instant::now() + time),
+            paused: None,
+        }
+    }
+
+    fn remaining(&self) -> Option<Duration> {
+        self.paused.or_else(|| {
+            self.deadline
+                .map(|deadline| deadline.saturating_duration_since(Instant::now()))
+        })
+    }
+}
+
+// 교실 화면에 띄워 놓고 문제를 하나씩 보여줍니다. 학생은 종이에 답하고, 정답은 진행자가 원할 때 보여줍니다
+pub fn run(args: PresentArgs) {
+    if !tui::available() {
+        println!(
+            "{}",
+            styled(Color::Red).paint("발표 모드는 터미널에서만 쓸 수 있습니다.")
+        );
+        std::process::exit(1);
+    }
+    let quiz = QuizArgs {
+        bank: args.bank.clone(),
+        #[cfg(feature = "sqlite")]
+        db: args.db.clone(),
+        chapters: args.chapters.clone(),
+        tags: args.tags.clone(),
+        kinds: args.kinds.clone(),
+        count: args.count,
+        seed: args.seed,
+        ..Default::default()
+    };
+    let (questions, _) = deal(&quiz);
+    if questions.is_empty() {
+        println!("낼 문제가 없습니다.");
+        return;
+    }
+    let mut phrasing = match args.seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+    };
+    // 앞 문제로 돌아가도 같은 문장이 나오도록 미리 만들어 둡니다
+    let slides: Vec<Slide> = questions
+        .iter()
+        .map(|(question, chapter)| {
+            let generated = question.generate();
+            let question: &dyn Askable = match &generated {
+                Some(generated) => generated,
+                None => question.as_ref(),
+            };
+            Slide {
+                chapter: *chapter,
+                question: question.prompt(&mut phrasing),
+                options: question
+                    .choices()
+                    .map(|(options, marker)| {
+                        options
+                            .iter()
+                            .enumerate()
+                            .map(|(i, option)| match marker.label(i) {
+                                label if label.is_empty() => (String::new(), option.clone()),
+                                label => (format!("{}. ", label), option.clone()),
+                            })
+                            .collect()
+                    })
+                    .unwrap_or_default(),
+                answer: question.correct_answer(),
+                reference: question.reference(),
+            }
+        })
+        .collect();
+
+    let time = (args.time > 0).then(|| Duration::from_secs(args.time));
+    let shown = match present(&slides, time) {
+        Ok(shown) => shown,
+        Err(e) => {
+            println!(
+                "{} {}",
+                styled(Color::Red).paint("화면을 그릴 수 없습니다:"),
+                e
+            );
+            std::process::exit(1);
+        }
+    };
+
+    // 종이에 쓴 답을 바로 매길 수 있도록 보여준 문제의 정답을 남깁니다
+    println!("{}", styled(Color::Yellow.bold()).paint("정답"));
+    for (i, slide) in slides.iter().take(shown).enumerate() {
+        println!(
+            "{}. {} {} {}",
+            i + 1,
+            slide.question.lines().next().unwrap_or_default(),
+            styled(Color::Cyan).paint("→"),
+            styled(Color::Green).paint(&slide.answer)
+        );
+    }
+}
+
+// 보여준 문제 수를 돌려줍니다
+fn present(slides: &[Slide], time: Option<Duration>) -> io::Result<usize> {
+    crate::input::release();
+    let _screen = Screen::enter()?;
+    let mut current = 0;
+    let mut shown = 1;
+    let mut stage = Stage::new(time);
+    let mut drawn = None;
+    loop {
+        // 프로젝터에서 깜박이지 않도록 보이는 것이 바뀔 때만 다시 그립니다
+        let frame = (
+            current,
+            stage.options,
+            stage.answer,
+            stage.remaining().map(|r| r.as_millis().div_ceil(1000)),
+            stage.paused.is_some(),
+            terminal::size()?,
+        );
+        if drawn != Some(frame) {
+            render(slides, current, &stage)?;
+            drawn = Some(frame);
+        }
+        if !event::poll(Duration::from_millis(200))? {
+            continue;
+        }
+        let key = match event::read()? {
+            Event::Key(key) if key.kind != KeyEventKind::Release => key,
+            _ => continue,
+        };
+        let slide = &slides[current];
+        let mut next = false;
+        match key.code {
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => break,
+            KeyCode::Char('q') | KeyCode::Esc => break,
+            // 보기를 하나씩, 다 보였으면 정답을, 정답까지 보였으면 다음 문제를 보여줍니다
+            KeyCode::Char(' ') | KeyCode::Enter | KeyCode::Right | KeyCode::Char('n') => {
+                if stage.options < slide.options.len() {
+                    stage.options += 1;
+                } else if !stage.answer {
+                    stage.answer = true;
+                } else {
+                    next = true;
+                }
+            }
+            KeyCode::Char('a') => {
+                stage.options = slide.options.len();
+                stage.answer = true;
+            }
+            KeyCode::Left | KeyCode::Char('p') | KeyCode::Backspace if current > 0 => {
+                current -= 1;
+                stage = Stage::new(time);
+            }
+            KeyCode::Char('t') => {
+                stage.deadline = time.map(|time| Instant::now() + time);
+                stage.paused = None;
+            }
+            KeyCode::Char('s') => match stage.paused.take() {
+                Some(remaining) => stage.deadline = Some(Instant::now() + remaining),
+                None => stage.paused = stage.remaining(),
+            },
+            _ => {}
+        }
+        if next {
+            if current + 1 == slides.len() {
+                break;
+            }
+            current += 1;
+            shown = shown.max(current + 1);
+            stage = Stage::new(time);
+        }
+    }
+    Ok(shown)
+}
+
+fn render(slides: &[Slide], current: usize, stage: &Stage) -> io::Result<()> {
+    let (cols, rows) = terminal::size()?;
+    let (cols, rows) = (cols.max(1) as usize, rows as usize);
+    let slide = &slides[current];
+    let mut out = io::stdout();
+    queue!(out, Clear(ClearType::All), MoveTo(0, 0))?;
+
+    queue!(
+        out,
+        SetAttribute(Attribute::Dim),
+        Print(truncate(
+            &format!("[{}/{}] 챕터 {}", current + 1, slides.len(), slide.chapter),
+            cols
+        )),
+        SetAttribute(Attribute::Reset)
+    )?;
+    if let Some(remaining) = stage.remaining().filter(|_| !stage.answer) {
+        let secs = remaining.as_millis().div_ceil(1000);
+        let clock = match (secs, stage.paused) {
+            (0, _) => "시간 끝".to_string(),
+            (_, Some(_)) => format!("{}초 (멈춤)", secs),
+            _ => format!("{}초", secs),
+        };
+        let ink = if secs <= 5 { Ink::Red } else { Ink::Yellow };
+        queue!(
+            out,
+            MoveTo(cols.saturating_sub(clock.width() + 1) as u16, 0),
+            SetForegroundColor(ink),
+            SetAttribute(Attribute::Bold),
+            Print(&clock),
+            SetAttribute(Attribute::Reset)
+        )?;
+    }
+
+    // 멀리서도 읽기 쉽도록 가운데 좁은 단에 문제를 굵게, 줄 사이를 띄워 씁니다
+    let column = (cols * 3 / 4).clamp(cols.min(20), cols);
+    let left = (cols - column) / 2;
+    let question = wrap(&slide.question, column, 0);
+    let options: Vec<Vec<String>> = slide
+        .options
+        .iter()
+        .map(|(marker, option)| wrap(&format!("{}{}", marker, option), column, marker.width()))
+        .collect();
+    let answer = wrap(&format!("정답: {}", slide.answer), column, 0);
+    let reference = slide
+        .reference
+        .as_ref()
+        .map(|reference| wrap(&format!("출처: {}", reference), column, 0))
+        .unwrap_or_default();
+    // 보기와 정답을 보여줘도 문제가 움직이지 않도록 다 보였을 때의 높이로 자리를 잡습니다
+    let height = question.len() * 2
+        + options.iter().map(Vec::len).sum::<usize>()
+        + answer.len()
+        + reference.len()
+        + 1;
+    // 맨 윗줄(번호, 시간)과 맨 아랫줄(도움말) 사이에 씁니다. 넘치는 줄은 그리지 않습니다
+    let bottom = rows.saturating_sub(1);
+    let mut row = (rows.saturating_sub(height) / 2).max(2);
+    for line in &question {
+        if row >= bottom {
+            break;
+        }
+        let indent = left + column.saturating_sub(line.width()) / 2;
+        queue!(
+            out,
+            MoveTo(indent as u16, row as u16),
+            SetAttribute(Attribute::Bold),
+            Print(line),
+            SetAttribute(Attribute::Reset)
+        )?;
+        row += 2;
+    }
+    for ((_, option), lines) in slide.options.iter().zip(&options).take(stage.options) {
+        let correct = stage.answer && *option == slide.answer;
+        for line in lines {
+            if row >= bottom {
+                break;
+            }
+            queue!(out, MoveTo(left as u16, row as u16))?;
+            if correct {
+                queue!(
+                    out,
+                    SetForegroundColor(Ink::Green),
+                    SetAttribute(Attribute::Bold),
+                    Print(line),
+                    SetAttribute(Attribute::Reset)
+                )?;
+            } else {
+                queue!(out, Print(line))?;
+            }
+            row += 1;
+        }
+    }
+    row += 1 + options[stage.options..].iter().map(Vec::len).sum::<usize>();
+    for (i, line) in answer.iter().chain(&reference).enumerate() {
+        if !stage.answer || row >= bottom {
+            break;
+        }
+        let indent = left + column.saturating_sub(line.width()) / 2;
+        queue!(out, MoveTo(indent as u16, row as u16))?;
+        if i < answer.len() {
+            queue!(
+                out,
+                SetForegroundColor(Ink::Green),
+                SetAttribute(Attribute::Bold),
+                Print(line),
+                SetAttribute(Attribute::Reset)
+            )?;
+        } else {
+            queue!(
+                out,
+                SetAttribute(Attribute::Dim),
+                Print(line),
+                SetAttribute(Attribute::Reset)
+            )?;
+        }
+        row += 1;
+    }
+
+    let help = "Space 다음  a 정답  ← 이전  t 시간 다시  s 멈춤  q 끝내기";
+    queue!(
+        out,
+        MoveTo(0, rows.saturating_sub(1) as u16),
+        SetAttribute(Attribute::Dim),
+        Print(truncate(help, cols)),
+        SetAttribute(Attribute::Reset)
+    )?;
+    out.flush()
+}
@@ -0,0 +1,177 @@
+use crate::config::{Config, Goal};
+use crate::history::{self, Answer, Session};
+use crate::style::styled;
+use crate::{exit_with, file_name, read_text, LoadError};
+use ansi_term::Color;
+use chrono::{Local, NaiveDate};
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+use terms::{build_questions, Filter, Questions};
+
+enum Status {
+    Met,
+    Pending,
+    Overdue,
+}
+
+pub fn run() {
+    let config = Config::load();
+    if config.goals.is_empty() {
+        println!(
+            "아직 정한 목표가 없습니다. {} 의 goals: 항목에 목표를 적으세요.",
+            history::home_dir().join("config.yaml").display()
+        );
+        return;
+    }
+    let sessions = match history::load() {
+        Ok(sessions) => sessions,
+        Err(e) => {
+            println!(
+                "{} {}",
+                styled(Color::Red).paint("학습 기록을 읽을 수 없습니다:"),
+                e
+            );
+            return;
+        }
+    };
+
+    let today = Local::now().date_naive();
+    // 태그를 찾으려고 읽은 문제 파일 (암호를 건 파일이면 한 번만 묻도록)
+    let mut banks = BTreeMap::new();
+    let mut met = 0;
+    for goal in &config.goals {
+        let tagged = goal
+            .tag
+            .as_ref()
+            .map(|tag| tagged(&mut banks, goal.bank.as_deref(), tag));
+        let recent = recent(goal, &sessions, tagged.as_ref());
+        let correct = recent.iter().filter(|a| a.correct).count();
+        let accuracy = match recent.len() {
+            0 => 0.0,
+            answered => correct as f64 * 100.0 / answered as f64,
+        };
+        let status = if recent.len() >= goal.last && accuracy >= goal.accuracy {
+            met += 1;
+            Status::Met
+        } else if goal.by.is_some_and(|by| by < today) {
+            Status::Overdue
+        } else {
+            Status::Pending
+        };
+        print(goal, &status, recent.len(), accuracy, today);
+    }
+    println!(
+        "\n목표 {}개 중 {}개를 이뤘습니다.",
+        config.goals.len(),
+        styled(Color::Yellow.bold()).paint(met.to_string())
+    );
+}
+
+// 목표에 드는 답 중 가장 최근의 `last` 개
+fn recent<'a>(
+    goal: &Goal,
+    sessions: &'a [Session],
+    tagged: Option<&HashSet<String>>,
+) -> Vec<&'a Answer> {
+    let bank = goal.bank.as_deref().map(bank_name);
+    let answers: Vec<&Answer> = sessions
+        .iter()
+        .filter(|s| bank.as_ref().is_none_or(|bank| &s.bank == bank))
+        .flat_map(|s| &s.answers)
+        .filter(|a| goal.chapter.is_none_or(|chapter| a.chapter == chapter))
+        .filter(|a| tagged.is_none_or(|ids| ids.contains(&a.id)))
+        .collect();
+    answers[answers.len().saturating_sub(goal.last)..].to_vec()
+}
+
+// 학습 기록은 문제 파일을 파일 이름으로 적어 둡니다
+fn bank_name(path: &Path) -> String {
+    path.file_name()
+        .unwrap_or(path.as_os_str())
+        .to_string_lossy()
+        .into_owned()
+}
+
+// 태그가 붙은 문제의 ID
+fn tagged(
+    banks: &mut BTreeMap<Option<PathBuf>, String>,
+    path: Option<&Path>,
+    tag: &str,
+) -> HashSet<String> {
+    let data = banks
+        .entry(path.map(Path::to_path_buf))
+        .or_insert_with(|| read_text(path).unwrap_or_else(|e| exit_with(e)));
+    let mut questions = Questions::parse(data).unwrap_or_else(|source| {
+        exit_with(LoadError::Parse {
+            file: file_name(path),
+            source,
+        })
+    });
+    let filter = Filter {
+        tags: vec![tag.to_string()],
+        kinds: Vec::new(),
+        include_drafts: true,
+    };
+    for chapter in &mut questions.chapters {
+        chapter.retain(&filter);
+    }
+    build_questions(questions.chapters)
+        .iter()
+        .map(|(question, _)| question.id())
+        .collect()
+}
+
+fn print(goal: &Goal, status: &Status, answered: usize, accuracy: f64, today: NaiveDate) {
+    let mut target = Vec::new();
+    if let Some(chapter) = goal.chapter {
+        target.push(format!("챕터 {}", chapter));
+    }
+    if let Some(tag) = &goal.tag {
+        target.push(format!("태그 {}", tag));
+    }
+    if target.is_empty() {
+        target.push("전체".to_string());
+    }
+    let bank = goal
+        .bank
+        .as_deref()
+        .map_or(String::new(), |path| format!(" ({})", bank_name(path)));
+    let (label, color) = match status {
+        Status::Met => ("이룸", Color::Green),
+        Status::Pending => ("진행 중", Color::Yellow),
+        Status::Overdue => ("기한 지남", Color::Red),
+    };
+    println!(
+        "{} {}{}",
+        styled(color.bold()).paint(format!("[{}]", label)),
+        target.join(" · "),
+        bank
+    );
+
+    let mut line = format!(
+        "  최근 {}문제 정답률 {:.0}% / 목표 {:.0}%  {}",
+        goal.last,
+        accuracy,
+        goal.accuracy,
+        bar(accuracy / goal.accuracy.max(1.0))
+    );
+    if answered < goal.last {
+        line.push_str(&format!(" (아직 {}문제만 풂)", answered));
+    }
+    println!("{}", styled(color).paint(line));
+    if let Some(by) = goal.by {
+        let days = (by - today).num_days();
+        let left = match days {
+            0 => "오늘까지".to_string(),
+            days if days > 0 => format!("D-{}", days),
+            days => format!("{}일 지남", -days),
+        };
+        println!("  기한 {} ({})", by.format("%Y-%m-%d"), left);
+    }
+}
+
+fn bar(fraction: f64) -> String {
+    const WIDTH: usize = 20;
+    let filled = ((fraction.clamp(0.0, 1.0) * WIDTH as f64).round() as usize).min(WIDTH);
+    format!("[{}{}]", "#".repeat(filled), "-".repeat(WIDTH - filled))
+}
@@ -0,0 +1,1409 @@
+use crate::error::ParseError;
+use crate::frontend::Frontend;
+use crate::normalize::Normalize;
+use crate::score::Score;
+use rand::seq::SliceRandom;
+use rand::{Rng, RngCore};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::rc::Rc;
+
+#[derive(Serialize, Deserialize)]
+pub struct MultipleChoiceQuestion {
+    pub question: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub variants: Vec<String>,
+    // 보기는 2~8개이고 a, b, c, ... 로 표시합니다
+    pub options: Vec<String>,
+    pub answer: Letters,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Status::is_active")]
+    pub status: Status,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub points: Option<f64>,
+    // 출제 빈도. 3이면 다른 문제보다 세 배 자주 뽑힙니다 (배점은 `points`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weight: Option<f64>,
+    // 다시 읽을 곳: 교과서 이름이나 URL, 쪽수 (`page: 42` 또는 `page: 42-45`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "page"
+    )]
+    pub page: Option<String>,
+    // 답한 뒤에 보여줄 해설. `:copy` 로 정답과 함께 복사합니다
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub explanation: Option<String>,
+    // 언어(`en`, `en-US` 등)마다 옮긴 문제. `--lang` 으로 고릅니다
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub translations: BTreeMap<String, Translation>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct MatchingPair {
+    pub term: String,
+    pub definition: String,
+    // 용어가 들어간 예문. `terms generate cloze` 가 정의 대신 이 문장에서 용어를 빈칸으로 만듭니다
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sentence: Option<String>,
+    // 용어의 발음을 녹음한 파일. 문제 파일이 있는 폴더를 기준으로 한 경로입니다 (`terms listen`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub audio: Option<String>,
+    // 옮긴 문제를 풀어도 기록이 이어지도록 남겨 둔 기본 언어의 용어와 정의 (`Chapter::localize`)
+    #[serde(skip)]
+    pub base: Option<(String, String)>,
+}
+
+impl MatchingPair {
+    fn key(&self) -> (&str, &str) {
+        match &self.base {
+            Some((term, definition)) => (term, definition),
+            None => (&self.term, &self.definition),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct MatchingQuestion {
+    pub question: String,
+    pub pairs: Vec<MatchingPair>,
+    // 옮긴 문제에서도 같은 묶음으로 세도록 남겨 둔 기본 언어의 묶음 이름
+    #[serde(skip)]
+    pub group: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Status::is_active")]
+    pub status: Status,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub points: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weight: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "page"
+    )]
+    pub page: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub explanation: Option<String>,
+    // 직접 쓴 답을 비교하기 전에 거칠 단계. 생략하면 문제 파일의 `normalize` 를 따릅니다
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub normalize: Option<Normalize>,
+    // 용어마다 따로 묻지 않고 묶음 전체를 한 번에 짝짓게 합니다. 맞힌 짝만큼 점수를 줍니다
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub round: bool,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub translations: BTreeMap<String, Translation>,
+}
+
+impl MatchingQuestion {
+    pub fn reference(&self) -> Option<String> {
+        reference(&self.source, &self.page)
+    }
+
+    // 기록과 `--group` 에 쓰는 묶음 이름
+    pub fn group(&self) -> &str {
+        self.group.as_deref().unwrap_or(&self.question)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct FillInTheBlankQuestion {
+    pub question: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub variants: Vec<String>,
+    pub answer: Answers,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Status::is_active")]
+    pub status: Status,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub points: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weight: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "page"
+    )]
+    pub page: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub explanation: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub normalize: Option<Normalize>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub translations: BTreeMap<String, Translation>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct SpellingQuestion {
+    pub question: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub variants: Vec<String>,
+    // 번호를 붙여 보여주는 철자 보기. 비워 두면 보기 없이 철자를 직접 씁니다
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub options: Vec<String>,
+    pub answer: Answers,
+    // 정답 단어의 발음을 녹음한 파일 (`MatchingPair::audio` 와 같음)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub audio: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Status::is_active")]
+    pub status: Status,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub points: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weight: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "page"
+    )]
+    pub page: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub explanation: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub normalize: Option<Normalize>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub translations: BTreeMap<String, Translation>,
+}
+
+fn page<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<Option<String>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Page {
+        Number(u64),
+        Text(String),
+    }
+    Ok(Some(match Page::deserialize(deserializer)? {
+        Page::Number(page) => page.to_string(),
+        Page::Text(page) => page,
+    }))
+}
+
+// 한 언어로 옮긴 문제. 비워 둔 필드는 기본 언어의 것을 씁니다
+#[derive(Default, Serialize, Deserialize)]
+pub struct Translation {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub question: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub variants: Option<Vec<String>>,
+    // 객관식, 철자 문제의 보기. 기본 언어와 같은 순서, 같은 수여야 합니다
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub options: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pairs: Option<Vec<MatchingPair>>,
+    // 빈칸, 철자 문제의 답
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub answer: Option<Answers>,
+}
+
+// `en-US` 이 없으면 `en` 을 찾습니다
+fn translation(
+    translations: &mut BTreeMap<String, Translation>,
+    lang: &str,
+) -> Option<Translation> {
+    let mut translations = std::mem::take(translations);
+    translations.remove(lang).or_else(|| {
+        let base = lang.split(['-', '_']).next()?;
+        translations.remove(base)
+    })
+}
+
+// 옮긴 문장을 쓰면 기본 언어의 다른 표현은 버립니다
+fn translate_text(
+    question: &mut String,
+    variants: &mut Vec<String>,
+    translation: &mut Translation,
+) {
+    if let Some(text) = translation.question.take() {
+        *question = text;
+        *variants = Vec::new();
+    }
+    if let Some(translated) = translation.variants.take() {
+        *variants = translated;
+    }
+}
+
+// 출처와 쪽수를 한 줄로 (예: 생명과학 교과서 42쪽)
+fn reference(source: &Option<String>, page: &Option<String>) -> Option<String> {
+    match (source, page) {
+        (Some(source), Some(page)) => Some(format!("{} {}쪽", source, page)),
+        (Some(source), None) => Some(source.clone()),
+        (None, Some(page)) => Some(format!("{}쪽", page)),
+        (None, None) => None,
+    }
+}
+
+// 정답 하나, 또는 `[color, colour]` 처럼 인정하는 답 여럿. 첫 번째가 대표 정답입니다
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Answers {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl Answers {
+    pub fn all(&self) -> &[String] {
+        match self {
+            Answers::One(answer) => std::slice::from_ref(answer),
+            Answers::Many(answers) => answers,
+        }
+    }
+
+    pub fn primary(&self) -> &str {
+        self.all().first().map_or("", String::as_str)
+    }
+
+    pub fn accepts(&self, answer: &str, normalize: &Normalize) -> bool {
+        self.all().iter().any(|a| normalize.matches(a, answer))
+    }
+}
+
+impl From<String> for Answers {
+    fn from(answer: String) -> Self {
+        Answers::One(answer)
+    }
+}
+
+// 객관식의 정답 글자. 여러 개를 골라야 하는 문제는 `answer: [b, d]` 처럼 씁니다
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Letters {
+    One(char),
+    Many(Vec<char>),
+}
+
+impl Letters {
+    pub fn all(&self) -> &[char] {
+        match self {
+            Letters::One(letter) => std::slice::from_ref(letter),
+            Letters::Many(letters) => letters,
+        }
+    }
+
+    pub fn is_multiple(&self) -> bool {
+        matches!(self, Letters::Many(_))
+    }
+}
+
+impl From<char> for Letters {
+    fn from(letter: char) -> Self {
+        Letters::One(letter)
+    }
+}
+
+impl fmt::Display for Letters {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let letters: Vec<String> = self.all().iter().map(char::to_string).collect();
+        write!(f, "{}", letters.join(", "))
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Status {
+    Draft,
+    #[default]
+    Active,
+    Retired,
+}
+
+impl Status {
+    pub fn is_active(&self) -> bool {
+        *self == Status::Active
+    }
+}
+
+#[derive(Serialize, Deserialize, Default)]
+pub struct Chapter {
+    pub chapter: u32,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub multiple_choice: Vec<MultipleChoiceQuestion>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub matching: Vec<MatchingQuestion>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fill_in_the_blanks: Vec<FillInTheBlankQuestion>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub spelling: Vec<SpellingQuestion>,
+    // 기본 유형이 아닌 키는 그대로 두었다가 등록된 유형([`crate::Registry`])이 읽습니다
+    #[serde(flatten)]
+    pub custom: BTreeMap<String, serde_yaml::Value>,
+}
+
+// 태그, 유형, 상태로 풀 문제를 고릅니다
+#[derive(Default)]
+pub struct Filter {
+    pub tags: Vec<String>,
+    pub kinds: Vec<Kind>,
+    pub include_drafts: bool,
+}
+
+impl Chapter {
+    // 문제를 `lang` 으로 옮깁니다. 옮긴 문제도 학습 기록에서 같은 문제로 남도록 기본 언어의 ID 를 붙여 둡니다
+    pub fn localize(&mut self, lang: &str) {
+        for q in &mut self.multiple_choice {
+            let Some(mut t) = translation(&mut q.translations, lang) else {
+                continue;
+            };
+            if q.id.is_none() {
+                q.id = Some(q.id());
+            }
+            translate_text(&mut q.question, &mut q.variants, &mut t);
+            if let Some(options) = t.options.filter(|o| o.len() == q.options.len()) {
+                q.options = options;
+            }
+        }
+        for q in &mut self.matching {
+            let Some(mut t) = translation(&mut q.translations, lang) else {
+                continue;
+            };
+            if q.group.is_none() {
+                q.group = Some(q.question.clone());
+            }
+            if let Some(text) = t.question.take() {
+                q.question = text;
+            }
+            // 짝의 순서와 수가 같을 때만 옮긴 짝으로 바꿉니다
+            if let Some(mut pairs) = t.pairs.filter(|p| p.len() == q.pairs.len()) {
+                for (pair, base) in pairs.iter_mut().zip(&q.pairs) {
+                    let (term, definition) = base.key();
+                    pair.base = Some((term.to_string(), definition.to_string()));
+                }
+                q.pairs = pairs;
+            }
+        }
+        for q in &mut self.fill_in_the_blanks {
+            let Some(mut t) = translation(&mut q.translations, lang) else {
+                continue;
+            };
+            if q.id.is_none() {
+                q.id = Some(q.id());
+            }
+            translate_text(&mut q.question, &mut q.variants, &mut t);
+            if let Some(answer) = t.answer {
+                q.answer = answer;
+            }
+        }
+        for q in &mut self.spelling {
+            let Some(mut t) = translation(&mut q.translations, lang) else {
+                continue;
+            };
+            if q.id.is_none() {
+                q.id = Some(q.id());
+            }
+            translate_text(&mut q.question, &mut q.variants, &mut t);
+            if let Some(options) = t.options.filter(|o| o.len() == q.options.len()) {
+                q.options = options;
+            }
+            if let Some(answer) = t.answer {
+                q.answer = answer;
+            }
+        }
+    }
+
+    // 답을 비교할 단계를 따로 정하지 않은 문제에 `normalize` 를 씁니다
+    pub fn default_normalize(&mut self, normalize: &Normalize) {
+        let fill = |n: &mut Option<Normalize>| {
+            n.get_or_insert_with(|| normalize.clone());
+        };
+        self.matching
+            .iter_mut()
+            .for_each(|q| fill(&mut q.normalize));
+        self.fill_in_the_blanks
+            .iter_mut()
+            .for_each(|q| fill(&mut q.normalize));
+        self.spelling
+            .iter_mut()
+            .for_each(|q| fill(&mut q.normalize));
+    }
+
+    pub fn retain(&mut self, filter: &Filter) {
+        let keep = |kind: Kind, tags: &[String], status: Status| {
+            let status_ok = match status {
+                Status::Active => true,
+                Status::Draft => filter.include_drafts,
+                Status::Retired => false,
+            };
+            status_ok
+                && (filter.kinds.is_empty() || filter.kinds.contains(&kind))
+                && (filter.tags.is_empty() || tags.iter().any(|t| filter.tags.contains(t)))
+        };
+        self.multiple_choice
+            .retain(|q| keep(Kind::MultipleChoice, &q.tags, q.status));
+        self.matching
+            .retain(|q| keep(Kind::Matching, &q.tags, q.status));
+        self.fill_in_the_blanks
+            .retain(|q| keep(Kind::FillInTheBlank, &q.tags, q.status));
+        self.spelling
+            .retain(|q| keep(Kind::Spelling, &q.tags, q.status));
+        for questions in self.custom.values_mut() {
+            if let serde_yaml::Value::Sequence(questions) = questions {
+                questions.retain(|q| {
+                    let field = |name| q.get(name).cloned().unwrap_or_default();
+                    let tags: Vec<String> =
+                        serde_yaml::from_value(field("tags")).unwrap_or_default();
+                    let status = serde_yaml::from_value(field("status")).unwrap_or_default();
+                    keep(Kind::Custom, &tags, status)
+                });
+            }
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Questions {
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub points: BTreeMap<Kind, f64>,
+    // 이 문제 파일의 직접 쓰는 답을 비교하기 전에 거칠 단계 (예: `[trim, case, particles]`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub normalize: Option<Normalize>,
+    pub chapters: Vec<Chapter>,
+}
+
+impl Questions {
+    pub fn parse(yaml: &str) -> Result<Self, ParseError> {
+        serde_path_to_error::deserialize(serde_yaml::Deserializer::from_str(yaml))
+            .map_err(|e| ParseError::new(yaml, e))
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+#[serde(rename_all = "snake_case")]
+pub enum Kind {
+    MultipleChoice,
+    Matching,
+    FillInTheBlank,
+    Spelling,
+    // 라이브러리를 쓰는 쪽에서 등록한 유형
+    Custom,
+}
+
+impl Kind {
+    pub const ALL: [Kind; 5] = [
+        Kind::MultipleChoice,
+        Kind::Matching,
+        Kind::FillInTheBlank,
+        Kind::Spelling,
+        Kind::Custom,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Kind::MultipleChoice => "객관식",
+            Kind::Matching => "짝짓기",
+            Kind::FillInTheBlank => "빈칸",
+            Kind::Spelling => "철자",
+            Kind::Custom => "기타",
+        }
+    }
+
+    pub fn code(self) -> &'static str {
+        match self {
+            Kind::MultipleChoice => "mc",
+            Kind::Matching => "match",
+            Kind::FillInTheBlank => "fill",
+            Kind::Spelling => "spell",
+            Kind::Custom => "custom",
+        }
+    }
+}
+
+pub trait Askable {
+    fn kind(&self) -> Kind;
+    fn text(&self) -> &str;
+    fn correct_answer(&self) -> String;
+    // 이번에 보여줄 문제 문장 (여러 표현이 있으면 그중 하나)
+    fn prompt(&self, rng: &mut dyn RngCore) -> String;
+    fn grade(&self, answer: &str) -> Score;
+
+    fn choices(&self) -> Option<(&[String], Marker)> {
+        None
+    }
+
+    fn explicit_id(&self) -> Option<&str> {
+        None
+    }
+
+    fn points(&self) -> Option<f64> {
+        None
+    }
+
+    fn frequency(&self) -> f64 {
+        1.0
+    }
+
+    // 답한 뒤에 보여줄 다시 읽을 곳
+    fn reference(&self) -> Option<String> {
+        None
+    }
+
+    fn explanation(&self) -> Option<&str> {
+        None
+    }
+
+    // 보기 없이 떠올려서 써야 하는 문제로 바꾼 것. 바꿀 수 없는 유형이면 없습니다
+    fn recall(&self) -> Option<Box<dyn Askable>> {
+        None
+    }
+
+    // 짝짓기 문제면 이 용어가 들어 있던 묶음의 이름(`question`)
+    fn group(&self) -> Option<&str> {
+        None
+    }
+
+    // 물을 때마다 내용을 새로 만드는 문제(스크립트 문제)면 이번에 낼 문제.
+    // 묻는 쪽은 이것을 보여주고 채점, 기록까지 이것으로 합니다
+    fn generate(&self) -> Option<GeneratedQuestion> {
+        None
+    }
+
+    fn id(&self) -> String {
+        if let Some(id) = self.explicit_id() {
+            return id.to_string();
+        }
+        key_id(self.kind(), self.text(), &self.correct_answer())
+    }
+
+    fn ask(&self, frontend: &mut dyn Frontend, rng: &mut dyn RngCore) -> Score {
+        let question = self.prompt(rng);
+        frontend.show(&question);
+
+        // 다시 풀게 해도 점수는 첫 번째 답으로 매깁니다
+        let mut first = None;
+        loop {
+            let answer = match self.choices() {
+                Some((options, marker)) => {
+                    // 여러 개를 고르는 문제에서 정답 하나만 고르면 부분 점수이므로 지우지 않습니다
+                    let wrong: Vec<usize> = (0..options.len())
+                        .filter(|&i| self.grade(&marker.answer(options, i)).earned <= 0.0)
+                        .collect();
+                    frontend.offer_elimination(&wrong);
+                    frontend.choose(&question, options, marker, marker.prompt())
+                }
+                None => {
+                    frontend.offer_hints(&self.correct_answer());
+                    frontend.answer("당신의 답변: ")
+                }
+            };
+            let Some(answer) = answer else {
+                return match first {
+                    Some(score) => {
+                        frontend.result(score, &self.correct_answer());
+                        score
+                    }
+                    None => frontend.unanswered(&self.correct_answer()),
+                };
+            };
+            let mut score = self.grade(&answer);
+            if self.choices().is_none() && !score.is_correct() && !score.is_partial() {
+                score = frontend.regrade(&answer, &self.correct_answer(), score);
+            }
+            let hints = frontend.hints_taken();
+            if hints > 0 {
+                score = score.hinted(hints, crate::frontend::hint_length(&self.correct_answer()));
+            }
+
+            let first = *first.get_or_insert(score);
+            if !frontend.try_again(score) {
+                frontend.result(first, &self.correct_answer());
+                return first;
+            }
+        }
+    }
+}
+
+impl MultipleChoiceQuestion {
+    pub const MIN_OPTIONS: usize = 2;
+    pub const MAX_OPTIONS: usize = 8;
+
+    // 보기 수에 맞는 정답 글자의 범위 (보기가 5개면 a~e)
+    pub fn letters(&self) -> std::ops::RangeInclusive<char> {
+        let last = self.options.len().clamp(1, 26) - 1;
+        'a'..=(b'a' + last as u8) as char
+    }
+}
+
+impl Askable for MultipleChoiceQuestion {
+    fn kind(&self) -> Kind {
+        Kind::MultipleChoice
+    }
+
+    fn explicit_id(&self) -> Option<&str> {
+        self.id.as_deref()
+    }
+
+    fn points(&self) -> Option<f64> {
+        self.points
+    }
+
+    fn frequency(&self) -> f64 {
+        self.weight.unwrap_or(1.0)
+    }
+
+    fn reference(&self) -> Option<String> {
+        reference(&self.source, &self.page)
+    }
+
+    fn explanation(&self) -> Option<&str> {
+        self.explanation.as_deref()
+    }
+
+    fn text(&self) -> &str {
+        &self.question
+    }
+
+    fn correct_answer(&self) -> String {
+        let answers: Vec<String> = self
+            .answer
+            .all()
+            .iter()
+            .map(|&letter| {
+                let index = (letter as u8).wrapping_sub(b'a') as usize;
+                match self.options.get(index) {
+                    Some(option) => format!("{}. {}", letter, option),
+                    None => letter.to_string(),
+                }
+            })
+            .collect();
+        answers.join(", ")
+    }
+
+    fn prompt(&self, rng: &mut dyn RngCore) -> String {
+        let question = phrasing(&self.question, &self.variants, rng);
+        match self.answer.is_multiple() {
+            true => format!("{} (정답을 모두 고르세요. 예: b d)", question),
+            false => question.to_string(),
+        }
+    }
+
+    fn choices(&self) -> Option<(&[String], Marker)> {
+        Some((&self.options, Marker::Letter))
+    }
+
+    // 여러 개를 고르는 문제는 맞힌 보기 수에서 잘못 고른 보기 수를 빼서 부분 점수를 줍니다
+    fn grade(&self, answer: &str) -> Score {
+        let letters = match &self.answer {
+            Letters::One(letter) => return Score::from_correct(answer == letter.to_string()),
+            Letters::Many(letters) => letters,
+        };
+        let chosen: BTreeSet<char> = answer
+            .chars()
+            .filter(char::is_ascii_alphabetic)
+            .map(|c| c.to_ascii_lowercase())
+            .collect();
+        let right = chosen.iter().filter(|c| letters.contains(c)).count();
+        let wrong = chosen.len() - right;
+        Score::fraction((right as f64 - wrong as f64) / letters.len().max(1) as f64)
+    }
+}
+
+pub struct SingleMatchingQuestion {
+    // 보여주는 쪽(거꾸로 묻는 문제면 정의)과 고를 보기(거꾸로면 용어들).
+    // 보기는 같은 묶음의 용어끼리 같이 씁니다
+    term: String,
+    definition: Rc<[String]>,
+    correct_answer: String,
+    reversed: bool,
+    // 기본 언어의 용어와 정의로 만든 id
+    id: String,
+    group: Rc<str>,
+    normalize: Normalize,
+    points: Option<f64>,
+    weight: Option<f64>,
+    reference: Option<String>,
+    explanation: Option<String>,
+}
+
+impl SingleMatchingQuestion {
+    pub fn new(term: String, matching: &MatchingQuestion) -> Self {
+        let pair = matching.pairs.iter().find(|p| p.term == term).unwrap();
+        Self::with(
+            matching,
+            pair,
+            definitions(matching),
+            matching.group().into(),
+        )
+    }
+
+    // 묶음의 모든 용어. 정의 목록은 한 번만 만들어 나눠 씁니다
+    pub fn all(matching: &MatchingQuestion) -> impl Iterator<Item = Self> + '_ {
+        let definitions = definitions(matching);
+        let group: Rc<str> = matching.group().into();
+        matching
+            .pairs
+            .iter()
+            .map(move |pair| Self::with(matching, pair, definitions.clone(), group.clone()))
+    }
+
+    fn with(
+        matching: &MatchingQuestion,
+        pair: &MatchingPair,
+        definitions: Rc<[String]>,
+        group: Rc<str>,
+    ) -> Self {
+        Self {
+            term: pair.term.clone(),
+            definition: definitions,
+            correct_answer: pair.definition.clone(),
+            reversed: false,
+            id: key_id(Kind::Matching, pair.key().0, pair.key().1),
+            group,
+            normalize: matching.normalize.clone().unwrap_or_default(),
+            // 묶음 전체에 매긴 점수를 용어마다 나눠 줍니다
+            points: matching.points.map(|p| p / matching.pairs.len() as f64),
+            // 출제 빈도는 나누지 않고 용어마다 그대로 씁니다
+            weight: matching.weight,
+            reference: matching.reference(),
+            explanation: matching.explanation.clone(),
+        }
+    }
+
+    // 정의를 보여주고 묶음의 용어 중에서 고르게 합니다
+    pub fn reversed(term: String, matching: &MatchingQuestion) -> Self {
+        let pair = matching.pairs.iter().find(|p| p.term == term).unwrap();
+        let (term, definition) = pair.key();
+        let forward = Self::new(pair.term.clone(), matching);
+        Self {
+            term: forward.correct_answer,
+            definition: matching.pairs.iter().map(|p| p.term.clone()).collect(),
+            correct_answer: forward.term,
+            reversed: true,
+            id: key_id(Kind::Matching, definition, term),
+            ..forward
+        }
+    }
+}
+
+// 묶음의 용어를 모두 보여주고 한 번에 짝짓게 하는 문제 (`round: true`)
+pub struct MatchingRoundQuestion {
+    // 기본 언어의 묶음 이름과 용어로 만든 id
+    id: String,
+    question: String,
+    group: String,
+    terms: Vec<String>,
+    // 보여줄 순서로 섞은 정의. 채점할 때도 같은 순서가 되도록 내용으로 섞습니다
+    definitions: Vec<String>,
+    // 용어마다 맞는 정의의 번호
+    answer: Vec<usize>,
+    points: Option<f64>,
+    weight: Option<f64>,
+    reference: Option<String>,
+    explanation: Option<String>,
+}
+
+impl MatchingRoundQuestion {
+    pub fn new(matching: &MatchingQuestion) -> Self {
+        let mut definitions: Vec<String> = definitions(matching).to_vec();
+        definitions.sort_by_key(|d| fnv1a(d));
+        let answer = matching
+            .pairs
+            .iter()
+            .map(|p| definitions.iter().position(|d| *d == p.definition).unwrap() + 1)
+            .collect();
+        let terms: Vec<&str> = matching.pairs.iter().map(|p| p.key().0).collect();
+        Self {
+            id: key_id(Kind::Matching, matching.group(), &terms.join("\0")),
+            question: matching.question.clone(),
+            group: matching.group().to_string(),
+            terms: matching.pairs.iter().map(|p| p.term.clone()).collect(),
+            definitions,
+            answer,
+            points: matching.points,
+            weight: matching.weight,
+            reference: matching.reference(),
+            explanation: matching.explanation.clone(),
+        }
+    }
+}
+
+impl Askable for MatchingRoundQuestion {
+    fn kind(&self) -> Kind {
+        Kind::Matching
+    }
+
+    fn explicit_id(&self) -> Option<&str> {
+        Some(&self.id)
+    }
+
+    fn points(&self) -> Option<f64> {
+        self.points
+    }
+
+    fn frequency(&self) -> f64 {
+        self.weight.unwrap_or(1.0)
+    }
+
+    fn reference(&self) -> Option<String> {
+        self.reference.clone()
+    }
+
+    fn explanation(&self) -> Option<&str> {
+        self.explanation.as_deref()
+    }
+
+    fn text(&self) -> &str {
+        &self.question
+    }
+
+    fn group(&self) -> Option<&str> {
+        Some(&self.group)
+    }
+
+    fn correct_answer(&self) -> String {
+        let answer: Vec<String> = self.answer.iter().map(usize::to_string).collect();
+        answer.join(" ")
+    }
+
+    fn prompt(&self, _rng: &mut dyn RngCore) -> String {
+        let mut prompt = self.question.clone();
+        for (i, term) in self.terms.iter().enumerate() {
+            prompt += &format!("\n  {}. {}", Marker::Letter.label(i), term);
+        }
+        for (i, definition) in self.definitions.iter().enumerate() {
+            prompt += &format!("\n  {}. {}", i + 1, definition);
+        }
+        let example: Vec<String> = (1..=self.terms.len()).map(|n| n.to_string()).collect();
+        prompt += &format!(
+            "\n용어 {}~{} 순서대로 정의 번호를 띄어 쓰세요 (예: {})",
+            Marker::Letter.label(0),
+            Marker::Letter.label(self.terms.len().saturating_sub(1)),
+            example.join(" ")
+        );
+        prompt
+    }
+
+    // 맞힌 짝의 비율만큼 점수를 줍니다
+    fn grade(&self, answer: &str) -> Score {
+        let given: Vec<Option<usize>> = answer
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|s| !s.is_empty())
+            .map(|s| s.parse().ok())
+            .collect();
+        let matched = self
+            .answer
+            .iter()
+            .zip(&given)
+            .filter(|(expected, given)| **given == Some(**expected))
+            .count();
+        Score::fraction(matched as f64 / self.answer.len().max(1) as f64)
+    }
+}
+
+fn definitions(matching: &MatchingQuestion) -> Rc<[String]> {
+    matching
+        .pairs
+        .iter()
+        .map(|p| p.definition.clone())
+        .collect()
+}
+
+impl Askable for SingleMatchingQuestion {
+    fn kind(&self) -> Kind {
+        Kind::Matching
+    }
+
+    fn explicit_id(&self) -> Option<&str> {
+        Some(&self.id)
+    }
+
+    fn points(&self) -> Option<f64> {
+        self.points
+    }
+
+    fn frequency(&self) -> f64 {
+        self.weight.unwrap_or(1.0)
+    }
+
+    fn reference(&self) -> Option<String> {
+        self.reference.clone()
+    }
+
+    fn explanation(&self) -> Option<&str> {
+        self.explanation.as_deref()
+    }
+
+    fn text(&self) -> &str {
+        &self.term
+    }
+
+    fn correct_answer(&self) -> String {
+        self.correct_answer.clone()
+    }
+
+    fn prompt(&self, _rng: &mut dyn RngCore) -> String {
+        match self.reversed {
+            true => format!("다음 정의에 맞는 용어를 고르세요: {}", self.term),
+            false => format!("다음 용어에 맞는 정의를 고르세요: {}", self.term),
+        }
+    }
+
+    fn group(&self) -> Option<&str> {
+        Some(&self.group)
+    }
+
+    fn choices(&self) -> Option<(&[String], Marker)> {
+        Some((&self.definition, Marker::Number))
+    }
+
+    fn recall(&self) -> Option<Box<dyn Askable>> {
+        let (term, definition) = match self.reversed {
+            true => (&self.correct_answer, &self.term),
+            false => (&self.term, &self.correct_answer),
+        };
+        Some(Box::new(RecallQuestion {
+            id: self.id(),
+            kind: Kind::Matching,
+            question: definition.clone(),
+            variants: Vec::new(),
+            answer: Answers::One(term.clone()),
+            normalize: self.normalize.clone(),
+            group: Some(self.group.to_string()),
+            points: self.points,
+            weight: self.weight,
+            reference: self.reference.clone(),
+            explanation: self.explanation.clone(),
+        }))
+    }
+
+    fn grade(&self, answer: &str) -> Score {
+        let answer: usize = answer.trim().parse().unwrap_or(0);
+        Score::from_correct(
+            answer != 0 && self.definition.get(answer - 1) == Some(&self.correct_answer),
+        )
+    }
+}
+
+impl FillInTheBlankQuestion {
+    pub fn blank_count(&self) -> usize {
+        self.question
+            .split(|c| c != '_')
+            .filter(|run| run.len() >= 3)
+            .count()
+    }
+}
+
+impl Askable for FillInTheBlankQuestion {
+    fn kind(&self) -> Kind {
+        Kind::FillInTheBlank
+    }
+
+    fn explicit_id(&self) -> Option<&str> {
+        self.id.as_deref()
+    }
+
+    fn points(&self) -> Option<f64> {
+        self.points
+    }
+
+    fn frequency(&self) -> f64 {
+        self.weight.unwrap_or(1.0)
+    }
+
+    fn reference(&self) -> Option<String> {
+        reference(&self.source, &self.page)
+    }
+
+    fn explanation(&self) -> Option<&str> {
+        self.explanation.as_deref()
+    }
+
+    fn text(&self) -> &str {
+        &self.question
+    }
+
+    // 기록의 id 가 바뀌지 않도록 인정하는 답이 늘어도 대표 정답만 씁니다
+    fn correct_answer(&self) -> String {
+        self.answer.primary().to_string()
+    }
+
+    fn prompt(&self, rng: &mut dyn RngCore) -> String {
+        phrasing(&self.question, &self.variants, rng).to_string()
+    }
+
+    // 인정하는 답마다 채점해서 가장 높은 점수를 줍니다
+    fn grade(&self, answer: &str) -> Score {
+        self.answer
+            .all()
+            .iter()
+            .map(|expected| self.grade_one(expected, answer))
+            .max_by(|a, b| a.percent().total_cmp(&b.percent()))
+            .unwrap_or_else(|| Score::from_correct(false))
+    }
+}
+
+impl FillInTheBlankQuestion {
+    // 빈칸이 여러 개이면 콤마로 구분한 답 중 맞힌 만큼 부분 점수를 줍니다
+    fn grade_one(&self, expected: &str, answer: &str) -> Score {
+        let normalize = self.normalize.clone().unwrap_or_default();
+        let whole = expected;
+        let expected: Vec<&str> = whole.split(',').map(str::trim).collect();
+        if self.blank_count() < 2 || expected.len() < 2 {
+            return Score::from_correct(normalize.matches(whole, answer));
+        }
+
+        let mut given: Vec<&str> = answer.split(',').map(str::trim).collect();
+        let mut matched = 0;
+        for part in &expected {
+            if let Some(pos) = given.iter().position(|g| normalize.matches(part, g)) {
+                given.remove(pos);
+                matched += 1;
+            }
+        }
+        Score::fraction(matched as f64 / expected.len() as f64)
+    }
+}
+
+impl Askable for SpellingQuestion {
+    fn kind(&self) -> Kind {
+        Kind::Spelling
+    }
+
+    fn explicit_id(&self) -> Option<&str> {
+        self.id.as_deref()
+    }
+
+    fn points(&self) -> Option<f64> {
+        self.points
+    }
+
+    fn frequency(&self) -> f64 {
+        self.weight.unwrap_or(1.0)
+    }
+
+    fn reference(&self) -> Option<String> {
+        reference(&self.source, &self.page)
+    }
+
+    fn explanation(&self) -> Option<&str> {
+        self.explanation.as_deref()
+    }
+
+    fn text(&self) -> &str {
+        &self.question
+    }
+
+    fn correct_answer(&self) -> String {
+        self.answer.primary().to_string()
+    }
+
+    fn prompt(&self, rng: &mut dyn RngCore) -> String {
+        phrasing(&self.question, &self.variants, rng).to_string()
+    }
+
+    fn choices(&self) -> Option<(&[String], Marker)> {
+        (!self.options.is_empty()).then_some((&self.options[..], Marker::Number))
+    }
+
+    // 보기가 없는 철자 문제는 이미 떠올려서 쓰는 문제입니다
+    fn recall(&self) -> Option<Box<dyn Askable>> {
+        if self.options.is_empty() {
+            return None;
+        }
+        Some(Box::new(RecallQuestion {
+            id: self.id(),
+            kind: Kind::Spelling,
+            question: self.question.clone(),
+            variants: self.variants.clone(),
+            answer: Answers::Many(self.answer.all().to_vec()),
+            normalize: self.normalize.clone().unwrap_or_default(),
+            group: None,
+            points: self.points,
+            weight: self.weight,
+            reference: self.reference(),
+            explanation: self.explanation.clone(),
+        }))
+    }
+
+    // 보기 번호로 답해도 되고 철자를 직접 써도 됩니다
+    fn grade(&self, answer: &str) -> Score {
+        let answer = answer.trim();
+        let chosen = answer
+            .parse::<usize>()
+            .ok()
+            .and_then(|n| self.options.get(n.checked_sub(1)?))
+            .map_or(answer, String::as_str);
+        let normalize = self.normalize.clone().unwrap_or_default();
+        Score::from_correct(self.answer.accepts(chosen, &normalize))
+    }
+}
+
+// 뜻만 보여주고 보기 없이 용어를 쓰게 하는 문제 ([`Askable::recall`]).
+// 기록이 이어지도록 원래 문제의 id 를 그대로 씁니다
+pub struct RecallQuestion {
+    id: String,
+    kind: Kind,
+    question: String,
+    variants: Vec<String>,
+    answer: Answers,
+    normalize: Normalize,
+    group: Option<String>,
+    points: Option<f64>,
+    weight: Option<f64>,
+    reference: Option<String>,
+    explanation: Option<String>,
+}
+
+impl Askable for RecallQuestion {
+    fn kind(&self) -> Kind {
+        self.kind
+    }
+
+    fn explicit_id(&self) -> Option<&str> {
+        Some(&self.id)
+    }
+
+    fn points(&self) -> Option<f64> {
+        self.points
+    }
+
+    fn frequency(&self) -> f64 {
+        self.weight.unwrap_or(1.0)
+    }
+
+    fn reference(&self) -> Option<String> {
+        self.reference.clone()
+    }
+
+    fn explanation(&self) -> Option<&str> {
+        self.explanation.as_deref()
+    }
+
+    fn group(&self) -> Option<&str> {
+        self.group.as_deref()
+    }
+
+    fn text(&self) -> &str {
+        &self.question
+    }
+
+    fn correct_answer(&self) -> String {
+        self.answer.primary().to_string()
+    }
+
+    fn prompt(&self, rng: &mut dyn RngCore) -> String {
+        format!(
+            "다음 뜻의 용어를 쓰세요: {}",
+            phrasing(&self.question, &self.variants, rng)
+        )
+    }
+
+    fn grade(&self, answer: &str) -> Score {
+        Score::from_correct(self.answer.accepts(answer, &self.normalize))
+    }
+}
+
+// 템플릿으로 문제 파일을 읽을 때, 또는 스크립트로 물을 때 만든 문제
+#[derive(Clone, Serialize, Deserialize)]
+pub struct GeneratedQuestion {
+    pub id: String,
+    pub question: String,
+    pub answer: String,
+    pub options: Option<Vec<String>>,
+    pub points: Option<f64>,
+    pub weight: Option<f64>,
+}
+
+impl Askable for GeneratedQuestion {
+    fn kind(&self) -> Kind {
+        Kind::Custom
+    }
+
+    // 만들 때마다 내용이 달라지므로 기록은 만든 틀(스크립트, 템플릿) 단위로 모읍니다
+    fn explicit_id(&self) -> Option<&str> {
+        Some(&self.id)
+    }
+
+    fn points(&self) -> Option<f64> {
+        self.points
+    }
+
+    fn frequency(&self) -> f64 {
+        self.weight.unwrap_or(1.0)
+    }
+
+    fn text(&self) -> &str {
+        &self.question
+    }
+
+    fn correct_answer(&self) -> String {
+        self.answer.clone()
+    }
+
+    fn prompt(&self, _rng: &mut dyn RngCore) -> String {
+        self.question.clone()
+    }
+
+    fn choices(&self) -> Option<(&[String], Marker)> {
+        self.options
+            .as_deref()
+            .map(|options| (options, Marker::Number))
+    }
+
+    fn grade(&self, answer: &str) -> Score {
+        let answer = answer.trim();
+        match &self.options {
+            Some(options) => {
+                let chosen = answer
+                    .parse::<usize>()
+                    .ok()
+                    .and_then(|n| options.get(n.checked_sub(1)?));
+                Score::from_correct(chosen == Some(&self.answer))
+            }
+            None => Score::from_correct(answer.eq_ignore_ascii_case(self.answer.trim())),
+        }
+    }
+}
+
+// FNV-1a: 문제 내용이 바뀌지 않는 한 실행할 때마다 같은 값이 나와야 합니다
+// 문제 문장과 정답으로 만든 id. 문제 파일에 `id` 가 없으면 이것으로 기록합니다
+fn key_id(kind: Kind, text: &str, answer: &str) -> String {
+    let key = format!("{}\0{}", text, answer);
+    format!("{}-{:08x}", kind.code(), fnv1a(&key))
+}
+
+pub(crate) fn fnv1a(key: &str) -> u32 {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in key.bytes() {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    hash as u32
+}
+
+fn phrasing<'a>(question: &'a str, variants: &'a [String], rng: &mut dyn RngCore) -> &'a str {
+    let index = rng.gen_range(0..=variants.len());
+    match index {
+        0 => question,
+        i => &variants[i - 1],
+    }
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Marker {
+    Letter,
+    Number,
+    None,
+}
+
+impl Marker {
+    pub fn label(self, index: usize) -> String {
+        match self {
+            Marker::Letter => ((b'a' + index as u8) as char).to_string(),
+            Marker::Number => (index + 1).to_string(),
+            Marker::None => String::new(),
+        }
+    }
+
+    pub fn prompt(self) -> &'static str {
+        match self {
+            Marker::Number => "당신의 답변 (정답 번호를 입력하세요): ",
+            _ => "당신의 답변: ",
+        }
+    }
+
+    // 몇 번째 보기를 골랐는지를 줄 입력으로 답했을 때와 같은 형태로 바꿉니다
+    pub fn answer(self, options: &[String], index: usize) -> String {
+        match self {
+            Marker::None => options[index].clone(),
+            _ => self.label(index),
+        }
+    }
+}
+
+// 기본 유형만 만듭니다. 직접 만든 유형까지 쓰려면 [`crate::Registry::build`] 를 씁니다
+pub fn build_questions(chapters: Vec<Chapter>) -> Vec<(Box<dyn Askable>, u32)> {
+    let mut all_questions = Vec::new();
+
+    for chapter in chapters {
+        all_questions.extend(
+            chapter
+                .multiple_choice
+                .into_iter()
+                .map(|q| (Box::new(q) as Box<dyn Askable>, chapter.chapter)),
+        );
+        for matching in &chapter.matching {
+            if matching.round {
+                all_questions.push((
+                    Box::new(MatchingRoundQuestion::new(matching)),
+                    chapter.chapter,
+                ));
+                continue;
+            }
+            all_questions.extend(
+                SingleMatchingQuestion::all(matching)
+                    .map(|q| (Box::new(q) as Box<dyn Askable>, chapter.chapter)),
+            );
+        }
+        all_questions.extend(
+            chapter
+                .fill_in_the_blanks
+                .into_iter()
+                .map(|q| (Box::new(q) as Box<dyn Askable>, chapter.chapter)),
+        );
+        all_questions.extend(
+            chapter
+                .spelling
+                .into_iter()
+                .map(|q| (Box::new(q) as Box<dyn Askable>, chapter.chapter)),
+        );
+    }
+
+    all_questions
+}
+
+// 출제 빈도(`weight`)가 클수록 앞쪽에 오도록 섞습니다 (가중치 무작위 순열).
+// 모두 1이면 같은 시드에서 예전과 같은 순서가 나오도록 그냥 섞습니다
+pub fn weighted_shuffle<T>(items: &mut Vec<T>, weight: impl Fn(&T) -> f64, rng: &mut impl Rng) {
+    if items.iter().all(|item| weight(item) == 1.0) {
+        items.shuffle(rng);
+        return;
+    }
+    let mut keyed: Vec<(f64, T)> = items
+        .drain(..)
+        .map(|item| {
+            let w = weight(&item);
+            let key = if w > 0.0 {
+                rng.gen::<f64>().powf(1.0 / w)
+            } else {
+                -1.0
+            };
+            (key, item)
+        })
+        .collect();
+    keyed.sort_by(|a, b| b.0.total_cmp(&a.0));
+    items.extend(keyed.into_iter().map(|(_, item)| item));
+}
+
+// 문제에 적힌 배점 > 문제 파일의 유형별 배점 > 설정 파일의 유형별 배점 > 1점 순으로 정합니다
+pub fn weight(
+    question: &dyn Askable,
+    bank: &BTreeMap<Kind, f64>,
+    config: &BTreeMap<Kind, f64>,
+) -> f64 {
+    let kind = question.kind();
+    question
+        .points()
+        .or_else(|| bank.get(&kind).copied())
+        .or_else(|| config.get(&kind).copied())
+        .unwrap_or(1.0)
+}
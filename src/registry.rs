@@ -0,0 +1,115 @@
+use crate::question::{build_questions, Askable, Chapter};
+use serde::de::DeserializeOwned;
+use serde_yaml::Value;
+use std::collections::BTreeMap;
+use std::marker::PhantomData;
+use thiserror::Error;
+
+// 문제 유형 하나: 챕터 안의 어느 키를 읽고, 항목 하나를 어떤 문제(들)로 만드는지.
+// 만들어진 문제가 어떻게 보이고 채점되는지는 그 문제의 `Askable` 구현이 정합니다
+pub trait QuestionType {
+    fn key(&self) -> &str;
+    fn build(&self, value: Value) -> Result<Vec<Box<dyn Askable>>, String>;
+}
+
+// serde 로 바로 읽을 수 있는 문제 구조체를 그대로 문제 유형으로 씁니다
+pub struct Typed<T> {
+    key: String,
+    question: PhantomData<fn() -> T>,
+}
+
+pub fn typed<T>(key: &str) -> Typed<T> {
+    Typed {
+        key: key.to_string(),
+        question: PhantomData,
+    }
+}
+
+impl<T: DeserializeOwned + Askable + 'static> QuestionType for Typed<T> {
+    fn key(&self) -> &str {
+        &self.key
+    }
+
+    fn build(&self, value: Value) -> Result<Vec<Box<dyn Askable>>, String> {
+        let question: T = serde_yaml::from_value(value).map_err(|e| e.to_string())?;
+        Ok(vec![Box::new(question)])
+    }
+}
+
+// 만든 문제와 그 문제의 챕터 번호
+pub type BuiltQuestions = Vec<(Box<dyn Askable>, u32)>;
+
+#[derive(Debug, Error)]
+#[error("챕터 {chapter}의 {}: {message}", self.location())]
+pub struct BuildError {
+    pub chapter: u32,
+    pub key: String,
+    // 목록에서 몇 번째 항목인지 (0부터)
+    pub index: Option<usize>,
+    pub message: String,
+}
+
+impl BuildError {
+    // 예: `templates 3번`
+    pub fn location(&self) -> String {
+        match self.index {
+            Some(index) => format!("{} {}번", self.key, index + 1),
+            None => self.key.clone(),
+        }
+    }
+}
+
+// 기본 유형 외에 문제 파일에서 읽을 수 있는 유형들. 등록되지 않은 키는 전처럼 무시합니다
+#[derive(Default)]
+pub struct Registry {
+    types: BTreeMap<String, Box<dyn QuestionType>>,
+}
+
+impl Registry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn register(&mut self, question_type: impl QuestionType + 'static) -> &mut Self {
+        self.types
+            .insert(question_type.key().to_string(), Box::new(question_type));
+        self
+    }
+
+    pub fn contains(&self, key: &str) -> bool {
+        self.types.contains_key(key)
+    }
+
+    pub fn build(&self, chapters: Vec<Chapter>) -> Result<BuiltQuestions, BuildError> {
+        let mut all_questions = Vec::new();
+
+        for mut chapter in chapters {
+            let number = chapter.chapter;
+            let custom = std::mem::take(&mut chapter.custom);
+            all_questions.extend(build_questions(vec![chapter]));
+
+            for (key, questions) in custom {
+                let Some(question_type) = self.types.get(&key) else {
+                    continue;
+                };
+                let error = |index, message: String| BuildError {
+                    chapter: number,
+                    key: key.clone(),
+                    index,
+                    message,
+                };
+                let Value::Sequence(questions) = questions else {
+                    return Err(error(None, "문제 목록이어야 합니다".to_string()));
+                };
+                for (index, question) in questions.into_iter().enumerate() {
+                    let built = question_type
+                        .build(question)
+                        .map_err(|message| error(Some(index), message))?;
+                    all_questions.extend(built.into_iter().map(|q| (q, number)));
+                }
+            }
+        }
+
+        Ok(all_questions)
+    }
+}
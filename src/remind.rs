@@ -0,0 +1,193 @@
+use crate::history;
+use crate::style::styled;
+use ansi_term::Color;
+use chrono::{Local, NaiveDate, NaiveTime, TimeDelta};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+#[derive(clap::Args)]
+pub struct RemindArgs {
+    /// 매일 이 시각(HH:MM)이 지난 뒤 처음 `terms remind` 를 실행할 때 복습할 문제 수를 알려 줍니다
+    #[arg(long, value_name = "HH:MM", value_parser = parse_time, conflicts_with = "off")]
+    daily: Option<NaiveTime>,
+    /// 등록한 알림을 지웁니다
+    #[arg(long)]
+    off: bool,
+    /// 복습할 문제 수를 데스크톱 알림으로 보냅니다 (cron 등에서 부를 때)
+    #[cfg(feature = "notify")]
+    #[arg(long, conflicts_with_all = ["daily", "off"])]
+    notify: bool,
+}
+
+fn parse_time(text: &str) -> Result<NaiveTime, String> {
+    NaiveTime::parse_from_str(text, "%H:%M").map_err(|_| "21:00 처럼 적어 주세요".to_string())
+}
+
+#[derive(Serialize, Deserialize)]
+struct Schedule {
+    daily: NaiveTime,
+    // 같은 날 셸을 열 때마다 알리지 않도록 마지막으로 알린 날을 적어 둡니다
+    last: Option<NaiveDate>,
+}
+
+fn schedule_path() -> PathBuf {
+    history::data_dir().join("remind.yaml")
+}
+
+impl Schedule {
+    fn load() -> Option<Self> {
+        let data = fs::read_to_string(schedule_path()).ok()?;
+        serde_yaml::from_str(&data).ok()
+    }
+
+    fn save(&self) -> io::Result<()> {
+        fs::create_dir_all(history::data_dir())?;
+        let data = serde_yaml::to_string(self)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        fs::write(schedule_path(), data)
+    }
+}
+
+// 문제마다 마지막으로 푼 날부터 연달아 맞힌 횟수에 따라 1, 2, 4, 8... 일 뒤에 다시 풀 때가 됩니다.
+// 틀리면 다음 날 다시 풉니다
+fn due(sessions: &[history::Session]) -> BTreeMap<&str, usize> {
+    let mut cards: HashMap<(&str, &str), (u32, NaiveDate)> = HashMap::new();
+    for session in sessions {
+        for answer in &session.answers {
+            let card = cards
+                .entry((session.bank.as_str(), answer.id.as_str()))
+                .or_insert((0, session.started_at.date_naive()));
+            card.0 = if answer.correct { card.0 + 1 } else { 0 };
+            card.1 = session.started_at.date_naive();
+        }
+    }
+    let today = Local::now().date_naive();
+    let mut banks: BTreeMap<&str, usize> = BTreeMap::new();
+    for ((bank, _), (streak, last)) in cards {
+        if last + TimeDelta::days(1 << streak.min(9)) <= today {
+            *banks.entry(bank).or_default() += 1;
+        }
+    }
+    banks
+}
+
+fn summary() -> Result<Option<String>, String> {
+    let sessions = history::load().map_err(|e| e.to_string())?;
+    let banks = due(&sessions);
+    let total: usize = banks.values().sum();
+    if total == 0 {
+        return Ok(None);
+    }
+    let detail: Vec<String> = banks
+        .iter()
+        .map(|(bank, count)| format!("{} {}개", bank, count))
+        .collect();
+    Ok(Some(format!(
+        "복습할 문제가 {}개 있습니다 ({}).",
+        total,
+        detail.join(", ")
+    )))
+}
+
+fn print_summary(quiet: bool) {
+    match summary() {
+        Ok(Some(text)) => println!(
+            "{} `terms quiz` 로 풀어 보세요.",
+            styled(Color::Yellow).paint(text)
+        ),
+        Ok(None) if !quiet => println!("지금은 복습할 문제가 없습니다."),
+        Ok(None) => {}
+        Err(e) => println!(
+            "{} {}",
+            styled(Color::Red).paint("학습 기록을 읽을 수 없습니다:"),
+            e
+        ),
+    }
+}
+
+#[cfg(feature = "notify")]
+fn notify() {
+    match summary() {
+        Ok(Some(text)) => {
+            if let Err(e) = notify_rust::Notification::new()
+                .summary("terms")
+                .body(&text)
+                .show()
+            {
+                println!(
+                    "{} {}",
+                    styled(Color::Red).paint("알림을 보낼 수 없습니다:"),
+                    e
+                );
+                println!("{}", text);
+            }
+        }
+        Ok(None) => {}
+        Err(e) => println!(
+            "{} {}",
+            styled(Color::Red).paint("학습 기록을 읽을 수 없습니다:"),
+            e
+        ),
+    }
+}
+
+pub fn run(args: RemindArgs) {
+    #[cfg(feature = "notify")]
+    if args.notify {
+        return notify();
+    }
+    if args.off {
+        match fs::remove_file(schedule_path()) {
+            Ok(()) => println!("알림을 지웠습니다."),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => println!("등록한 알림이 없습니다."),
+            Err(e) => println!(
+                "{} {}",
+                styled(Color::Red).paint("알림을 지울 수 없습니다:"),
+                e
+            ),
+        }
+        return;
+    }
+    if let Some(daily) = args.daily {
+        let schedule = Schedule { daily, last: None };
+        if let Err(e) = schedule.save() {
+            println!(
+                "{} {}",
+                styled(Color::Red).paint("알림을 저장할 수 없습니다:"),
+                e
+            );
+            return;
+        }
+        println!(
+            "매일 {} 이 지나 처음 `terms remind` 를 실행할 때 복습할 문제 수를 알려 드립니다.",
+            daily.format("%H:%M")
+        );
+        println!(
+            "셸을 열 때마다 확인하려면 ~/.bashrc 같은 셸 시작 파일에 `terms remind` 를 넣으세요."
+        );
+        if cfg!(feature = "notify") {
+            println!(
+                "데스크톱 알림을 받으려면 cron 에 `{} * * * terms remind --notify` 를 넣으세요.",
+                daily.format("%-M %-H")
+            );
+        }
+        return;
+    }
+
+    // 등록한 알림이 있으면 그 시각이 지난 뒤 하루에 한 번만, 복습할 문제가 있을 때만 알립니다
+    match Schedule::load() {
+        Some(mut schedule) => {
+            let now = Local::now();
+            if now.time() < schedule.daily || schedule.last == Some(now.date_naive()) {
+                return;
+            }
+            schedule.last = Some(now.date_naive());
+            schedule.save().ok();
+            print_summary(true);
+        }
+        None => print_summary(false),
+    }
+}
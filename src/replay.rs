@@ -0,0 +1,222 @@
+use crate::history::{self, Answer, Session};
+use crate::stats::pad;
+use crate::style::styled;
+use crate::{format_duration, input};
+use ansi_term::Color;
+use std::io::{self, IsTerminal, Write};
+use std::time::Duration;
+
+// 세션 ID 없이 부르면 보여줄 최근 세션 수
+const RECENT: usize = 20;
+
+#[derive(clap::Args)]
+pub struct ReplayArgs {
+    /// 다시 볼 세션의 ID (생략하면 최근 세션과 ID 를 보여줍니다). `last` 는 가장 최근 세션
+    session: Option<String>,
+    /// 한 문제씩 넘기지 않고 전부 한꺼번에 보여줍니다
+    #[arg(long)]
+    all: bool,
+    /// 틀리거나 건너뛴 문제만 보여줍니다
+    #[arg(long)]
+    missed: bool,
+}
+
+pub fn run(args: ReplayArgs) {
+    let sessions = match history::load() {
+        Ok(sessions) => sessions,
+        Err(e) => {
+            println!(
+                "{} {}",
+                styled(Color::Red).paint("학습 기록을 읽을 수 없습니다:"),
+                e
+            );
+            std::process::exit(1);
+        }
+    };
+    let Some(id) = &args.session else {
+        list(&sessions);
+        return;
+    };
+    let found = match id.as_str() {
+        "last" => sessions.last(),
+        id => sessions.iter().rev().find(|s| s.id() == id),
+    };
+    let Some(session) = found else {
+        println!(
+            "{} {}",
+            styled(Color::Red).paint("이 ID 의 세션이 없습니다:"),
+            id
+        );
+        std::process::exit(1);
+    };
+
+    let answers: Vec<&Answer> = session
+        .answers
+        .iter()
+        .filter(|a| !args.missed || !a.correct)
+        .collect();
+    header(session);
+    if answers.is_empty() {
+        println!("다시 볼 문제가 없습니다.");
+        return;
+    }
+    if args.all || !io::stdin().is_terminal() {
+        for (i, answer) in answers.iter().enumerate() {
+            show(answer, i, answers.len());
+        }
+        return;
+    }
+
+    let mut current = 0;
+    loop {
+        show(answers[current], current, answers.len());
+        print!("Enter 다음  p 이전  번호 그 문제로  q 그만두기: ");
+        io::stdout().flush().unwrap();
+        let input::Line::Text(line) = input::read_line() else {
+            return;
+        };
+        match line.trim() {
+            "" | "n" if current + 1 == answers.len() => return,
+            "" | "n" => current += 1,
+            "p" => current = current.saturating_sub(1),
+            "q" => return,
+            other => match other.parse::<usize>() {
+                Ok(n) if (1..=answers.len()).contains(&n) => current = n - 1,
+                _ => println!(
+                    "{}",
+                    styled(Color::Cyan)
+                        .paint(format!("1부터 {} 사이의 번호를 입력하세요.", answers.len()))
+                ),
+            },
+        }
+    }
+}
+
+fn list(sessions: &[Session]) {
+    let recent: Vec<&Session> = sessions
+        .iter()
+        .rev()
+        .filter(|s| !s.answers.is_empty())
+        .take(RECENT)
+        .collect();
+    if recent.is_empty() {
+        println!("아직 기록된 세션이 없습니다.");
+        return;
+    }
+    println!(
+        "{}{}{}{}문제 파일",
+        pad("ID", 18),
+        pad("날짜", 18),
+        pad("문제", 6),
+        pad("정답률", 8)
+    );
+    for session in recent {
+        let correct = session.answers.iter().filter(|a| a.correct).count();
+        println!(
+            "{}{}{}{}{}",
+            pad(&session.id(), 18),
+            pad(&session.started_at.format("%Y-%m-%d %H:%M").to_string(), 18),
+            pad(&session.answers.len().to_string(), 6),
+            pad(
+                &format!(
+                    "{:.0}%",
+                    correct as f64 * 100.0 / session.answers.len() as f64
+                ),
+                8
+            ),
+            session.bank
+        );
+    }
+    println!("\n`terms replay <ID>` 로 그 세션을 한 문제씩 다시 봅니다.");
+}
+
+fn header(session: &Session) {
+    let correct = session.answers.iter().filter(|a| a.correct).count();
+    println!(
+        "{} {}",
+        styled(Color::Yellow.bold()).paint(session.started_at.format("%Y-%m-%d %H:%M").to_string()),
+        session.bank
+    );
+    println!(
+        "{} 문제 중 {} 개 맞힘, 소요 시간 {}",
+        session.answers.len(),
+        correct,
+        format_duration(Duration::from_secs_f64(session.duration_secs))
+    );
+    // 답을 기록하기 전에 푼 세션에는 문제와 맞았는지만 남아 있습니다
+    if session
+        .answers
+        .iter()
+        .all(|a| a.given.is_empty() && a.expected.is_empty())
+    {
+        println!(
+            "{}",
+            styled(Color::Cyan).paint("이 세션에는 낸 답과 정답이 기록되어 있지 않습니다.")
+        );
+    }
+    println!();
+}
+
+// 짝짓기의 정답은 보기 번호 없이 적혀 있습니다
+fn same(option: &str, answer: &str) -> bool {
+    option == answer
+        || option
+            .split_once(". ")
+            .is_some_and(|(_, text)| text == answer)
+}
+
+fn show(answer: &Answer, index: usize, total: usize) {
+    let verdict = match answer.score {
+        Some(score) if score.skipped => styled(Color::Cyan).paint("건너뜀"),
+        _ if answer.correct => styled(Color::Green).paint("맞음"),
+        Some(score) if score.is_partial() => {
+            styled(Color::Yellow).paint(format!("부분 정답 ({})", score))
+        }
+        _ => styled(Color::Red).paint("틀림"),
+    };
+    println!(
+        "{} {} {}",
+        styled(Color::Yellow.bold()).paint(format!(
+            "[{}/{}] 챕터 {} {}",
+            index + 1,
+            total,
+            answer.chapter,
+            answer.kind.label()
+        )),
+        verdict,
+        styled(Color::Cyan).paint(format!(
+            "{:.1}초 {}",
+            answer.time_ms as f64 / 1000.0,
+            answer.id
+        ))
+    );
+    println!("{}", answer.text);
+    for option in &answer.options {
+        let mark = if same(option, &answer.expected) {
+            format!(" {}", styled(Color::Green).paint("✓ 정답"))
+        } else if answer.given.iter().any(|given| same(option, given)) {
+            format!(" {}", styled(Color::Red).paint("← 낸 답"))
+        } else {
+            String::new()
+        };
+        println!("  - {}{}", option, mark);
+    }
+    match answer.given.split_first() {
+        Some((first, retries)) => {
+            print!("낸 답: {}", first);
+            for retry in retries {
+                print!(" → {}", retry);
+            }
+            println!();
+        }
+        None if !answer.expected.is_empty() => println!("낸 답: 없음"),
+        None => {}
+    }
+    if !answer.expected.is_empty() {
+        println!("정답: {}", styled(Color::Green).paint(&answer.expected));
+    }
+    if answer.lifeline {
+        println!("{}", styled(Color::Cyan).paint("50:50 을 쓰고 답했습니다."));
+    }
+    println!();
+}
@@ -0,0 +1,455 @@
+use crate::add::{indent_of, is_content};
+use crate::history;
+use crate::style::styled;
+use ansi_term::Color;
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{self, Write};
+use std::path::PathBuf;
+use terms::{Askable, Kind, Questions, SingleMatchingQuestion};
+
+#[derive(Serialize, Deserialize)]
+pub struct Note {
+    pub at: DateTime<Local>,
+    pub note: String,
+}
+
+// 문제 하나에 모인 신고. 문제 파일이 바뀌어도 무엇을 신고했는지 알 수 있게 문제와 정답을 같이 남깁니다
+#[derive(Serialize, Deserialize)]
+pub struct Reported {
+    pub bank: String,
+    pub chapter: u32,
+    pub kind: Kind,
+    pub text: String,
+    pub answer: String,
+    pub notes: Vec<Note>,
+}
+
+fn reports_path() -> PathBuf {
+    history::data_dir().join("reports.yaml")
+}
+
+pub fn load() -> io::Result<BTreeMap<String, Reported>> {
+    let data = match fs::read_to_string(reports_path()) {
+        Ok(data) => data,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(BTreeMap::new()),
+        Err(e) => return Err(e),
+    };
+    serde_yaml::from_str(&data).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+fn save(reports: &BTreeMap<String, Reported>) -> io::Result<()> {
+    fs::create_dir_all(history::data_dir())?;
+    let data = serde_yaml::to_string(reports)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    fs::write(reports_path(), data)
+}
+
+pub fn append(bank: &str, question: &dyn Askable, chapter: u32, note: &str) -> io::Result<()> {
+    let mut reports = load()?;
+    let reported = reports.entry(question.id()).or_insert_with(|| Reported {
+        bank: bank.to_string(),
+        chapter,
+        kind: question.kind(),
+        text: question.text().to_string(),
+        answer: question.correct_answer(),
+        notes: Vec::new(),
+    });
+    reported.notes.push(Note {
+        at: Local::now(),
+        note: note.to_string(),
+    });
+    save(&reports)
+}
+
+#[derive(clap::Args)]
+pub struct TriageArgs {
+    /// 신고된 문제를 고칠 YAML 문제 파일
+    file: PathBuf,
+}
+
+// 문제 파일에서 신고된 문제가 있는 곳
+enum Target {
+    // 객관식, 빈칸, 철자: 문제 문장으로 찾습니다
+    Question { text: String },
+    // 짝짓기: 묶음의 설명과 용어로 찾습니다
+    Pair { group: String, term: String },
+}
+
+fn locate(questions: &Questions, id: &str) -> Option<Target> {
+    for chapter in &questions.chapters {
+        let plain = chapter
+            .multiple_choice
+            .iter()
+            .map(|q| (q as &dyn Askable, &q.question))
+            .chain(
+                chapter
+                    .fill_in_the_blanks
+                    .iter()
+                    .map(|q| (q as &dyn Askable, &q.question)),
+            )
+            .chain(
+                chapter
+                    .spelling
+                    .iter()
+                    .map(|q| (q as &dyn Askable, &q.question)),
+            );
+        for (question, text) in plain {
+            if question.id() == id {
+                return Some(Target::Question { text: text.clone() });
+            }
+        }
+        for group in &chapter.matching {
+            for pair in &group.pairs {
+                if SingleMatchingQuestion::new(pair.term.clone(), group).id() == id {
+                    return Some(Target::Pair {
+                        group: group.question.clone(),
+                        term: pair.term.clone(),
+                    });
+                }
+            }
+        }
+    }
+    None
+}
+
+enum Fix {
+    Answer(String),
+    Text(String),
+    Retire,
+    Later,
+}
+
+// 고칠 곳: `anchor` 문장을 가진 항목(짝짓기면 그 안의 `pair` 용어)의 `field` 를 `value` 로
+struct Edit {
+    anchor: String,
+    pair: Option<String>,
+    field: &'static str,
+    value: String,
+}
+
+impl Edit {
+    fn new(target: &Target, fix: &Fix) -> Option<Self> {
+        let (anchor, pair) = match target {
+            Target::Question { text } => (text.clone(), None),
+            Target::Pair { group, term } => (group.clone(), Some(term.clone())),
+        };
+        let (pair, field, value) = match (fix, pair) {
+            (Fix::Later, _) => return None,
+            (Fix::Retire, _) => (None, "status", "retired".to_string()),
+            (Fix::Answer(value), None) => (None, "answer", value.clone()),
+            (Fix::Text(value), None) => (None, "question", value.clone()),
+            (Fix::Answer(value), Some(term)) => (Some(term), "definition", value.clone()),
+            (Fix::Text(value), Some(term)) => (Some(term), "term", value.clone()),
+        };
+        Some(Self {
+            anchor,
+            pair,
+            field,
+            value,
+        })
+    }
+
+    // 읽어 들인 문제에 반영합니다. 글자 단위로 고친 결과가 맞는지 견줘 볼 때, 그리고 그러지 못했을 때 씁니다
+    fn apply(&self, questions: &Questions) -> Option<Questions> {
+        let mut value = serde_yaml::to_value(questions).ok()?;
+        let item = value
+            .get_mut("chapters")?
+            .as_sequence_mut()?
+            .iter_mut()
+            .filter_map(|c| c.as_mapping_mut())
+            .flat_map(|c| c.values_mut())
+            .filter_map(|list| list.as_sequence_mut())
+            .flatten()
+            .find(|q| q.get("question").and_then(|t| t.as_str()) == Some(&self.anchor))?;
+        let item = match &self.pair {
+            Some(term) => item
+                .get_mut("pairs")?
+                .as_sequence_mut()?
+                .iter_mut()
+                .find(|p| p.get("term").and_then(|t| t.as_str()) == Some(term))?,
+            None => item,
+        };
+        item[self.field] = self.value.clone().into();
+        serde_yaml::from_value(value).ok()
+    }
+
+    fn apply_text(&self, data: &str) -> Option<String> {
+        let mut lines: Vec<String> = data.lines().map(str::to_string).collect();
+        let mut item = find_item(&lines, 0, "question", &self.anchor)?;
+        if let Some(term) = &self.pair {
+            item = find_item(&lines, item.start, "term", term)?;
+        }
+        let value = match self.field {
+            "status" => self.value.clone(),
+            _ => serde_yaml::to_string(&self.value)
+                .ok()?
+                .trim_end()
+                .to_string(),
+        };
+        set_field(&mut lines, &item, self.field, &value);
+        Some(lines.join("\n") + "\n")
+    }
+}
+
+// `key: 값` 줄이면 값을 돌려줍니다 (목록 항목의 `- ` 는 떼고 봅니다)
+fn field<'a>(line: &'a str, key: &str) -> Option<&'a str> {
+    let line = line.trim_start();
+    let line = line.strip_prefix("- ").unwrap_or(line);
+    line.strip_prefix(key)?.strip_prefix(':').map(str::trim)
+}
+
+// 문제 파일의 한 항목(`- ` 로 시작하는 덩어리)과 그 안의 키가 있는 열
+struct Item {
+    start: usize,
+    end: usize,
+    column: usize,
+}
+
+fn find_item(lines: &[String], from: usize, key: &str, value: &str) -> Option<Item> {
+    let anchor = (from..lines.len()).find(|&i| {
+        field(&lines[i], key)
+            .is_some_and(|v| serde_yaml::from_str::<String>(v).ok().as_deref() == Some(value))
+    })?;
+    let line = &lines[anchor];
+    let column = indent_of(line)
+        + if line.trim_start().starts_with("- ") {
+            2
+        } else {
+            0
+        };
+    let start = (0..=anchor)
+        .rev()
+        .find(|&i| lines[i].trim_start().starts_with("- ") && indent_of(&lines[i]) + 2 == column)?;
+    let end = (anchor + 1..lines.len())
+        .find(|&i| is_content(&lines[i]) && indent_of(&lines[i]) < column)
+        .unwrap_or(lines.len());
+    Some(Item { start, end, column })
+}
+
+// 항목 안의 키 값을 바꾸거나, 없으면 항목 끝에 새로 씁니다
+fn set_field(lines: &mut Vec<String>, item: &Item, key: &str, value: &str) {
+    for (i, line) in lines.iter_mut().enumerate().take(item.end).skip(item.start) {
+        let at_column = indent_of(line) == item.column
+            || (i == item.start && indent_of(line) + 2 == item.column);
+        if at_column && field(line, key).is_some() {
+            let prefix = if i == item.start { "- " } else { "" };
+            *line = format!(
+                "{}{}{}: {}",
+                " ".repeat(indent_of(line)),
+                prefix,
+                key,
+                value
+            );
+            return;
+        }
+    }
+    let end = (item.start..item.end)
+        .rev()
+        .find(|&i| is_content(&lines[i]))
+        .map_or(item.end, |i| i + 1);
+    lines.insert(
+        end,
+        format!("{}{}: {}", " ".repeat(item.column), key, value),
+    );
+}
+
+fn ask(prompt: &str) -> Option<String> {
+    print!("{}", prompt);
+    io::stdout().flush().unwrap();
+    match crate::input::read_line() {
+        crate::input::Line::Text(line) => Some(line.trim().to_string()),
+        _ => None,
+    }
+}
+
+pub fn triage(args: TriageArgs) {
+    let mut reports = match load() {
+        Ok(reports) => reports,
+        Err(e) => {
+            println!(
+                "{} {}",
+                styled(Color::Red).paint("신고를 읽을 수 없습니다:"),
+                e
+            );
+            return;
+        }
+    };
+    let mut data = match fs::read_to_string(&args.file) {
+        Ok(data) => data,
+        Err(e) => {
+            println!(
+                "{} {}",
+                styled(Color::Red).paint("파일을 읽을 수 없습니다:"),
+                e
+            );
+            return;
+        }
+    };
+
+    let ids: Vec<String> = reports.keys().cloned().collect();
+    let mut elsewhere = 0;
+    let mut handled = 0;
+    for id in ids {
+        let questions = match Questions::parse(&data) {
+            Ok(questions) => questions,
+            Err(e) => {
+                println!("{} {}", styled(Color::Red).paint("YAML 파싱 실패:"), e);
+                return;
+            }
+        };
+        let Some(target) = locate(&questions, &id) else {
+            elsewhere += 1;
+            continue;
+        };
+        let reported = &reports[&id];
+        println!(
+            "\n{} {}",
+            styled(Color::Yellow.bold()).paint(format!(
+                "[챕터 {} {}]",
+                reported.chapter,
+                reported.kind.label()
+            )),
+            reported.text
+        );
+        println!("정답: {}", styled(Color::Green).paint(&reported.answer));
+        for note in &reported.notes {
+            println!(
+                "- {} {}",
+                styled(Color::Cyan).paint(note.at.format("%Y-%m-%d %H:%M").to_string()),
+                note.note
+            );
+        }
+        // 짝 하나만 은퇴시킬 수는 없어서 묶음 전체를 은퇴시킵니다
+        let retire = match target {
+            Target::Pair { .. } => "묶음 전체 은퇴시키기",
+            Target::Question { .. } => "은퇴시키기",
+        };
+        println!(
+            "1. 정답 고치기  2. 문제 고치기  3. {}  4. 문제 없음(신고 지우기)  Enter 다음에 보기  q 그만두기",
+            retire
+        );
+
+        // 객관식이면 보기 수에 맞는 글자만 정답으로 받습니다
+        let letters = match &target {
+            Target::Question { text } if reported.kind == Kind::MultipleChoice => questions
+                .chapters
+                .iter()
+                .flat_map(|c| &c.multiple_choice)
+                .find(|q| q.question == *text)
+                .map(|q| q.letters()),
+            _ => None,
+        };
+        let fix = loop {
+            let Some(choice) = ask("선택: ") else {
+                return;
+            };
+            let prompt = match (&target, choice.as_str(), &letters) {
+                (Target::Pair { .. }, "1", _) => "새 정의: ".to_string(),
+                (Target::Pair { .. }, "2", _) => "새 용어: ".to_string(),
+                (_, "1", Some(letters)) => {
+                    format!("새 정답 ({}~{}): ", letters.start(), letters.end())
+                }
+                (_, "1", None) => "새 정답: ".to_string(),
+                (_, "2", _) => "새 문제: ".to_string(),
+                _ => String::new(),
+            };
+            match choice.as_str() {
+                "1" | "2" => {
+                    let Some(value) = ask(&prompt) else {
+                        return;
+                    };
+                    if value.is_empty() {
+                        continue;
+                    }
+                    if let Some(letters) = letters.as_ref().filter(|_| choice == "1") {
+                        let mut chars = value.chars();
+                        if !(chars.next().is_some_and(|c| letters.contains(&c))
+                            && chars.next().is_none())
+                        {
+                            println!(
+                                "{}",
+                                styled(Color::Red).paint(format!(
+                                    "{}~{} 중 하나를 입력하세요.",
+                                    letters.start(),
+                                    letters.end()
+                                ))
+                            );
+                            continue;
+                        }
+                    }
+                    break Some(if choice == "1" {
+                        Fix::Answer(value)
+                    } else {
+                        Fix::Text(value)
+                    });
+                }
+                "3" => break Some(Fix::Retire),
+                "4" => break None,
+                "q" => return,
+                // 다음에 보기: 신고를 그대로 둡니다
+                "" => break Some(Fix::Later),
+                _ => continue,
+            }
+        };
+        if matches!(fix, Some(Fix::Later)) {
+            continue;
+        }
+
+        if let Some(edit) = fix.and_then(|fix| Edit::new(&target, &fix)) {
+            let Some(expected) = edit.apply(&questions) else {
+                println!("{}", styled(Color::Red).paint("문제를 고칠 수 없습니다."));
+                continue;
+            };
+            let normalize = |q: &Questions| serde_yaml::to_value(q).ok();
+            // 글자 단위로 고친 결과가 읽어 들여 고친 것과 같을 때만 그대로 씁니다
+            data = match edit.apply_text(&data) {
+                Some(text)
+                    if Questions::parse(&text).ok().as_ref().and_then(normalize)
+                        == normalize(&expected) =>
+                {
+                    text
+                }
+                _ => {
+                    println!(
+                        "{}",
+                        styled(Color::Yellow)
+                            .paint("원래 서식을 유지할 수 없어 파일 전체를 다시 씁니다.")
+                    );
+                    serde_yaml::to_string(&expected).unwrap()
+                }
+            };
+            if let Err(e) = fs::write(&args.file, &data) {
+                println!(
+                    "{} {}",
+                    styled(Color::Red).paint("파일을 쓸 수 없습니다:"),
+                    e
+                );
+                return;
+            }
+        }
+        reports.remove(&id);
+        handled += 1;
+        if let Err(e) = save(&reports) {
+            println!(
+                "{} {}",
+                styled(Color::Red).paint("신고를 저장할 수 없습니다:"),
+                e
+            );
+            return;
+        }
+    }
+
+    println!(
+        "\n신고 {}건을 처리했습니다. 남은 신고 {}건{}",
+        handled,
+        reports.len(),
+        if elsewhere > 0 {
+            format!(" (이 파일에 없는 문제 {}건)", elsewhere)
+        } else {
+            String::new()
+        }
+    );
+}
@@ -0,0 +1,131 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::ops::AddAssign;
+
+#[derive(Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct Score {
+    pub earned: f64,
+    pub possible: f64,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub skipped: bool,
+}
+
+impl Score {
+    pub fn new(earned: f64, possible: f64) -> Self {
+        Self {
+            earned,
+            possible,
+            skipped: false,
+        }
+    }
+
+    pub fn skipped() -> Self {
+        Self {
+            skipped: true,
+            ..Self::new(0.0, 1.0)
+        }
+    }
+
+    pub fn from_correct(is_correct: bool) -> Self {
+        Self::fraction(if is_correct { 1.0 } else { 0.0 })
+    }
+
+    pub fn fraction(fraction: f64) -> Self {
+        Self::new(fraction.clamp(0.0, 1.0), 1.0)
+    }
+
+    pub fn weighted(self, weight: f64) -> Self {
+        Self {
+            earned: self.earned * weight,
+            possible: self.possible * weight,
+            ..self
+        }
+    }
+
+    // 정답 `length` 글자 중 `revealed` 글자를 힌트로 보았으면 그 비율만큼 점수를 줄입니다
+    pub fn hinted(self, revealed: usize, length: usize) -> Self {
+        let kept = 1.0 - revealed as f64 / length.max(1) as f64;
+        Self {
+            earned: self.earned * kept.max(0.0),
+            ..self
+        }
+    }
+
+    // 오답에만 감점하고, 건너뛰었거나 부분 점수를 받은 문제는 그대로 둡니다
+    pub fn penalized(self, fraction: f64) -> Self {
+        if self.skipped || self.earned > 0.0 {
+            return self;
+        }
+        Self {
+            earned: -fraction * self.possible,
+            ..self
+        }
+    }
+
+    // 배점이 0인 문제는 맞혔다고 세지 않습니다
+    pub fn is_correct(&self) -> bool {
+        self.possible > 0.0 && self.earned >= self.possible
+    }
+
+    pub fn is_partial(&self) -> bool {
+        self.earned > 0.0 && !self.is_correct()
+    }
+
+    pub fn percent(&self) -> f64 {
+        if self.possible > 0.0 {
+            self.earned / self.possible * 100.0
+        } else {
+            0.0
+        }
+    }
+}
+
+#[derive(Deserialize)]
+pub struct GradeBoundary {
+    pub min: f64,
+    pub grade: String,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+pub struct Grading {
+    pub grades: Vec<GradeBoundary>,
+    pub pass_mark: Option<f64>,
+}
+
+impl Grading {
+    pub fn grade(&self, score: &Score) -> Option<&str> {
+        let percent = score.percent();
+        self.grades
+            .iter()
+            .filter(|b| percent >= b.min)
+            .max_by(|a, b| a.min.total_cmp(&b.min))
+            .map(|b| b.grade.as_str())
+    }
+
+    pub fn passed(&self, score: &Score) -> Option<bool> {
+        self.pass_mark.map(|mark| score.percent() >= mark)
+    }
+}
+
+impl AddAssign for Score {
+    fn add_assign(&mut self, other: Self) {
+        self.earned += other.earned;
+        self.possible += other.possible;
+    }
+}
+
+impl fmt::Display for Score {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} / {}점", points(self.earned), points(self.possible))
+    }
+}
+
+pub fn points(value: f64) -> String {
+    let rounded = (value * 100.0).round() / 100.0;
+    if rounded.fract() == 0.0 {
+        format!("{}", rounded as i64)
+    } else {
+        format!("{}", rounded)
+    }
+}
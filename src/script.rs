@@ -0,0 +1,189 @@
+use crate::question::{fnv1a, Askable, GeneratedQuestion, Kind, Marker};
+use crate::registry::QuestionType;
+use crate::score::Score;
+use rand::rngs::StdRng;
+use rand::{Rng, RngCore, SeedableRng};
+use rhai::module_resolvers::DummyModuleResolver;
+use rhai::{Array, Dynamic, Engine, Map, Scope, AST};
+use serde::Deserialize;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+// 문제 파일에 적는 스크립트 문제 하나. 한 세션에 `count` 문제까지 내고, 낼 때마다 스크립트를 돌립니다
+#[derive(Deserialize)]
+struct Entry {
+    script: String,
+    #[serde(default)]
+    id: Option<String>,
+    #[serde(default = "one")]
+    count: usize,
+    #[serde(default)]
+    points: Option<f64>,
+    #[serde(default)]
+    weight: Option<f64>,
+}
+
+fn one() -> usize {
+    1
+}
+
+// 챕터의 `scripted` 키를 읽는 문제 유형. 같은 시드면 같은 차례로 물을 때 같은 문제가 만들어집니다
+pub struct Scripted {
+    engine: Rc<Engine>,
+}
+
+impl Scripted {
+    pub fn new(seed: u64) -> Self {
+        let rng = Rc::new(RefCell::new(StdRng::seed_from_u64(seed)));
+        let mut engine = Engine::new();
+
+        // 문제 파일은 남이 만든 것일 수도 있으니 파일, 모듈, 출력에 손대지 못하고 오래 돌지 못하게 막습니다
+        engine.set_module_resolver(DummyModuleResolver::new());
+        engine.on_print(|_| {});
+        engine.on_debug(|_, _, _| {});
+        engine.set_max_operations(100_000);
+        engine.set_max_call_levels(32);
+        engine.set_max_expr_depths(64, 32);
+        engine.set_max_string_size(10_000);
+        engine.set_max_array_size(1_000);
+        engine.set_max_map_size(100);
+
+        let r = Rc::clone(&rng);
+        engine.register_fn("rand", move |low: i64, high: i64| {
+            r.borrow_mut().gen_range(low.min(high)..=low.max(high))
+        });
+        let r = Rc::clone(&rng);
+        engine.register_fn("pick", move |items: Array| {
+            if items.is_empty() {
+                return Dynamic::UNIT;
+            }
+            let index = r.borrow_mut().gen_range(0..items.len());
+            items[index].clone()
+        });
+        let r = rng;
+        engine.register_fn("shuffle", move |mut items: Array| {
+            let mut rng = r.borrow_mut();
+            for i in (1..items.len()).rev() {
+                items.swap(i, rng.gen_range(0..=i));
+            }
+            items
+        });
+
+        Self {
+            engine: Rc::new(engine),
+        }
+    }
+}
+
+// 스크립트가 돌려준 맵에서 (문제, 정답, 보기)를 꺼냅니다
+fn run(engine: &Engine, ast: &AST) -> Result<(String, String, Option<Vec<String>>), String> {
+    let generated: Map = engine
+        .eval_ast_with_scope(&mut Scope::new(), ast)
+        .map_err(|e| e.to_string())?;
+    let field = |name: &str| generated.get(name).map(Dynamic::to_string);
+    let question = field("question").ok_or("스크립트가 question 을 돌려주지 않았습니다")?;
+    let answer = field("answer").ok_or("스크립트가 answer 를 돌려주지 않았습니다")?;
+    let options = match generated.get("options") {
+        None => None,
+        Some(options) => {
+            let options = options
+                .clone()
+                .into_typed_array::<Dynamic>()
+                .map_err(|_| "options 는 배열이어야 합니다")?;
+            Some(options.iter().map(Dynamic::to_string).collect())
+        }
+    };
+    Ok((question, answer, options))
+}
+
+// 읽을 때 한 번 돌려 스크립트가 맞는지 확인한 결과를 들고 있다가, 물을 때마다 새로 돌린 문제를 냅니다.
+// 물을 때 스크립트가 실패하면 읽을 때 만든 문제를 그대로 냅니다
+struct ScriptedQuestion {
+    engine: Rc<Engine>,
+    ast: Rc<AST>,
+    sample: GeneratedQuestion,
+}
+
+impl Askable for ScriptedQuestion {
+    fn kind(&self) -> Kind {
+        self.sample.kind()
+    }
+
+    fn explicit_id(&self) -> Option<&str> {
+        self.sample.explicit_id()
+    }
+
+    fn points(&self) -> Option<f64> {
+        self.sample.points()
+    }
+
+    fn frequency(&self) -> f64 {
+        self.sample.frequency()
+    }
+
+    fn text(&self) -> &str {
+        self.sample.text()
+    }
+
+    fn correct_answer(&self) -> String {
+        self.sample.correct_answer()
+    }
+
+    fn prompt(&self, rng: &mut dyn RngCore) -> String {
+        self.sample.prompt(rng)
+    }
+
+    fn choices(&self) -> Option<(&[String], Marker)> {
+        self.sample.choices()
+    }
+
+    fn grade(&self, answer: &str) -> Score {
+        self.sample.grade(answer)
+    }
+
+    fn generate(&self) -> Option<GeneratedQuestion> {
+        let (question, answer, options) = run(&self.engine, &self.ast).ok()?;
+        Some(GeneratedQuestion {
+            question,
+            answer,
+            options,
+            ..self.sample.clone()
+        })
+    }
+}
+
+impl QuestionType for Scripted {
+    fn key(&self) -> &str {
+        "scripted"
+    }
+
+    fn build(&self, value: serde_yaml::Value) -> Result<Vec<Box<dyn Askable>>, String> {
+        let entry: Entry = serde_yaml::from_value(value).map_err(|e| e.to_string())?;
+        let ast = Rc::new(
+            self.engine
+                .compile(&entry.script)
+                .map_err(|e| e.to_string())?,
+        );
+        let id = entry
+            .id
+            .unwrap_or_else(|| format!("custom-{:08x}", fnv1a(&entry.script)));
+
+        let mut questions: Vec<Box<dyn Askable>> = Vec::new();
+        for _ in 0..entry.count {
+            let (question, answer, options) = run(&self.engine, &ast)?;
+            questions.push(Box::new(ScriptedQuestion {
+                engine: Rc::clone(&self.engine),
+                ast: Rc::clone(&ast),
+                sample: GeneratedQuestion {
+                    id: id.clone(),
+                    question,
+                    answer,
+                    options,
+                    points: entry.points,
+                    weight: entry.weight,
+                },
+            }));
+        }
+        Ok(questions)
+    }
+}
@@ -0,0 +1,228 @@
+use crate::config;
+use crate::history;
+use crate::style::styled;
+use ansi_term::Color;
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use rand::{thread_rng, RngCore};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static INSECURE: AtomicBool = AtomicBool::new(false);
+
+pub fn set_insecure(insecure: bool) {
+    INSECURE.store(insecure, Ordering::Relaxed);
+}
+
+#[derive(clap::Args)]
+pub struct SignArgs {
+    /// 서명할 문제 파일 (`.enc` 파일도 됩니다)
+    #[arg(required_unless_present = "public_key")]
+    file: Option<PathBuf>,
+    /// 서명 대신 SHA-256 체크섬(`파일.sha256`)만 만듭니다
+    #[arg(long)]
+    checksum: bool,
+    /// 내 서명 키의 공개 키를 보여줍니다
+    #[arg(long, conflicts_with_all = ["file", "checksum"])]
+    public_key: bool,
+}
+
+// `파일.sig` 의 내용. 받는 쪽은 공개 키가 `trusted_keys` 에 있어야 믿습니다
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Detached {
+    pub public_key: String,
+    pub signature: String,
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn unhex<const N: usize>(text: &str) -> Option<[u8; N]> {
+    let text = text.trim();
+    if text.len() != N * 2 || !text.is_ascii() {
+        return None;
+    }
+    let mut bytes = [0u8; N];
+    for (i, byte) in bytes.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&text[i * 2..i * 2 + 2], 16).ok()?;
+    }
+    Some(bytes)
+}
+
+fn sidecar(path: &Path, extension: &str) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(extension);
+    PathBuf::from(name)
+}
+
+fn key_path() -> PathBuf {
+    history::home_dir().join("signing.key")
+}
+
+// 서명 키가 없으면 새로 만들어 데이터 디렉토리에 둡니다
+fn signing_key() -> Result<SigningKey, String> {
+    let path = key_path();
+    match fs::read_to_string(&path) {
+        Ok(text) => unhex::<32>(&text)
+            .map(|bytes| SigningKey::from_bytes(&bytes))
+            .ok_or_else(|| format!("서명 키 파일이 잘못되었습니다: {}", path.display())),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            let mut bytes = [0u8; 32];
+            thread_rng().fill_bytes(&mut bytes);
+            fs::create_dir_all(history::home_dir())
+                .and_then(|()| fs::write(&path, hex(&bytes) + "\n"))
+                .map_err(|e| e.to_string())?;
+            println!(
+                "{} {}",
+                styled(Color::Cyan).paint("새 서명 키를 만들었습니다:"),
+                path.display()
+            );
+            Ok(SigningKey::from_bytes(&bytes))
+        }
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+// 결과 수집 서버가 나눠 줄 시험에 내 키로 서명합니다. 서명 키가 없으면 `None`
+#[cfg(feature = "web")]
+pub fn sign_text(text: &str) -> Option<Detached> {
+    let stored = fs::read_to_string(key_path()).ok()?;
+    let key = SigningKey::from_bytes(&unhex::<32>(&stored)?);
+    Some(Detached {
+        public_key: hex(key.verifying_key().as_bytes()),
+        signature: hex(&key.sign(text.as_bytes()).to_bytes()),
+    })
+}
+
+fn trusted(public_key: &str) -> bool {
+    let own = fs::read_to_string(key_path())
+        .ok()
+        .and_then(|text| unhex::<32>(&text))
+        .map(|bytes| hex(SigningKey::from_bytes(&bytes).verifying_key().as_bytes()));
+    own.as_deref() == Some(public_key)
+        || config::Config::load()
+            .trusted_keys
+            .iter()
+            .any(|key| key.trim().eq_ignore_ascii_case(public_key))
+}
+
+pub fn verify(data: &[u8], detached: &Detached) -> Result<(), String> {
+    let public_key = unhex::<32>(&detached.public_key)
+        .and_then(|bytes| VerifyingKey::from_bytes(&bytes).ok())
+        .ok_or("서명의 공개 키가 잘못되었습니다")?;
+    let signature = unhex::<64>(&detached.signature)
+        .map(|bytes| Signature::from_bytes(&bytes))
+        .ok_or("서명이 잘못되었습니다")?;
+    public_key
+        .verify(data, &signature)
+        .map_err(|_| "서명이 맞지 않습니다. 파일이 바뀌었을 수 있습니다")?;
+    if !trusted(&detached.public_key.to_ascii_lowercase()) {
+        return Err(format!(
+            "믿을 수 있는 키(`trusted_keys`)로 서명되지 않았습니다: {}",
+            detached.public_key
+        ));
+    }
+    Ok(())
+}
+
+// 파일 옆에 `.sig` 나 `.sha256` 이 있으면 확인합니다
+fn verify_file(path: &Path) -> Result<(), String> {
+    let signature = sidecar(path, ".sig");
+    let checksum = sidecar(path, ".sha256");
+    if !signature.exists() && !checksum.exists() {
+        return Ok(());
+    }
+    let data = fs::read(path).map_err(|e| e.to_string())?;
+    if let Ok(text) = fs::read_to_string(&signature) {
+        let detached: Detached = serde_yaml::from_str(&text)
+            .map_err(|e| format!("{} 를 읽을 수 없습니다: {}", signature.display(), e))?;
+        verify(&data, &detached)?;
+    }
+    if let Ok(text) = fs::read_to_string(&checksum) {
+        let expected = text.split_whitespace().next().unwrap_or_default();
+        if !expected.eq_ignore_ascii_case(&hex(&Sha256::digest(&data))) {
+            return Err("체크섬이 맞지 않습니다. 파일이 바뀌었을 수 있습니다".to_string());
+        }
+    }
+    Ok(())
+}
+
+// 검사에 실패하면 `--insecure` 가 없는 한 끝냅니다
+pub fn enforce(what: &str, result: Result<(), String>) {
+    let Err(e) = result else {
+        return;
+    };
+    if INSECURE.load(Ordering::Relaxed) {
+        println!(
+            "{} {}: {}",
+            styled(Color::Yellow).paint("검사에 실패했지만 --insecure 로 계속합니다."),
+            what,
+            e
+        );
+        return;
+    }
+    println!(
+        "{} {}: {}\n그래도 열려면 --insecure 를 붙이세요.",
+        styled(Color::Red).paint("문제 파일을 믿을 수 없습니다."),
+        what,
+        e
+    );
+    std::process::exit(1);
+}
+
+pub fn check(path: &Path) {
+    enforce(&path.display().to_string(), verify_file(path));
+}
+
+pub fn run(args: SignArgs) {
+    if args.public_key {
+        match signing_key() {
+            Ok(key) => println!("{}", hex(key.verifying_key().as_bytes())),
+            Err(e) => println!(
+                "{} {}",
+                styled(Color::Red).paint("서명 키를 읽을 수 없습니다:"),
+                e
+            ),
+        }
+        return;
+    }
+    let file = args.file.expect("clap 이 파일을 요구합니다");
+    let result = fs::read(&file).map_err(|e| e.to_string()).and_then(|data| {
+        if args.checksum {
+            let path = sidecar(&file, ".sha256");
+            let name = file.file_name().unwrap_or_default().to_string_lossy();
+            fs::write(
+                &path,
+                format!("{}  {}\n", hex(&Sha256::digest(&data)), name),
+            )
+            .map_err(|e| e.to_string())?;
+            return Ok(path);
+        }
+        let key = signing_key()?;
+        let detached = Detached {
+            public_key: hex(key.verifying_key().as_bytes()),
+            signature: hex(&key.sign(&data).to_bytes()),
+        };
+        let path = sidecar(&file, ".sig");
+        fs::write(&path, serde_yaml::to_string(&detached).unwrap()).map_err(|e| e.to_string())?;
+        println!(
+            "받는 사람의 config.yaml 의 trusted_keys 에 이 공개 키를 넣게 하세요: {}",
+            detached.public_key
+        );
+        Ok(path)
+    });
+    match result {
+        Ok(path) => println!(
+            "{} {}",
+            styled(Color::Green).paint("만들었습니다:"),
+            path.display()
+        ),
+        Err(e) => {
+            println!("{} {}", styled(Color::Red).paint("서명할 수 없습니다:"), e);
+            std::process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,175 @@
+use crate::style::styled;
+use crate::{deal, ChapterSet, QuizArgs};
+use ansi_term::Color;
+use chrono::{DateTime, Local};
+use rand::{thread_rng, Rng};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use terms::{BuiltQuestions, Kind};
+
+#[derive(clap::Args)]
+pub struct SnapshotArgs {
+    /// 내장된 문제 대신 사용할 YAML 문제 파일
+    #[arg(long, value_name = "FILE")]
+    bank: Option<PathBuf>,
+    /// YAML 대신 `terms db import`로 만든 SQLite 데이터베이스에서 문제를 읽습니다
+    #[cfg(feature = "sqlite")]
+    #[arg(long, value_name = "FILE", conflicts_with = "bank")]
+    db: Option<PathBuf>,
+    /// 이 챕터들에서 문제를 고릅니다 (예: 1-3,5). 생략하면 전부
+    #[arg(long)]
+    chapters: Option<ChapterSet>,
+    /// 이 태그가 붙은 문제만 고릅니다 (여러 번 지정 가능)
+    #[arg(long = "tag", value_name = "TAG")]
+    tags: Vec<String>,
+    /// 이 유형의 문제만 고릅니다 (여러 번 지정 가능)
+    #[arg(long = "kind", value_name = "KIND")]
+    kinds: Vec<Kind>,
+    /// 문제를 고르고 섞는 시드 (생략하면 무작위로 정해 스냅숏에 적습니다)
+    #[arg(long, value_name = "N")]
+    seed: Option<u64>,
+    /// 고를 문제 수 (생략하면 전부)
+    #[arg(short = 'n', long = "count", value_name = "N")]
+    count: Option<usize>,
+    /// 스냅숏 파일을 쓸 곳
+    #[arg(long, short, value_name = "FILE", default_value = "exam.lock")]
+    output: PathBuf,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Entry {
+    chapter: u32,
+    id: String,
+    // 사람이 읽기 위한 문제 문장. 다시 풀 때는 id 로만 찾습니다
+    #[serde(default)]
+    text: String,
+}
+
+// 고른 문제와 순서를 그대로 얼려 둔 것. 템플릿, 스크립트 문제도 같은 문제가 나오도록 시드를 같이 적습니다
+#[derive(Serialize, Deserialize)]
+pub struct Snapshot {
+    bank: String,
+    created_at: DateTime<Local>,
+    seed: u64,
+    chapters: Vec<u32>,
+    questions: Vec<Entry>,
+}
+
+impl Snapshot {
+    fn load(path: &Path) -> io::Result<Self> {
+        let data = fs::read_to_string(path)?;
+        serde_yaml::from_str(&data).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    fn save(&self, path: &Path) -> io::Result<()> {
+        let data = serde_yaml::to_string(self)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        fs::write(path, data)
+    }
+
+    // 스냅숏의 순서대로 문제를 늘어놓습니다. 문제 파일에서 찾을 수 없는 문제가 있으면 그 수를 돌려줍니다
+    pub fn arrange(&self, questions: BuiltQuestions) -> Result<BuiltQuestions, usize> {
+        let mut pool: Vec<_> = questions.into_iter().map(Some).collect();
+        let mut arranged = Vec::new();
+        let mut missing = 0;
+        for entry in &self.questions {
+            let found = pool.iter().position(|question| {
+                question
+                    .as_ref()
+                    .is_some_and(|(q, chapter)| *chapter == entry.chapter && q.id() == entry.id)
+            });
+            match found.and_then(|i| pool[i].take()) {
+                Some(question) => arranged.push(question),
+                None => missing += 1,
+            }
+        }
+        match missing {
+            0 => Ok(arranged),
+            missing => Err(missing),
+        }
+    }
+}
+
+// `quiz --from-snapshot` 으로 풀 때 스냅숏을 읽고 같은 문제를 만들 수 있도록 챕터와 시드를 맞춥니다.
+// 고를 문제는 스냅숏이 정하므로 태그, 유형, 작성 중 여부로 거르지 않습니다
+pub fn prepare(path: &Path, args: &mut QuizArgs) -> Snapshot {
+    let snapshot = Snapshot::load(path).unwrap_or_else(|e| {
+        println!(
+            "{} {}",
+            styled(Color::Red).paint("스냅숏을 읽을 수 없습니다:"),
+            e
+        );
+        std::process::exit(1)
+    });
+    if snapshot.bank != args.bank_name() {
+        println!(
+            "{} 이 스냅숏은 {} 로 만들었습니다.",
+            styled(Color::Yellow).paint("문제 파일의 이름이 다릅니다."),
+            snapshot.bank
+        );
+    }
+    args.chapters = Some(ChapterSet(snapshot.chapters.clone()));
+    args.seed = Some(snapshot.seed);
+    args.count = None;
+    args.tags.clear();
+    args.kinds.clear();
+    args.include_drafts = true;
+    snapshot
+}
+
+pub fn run(args: SnapshotArgs) {
+    let seed = args.seed.unwrap_or_else(|| thread_rng().gen());
+    let quiz = QuizArgs {
+        bank: args.bank,
+        #[cfg(feature = "sqlite")]
+        db: args.db,
+        chapters: args.chapters,
+        tags: args.tags,
+        kinds: args.kinds,
+        seed: Some(seed),
+        count: args.count,
+        ..Default::default()
+    };
+    let (questions, _) = deal(&quiz);
+    if questions.is_empty() {
+        println!("고를 문제가 없습니다.");
+        return;
+    }
+    let mut chapters: Vec<u32> = questions.iter().map(|(_, chapter)| *chapter).collect();
+    chapters.sort_unstable();
+    chapters.dedup();
+    let snapshot = Snapshot {
+        bank: quiz.bank_name(),
+        created_at: Local::now(),
+        seed,
+        chapters,
+        questions: questions
+            .iter()
+            .map(|(question, chapter)| Entry {
+                chapter: *chapter,
+                id: question.id(),
+                text: question.text().to_string(),
+            })
+            .collect(),
+    };
+    match snapshot.save(&args.output) {
+        Ok(()) => println!(
+            "{} {} ({}문제, 시드 {})\n`terms quiz --from-snapshot {}` 로 모두 같은 순서의 같은 문제를 풉니다.",
+            styled(Color::Green).paint("스냅숏을 만들었습니다:"),
+            args.output.display(),
+            snapshot.questions.len(),
+            seed,
+            args.output.display()
+        ),
+        Err(e) => {
+            println!(
+                "{} {}",
+                styled(Color::Red).paint("파일을 쓸 수 없습니다:"),
+                e
+            );
+            std::process::exit(1);
+        }
+    }
+}
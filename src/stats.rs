@@ -0,0 +1,365 @@
+use crate::history;
+use crate::style::styled;
+use ansi_term::{Color, Style};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write as _;
+use std::fs;
+use std::path::PathBuf;
+use terms::Kind;
+use unicode_width::UnicodeWidthStr;
+
+#[derive(clap::Args)]
+pub struct StatsArgs {
+    /// 색 대신 숫자 표로만 출력합니다
+    #[arg(long)]
+    no_color: bool,
+    /// 이 문제 파일(파일 이름)의 기록만 봅니다
+    #[arg(long, value_name = "NAME")]
+    bank: Option<String>,
+    /// 최근 세션별 정답률과 풀이 속도의 추이를 보여줍니다
+    #[arg(long)]
+    trend: bool,
+    /// 추이에 포함할 최근 세션 수
+    #[arg(long, value_name = "N", default_value_t = 10)]
+    last: usize,
+    /// 짝짓기 묶음별 정답률을 약한 묶음부터 보여줍니다
+    #[arg(long)]
+    groups: bool,
+    /// 문제별 통계를 CSV 파일로 내보냅니다
+    #[arg(long, value_name = "FILE")]
+    export: Option<PathBuf>,
+}
+
+impl StatsArgs {
+    fn color(&self) -> bool {
+        !self.no_color && !crate::style::is_plain()
+    }
+}
+
+#[derive(Default)]
+struct Tally {
+    attempts: usize,
+    correct: usize,
+}
+
+impl Tally {
+    fn accuracy(&self) -> f64 {
+        self.correct as f64 / self.attempts as f64
+    }
+}
+
+const CELL_WIDTH: usize = 8;
+
+pub fn run(args: StatsArgs) {
+    let mut sessions = match history::load() {
+        Ok(sessions) => sessions,
+        Err(e) => {
+            println!(
+                "{} {}",
+                styled(Color::Red).paint("학습 기록을 읽을 수 없습니다:"),
+                e
+            );
+            return;
+        }
+    };
+    if let Some(bank) = &args.bank {
+        sessions.retain(|s| &s.bank == bank);
+    }
+
+    if let Some(path) = &args.export {
+        match fs::write(path, export(&sessions)) {
+            Ok(()) => println!("통계를 내보냈습니다: {}", path.display()),
+            Err(e) => println!(
+                "{} {}",
+                styled(Color::Red).paint("CSV 파일을 쓸 수 없습니다:"),
+                e
+            ),
+        }
+    } else if args.trend {
+        trend(&sessions, &args);
+    } else if args.groups {
+        groups(&sessions, &args);
+    } else {
+        heatmap(&sessions, &args);
+    }
+}
+
+fn heatmap(sessions: &[history::Session], args: &StatsArgs) {
+    let mut grid: BTreeMap<(u32, Kind), Tally> = BTreeMap::new();
+    let mut total = Tally::default();
+    for answer in sessions.iter().flat_map(|s| &s.answers) {
+        let tally = grid.entry((answer.chapter, answer.kind)).or_default();
+        tally.attempts += 1;
+        total.attempts += 1;
+        if answer.correct {
+            tally.correct += 1;
+            total.correct += 1;
+        }
+    }
+
+    if total.attempts == 0 {
+        println!("아직 기록된 세션이 없습니다.");
+        return;
+    }
+
+    let color = args.color();
+    println!(
+        "세션 {}회, 문제 {}개, 정답률 {:.0}%\n",
+        sessions.len(),
+        total.attempts,
+        total.accuracy() * 100.0
+    );
+
+    let chapters: BTreeSet<u32> = grid.keys().map(|(chapter, _)| *chapter).collect();
+    print!("{}", pad("", CELL_WIDTH));
+    for kind in Kind::ALL {
+        print!("{}", pad(kind.label(), CELL_WIDTH));
+    }
+    println!();
+
+    for chapter in chapters {
+        print!("{}", pad(&format!("챕터 {}", chapter), CELL_WIDTH));
+        for kind in Kind::ALL {
+            match grid.get(&(chapter, kind)) {
+                None => print!("{}", pad("-", CELL_WIDTH)),
+                Some(tally) if color => {
+                    let cell = pad(
+                        &format!(" {:.0}%", tally.accuracy() * 100.0),
+                        CELL_WIDTH - 1,
+                    );
+                    print!("{} ", heat(tally.accuracy()).paint(cell));
+                }
+                Some(tally) => {
+                    let cell = format!("{}/{}", tally.correct, tally.attempts);
+                    print!("{}", pad(&cell, CELL_WIDTH));
+                }
+            }
+        }
+        println!();
+    }
+
+    if color {
+        println!(
+            "\n{} {} {}",
+            heat(0.0).paint(" 0% "),
+            heat(0.5).paint(" 50% "),
+            heat(1.0).paint(" 100% ")
+        );
+    }
+}
+
+fn trend(sessions: &[history::Session], args: &StatsArgs) {
+    let mut recent: Vec<&history::Session> = sessions
+        .iter()
+        .rev()
+        .filter(|s| !s.answers.is_empty())
+        .take(args.last)
+        .collect();
+    recent.reverse();
+    if recent.is_empty() {
+        println!("아직 기록된 세션이 없습니다.");
+        return;
+    }
+
+    let accuracies: Vec<f64> = recent
+        .iter()
+        .map(|s| s.answers.iter().filter(|a| a.correct).count() as f64 / s.answers.len() as f64)
+        .collect();
+    let speeds: Vec<f64> = recent
+        .iter()
+        .map(|s| {
+            s.answers.iter().map(|a| a.time_ms).sum::<u64>() as f64
+                / s.answers.len() as f64
+                / 1000.0
+        })
+        .collect();
+
+    println!("최근 {}개 세션\n", recent.len());
+    println!(
+        "{}{}{}평균 풀이 시간",
+        pad("날짜", 18),
+        pad("문제", 6),
+        pad("정답률", 8)
+    );
+    for ((session, accuracy), speed) in recent.iter().zip(&accuracies).zip(&speeds) {
+        println!(
+            "{}{}{}{:.1}초",
+            pad(&session.started_at.format("%Y-%m-%d %H:%M").to_string(), 18),
+            pad(&session.answers.len().to_string(), 6),
+            pad(&format!("{:.0}%", accuracy * 100.0), 8),
+            speed
+        );
+    }
+
+    let slowest = speeds.iter().cloned().fold(0.0, f64::max);
+    println!();
+    println!("정답률 {}", sparkline(&accuracies, 1.0));
+    println!("풀이 시간 {}", sparkline(&speeds, slowest));
+
+    if let (Some(first), Some(last)) = (accuracies.first(), accuracies.last()) {
+        let change = (last - first) * 100.0;
+        let message = format!("처음 대비 정답률 {:+.0}%p", change);
+        if !args.color() {
+            println!("{}", message);
+        } else if change >= 0.0 {
+            println!("{}", styled(Color::Green).paint(message));
+        } else {
+            println!("{}", styled(Color::Red).paint(message));
+        }
+    }
+}
+
+fn groups(sessions: &[history::Session], args: &StatsArgs) {
+    #[derive(Default)]
+    struct Group<'a> {
+        tally: Tally,
+        terms: BTreeSet<&'a str>,
+    }
+
+    let mut groups: BTreeMap<(u32, &str), Group> = BTreeMap::new();
+    for answer in sessions.iter().flat_map(|s| &s.answers) {
+        let Some(name) = &answer.group else {
+            continue;
+        };
+        let group = groups.entry((answer.chapter, name)).or_default();
+        group.tally.attempts += 1;
+        if answer.correct {
+            group.tally.correct += 1;
+        }
+        group.terms.insert(&answer.id);
+    }
+    if groups.is_empty() {
+        println!("아직 기록된 짝짓기 묶음이 없습니다.");
+        return;
+    }
+
+    let mut rows: Vec<_> = groups.into_iter().collect();
+    rows.sort_by(|a, b| a.1.tally.accuracy().total_cmp(&b.1.tally.accuracy()));
+    println!(
+        "{}{}{}{}묶음",
+        pad("정답률", CELL_WIDTH),
+        pad("답", 6),
+        pad("문항", 6),
+        pad("챕터", 6)
+    );
+    for ((chapter, name), group) in &rows {
+        let accuracy = format!("{:.0}%", group.tally.accuracy() * 100.0);
+        let accuracy = match args.color() {
+            true => {
+                heat(group.tally.accuracy())
+                    .paint(pad(&format!(" {}", accuracy), CELL_WIDTH - 1))
+                    .to_string()
+                    + " "
+            }
+            false => pad(&accuracy, CELL_WIDTH),
+        };
+        println!(
+            "{}{}{}{}{}",
+            accuracy,
+            pad(&group.tally.attempts.to_string(), 6),
+            pad(&group.terms.len().to_string(), 6),
+            pad(&chapter.to_string(), 6),
+            name
+        );
+    }
+    if let Some(((_, name), _)) = rows.first() {
+        println!(
+            "\n`terms quiz --group \"{}\"` 로 한 묶음만 집중해서 연습할 수 있습니다.",
+            name
+        );
+    }
+}
+
+fn export(sessions: &[history::Session]) -> String {
+    struct Row<'a> {
+        chapter: u32,
+        kind: Kind,
+        text: &'a str,
+        attempts: usize,
+        correct: usize,
+        total_ms: u64,
+        last_seen: chrono::DateTime<chrono::Local>,
+    }
+
+    let mut rows: BTreeMap<&str, Row> = BTreeMap::new();
+    for session in sessions {
+        for answer in &session.answers {
+            let row = rows.entry(&answer.id).or_insert(Row {
+                chapter: answer.chapter,
+                kind: answer.kind,
+                text: &answer.text,
+                attempts: 0,
+                correct: 0,
+                total_ms: 0,
+                last_seen: session.started_at,
+            });
+            row.attempts += 1;
+            row.total_ms += answer.time_ms;
+            if answer.correct {
+                row.correct += 1;
+            }
+            if !answer.text.is_empty() {
+                row.text = &answer.text;
+            }
+            row.last_seen = row.last_seen.max(session.started_at);
+        }
+    }
+
+    let mut out =
+        String::from("id,chapter,kind,question,attempts,correct,average_time_secs,last_seen\n");
+    for (id, row) in rows {
+        writeln!(
+            out,
+            "{},{},{},{},{},{},{:.1},{}",
+            csv_field(id),
+            row.chapter,
+            row.kind.code(),
+            csv_field(row.text),
+            row.attempts,
+            row.correct,
+            row.total_ms as f64 / row.attempts as f64 / 1000.0,
+            row.last_seen.format("%Y-%m-%d %H:%M:%S")
+        )
+        .unwrap();
+    }
+    out
+}
+
+pub fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn sparkline(values: &[f64], max: f64) -> String {
+    const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+    values
+        .iter()
+        .map(|v| {
+            let level = if max > 0.0 { v / max } else { 0.0 };
+            BARS[((level * 7.0).round() as usize).min(7)]
+        })
+        .collect()
+}
+
+fn heat(accuracy: f64) -> Style {
+    // 0%는 빨강, 50%는 노랑, 100%는 초록으로 이어지게 섞습니다
+    let red = if accuracy < 0.5 {
+        255.0
+    } else {
+        255.0 * (1.0 - accuracy) * 2.0
+    };
+    let green = if accuracy < 0.5 {
+        255.0 * accuracy * 2.0
+    } else {
+        255.0
+    };
+    Color::Black.on(Color::RGB(red as u8, green as u8, 0))
+}
+
+pub fn pad(text: &str, width: usize) -> String {
+    let fill = width.saturating_sub(text.width());
+    format!("{}{}", text, " ".repeat(fill))
+}
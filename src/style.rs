@@ -0,0 +1,95 @@
+use ansi_term::Style;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static PLAIN: AtomicBool = AtomicBool::new(false);
+static HIGH_CONTRAST: AtomicBool = AtomicBool::new(false);
+static BANNER: AtomicBool = AtomicBool::new(false);
+
+pub fn set_plain(plain: bool) {
+    PLAIN.store(plain, Ordering::Relaxed);
+}
+
+pub fn set_high_contrast(high_contrast: bool, banner: bool) {
+    HIGH_CONTRAST.store(high_contrast, Ordering::Relaxed);
+    BANNER.store(banner, Ordering::Relaxed);
+}
+
+// --plain 이거나 NO_COLOR 가 설정되어 있으면 색과 글꼴 효과를 쓰지 않습니다
+pub fn is_plain() -> bool {
+    PLAIN.load(Ordering::Relaxed) || std::env::var_os("NO_COLOR").is_some()
+}
+
+pub fn is_high_contrast() -> bool {
+    HIGH_CONTRAST.load(Ordering::Relaxed)
+}
+
+pub fn styled(style: impl Into<Style>) -> Style {
+    if is_plain() {
+        Style::new()
+    } else {
+        style.into()
+    }
+}
+
+// 고대비 모드에서는 색에 더해 굵게, 밑줄로 판정을 강조합니다
+pub fn emphasis(style: impl Into<Style>) -> Style {
+    let style = style.into();
+    if is_high_contrast() {
+        styled(style.bold().underline())
+    } else {
+        styled(style)
+    }
+}
+
+pub enum Verdict {
+    Correct,
+    Partial,
+    Wrong,
+}
+
+impl Verdict {
+    // 색을 구분하기 어려워도 모양으로 알아볼 수 있는 기호
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Verdict::Correct => "O",
+            Verdict::Partial => "△",
+            Verdict::Wrong => "X",
+        }
+    }
+
+    fn banner(&self) -> &'static [&'static str] {
+        match self {
+            Verdict::Correct => &[
+                "  █████  ",
+                " ██   ██ ",
+                " ██   ██ ",
+                " ██   ██ ",
+                "  █████  ",
+            ],
+            Verdict::Partial => &[
+                "    ██    ",
+                "   ████   ",
+                "  ██  ██  ",
+                " ██    ██ ",
+                "██████████",
+            ],
+            Verdict::Wrong => &[
+                " ██   ██ ",
+                "  ██ ██  ",
+                "   ███   ",
+                "  ██ ██  ",
+                " ██   ██ ",
+            ],
+        }
+    }
+
+    // --banner 가 켜져 있으면 판정 앞에 큰 글자를 찍습니다 (일반 텍스트 모드 제외)
+    pub fn print_banner(&self, style: Style) {
+        if !BANNER.load(Ordering::Relaxed) || is_plain() {
+            return;
+        }
+        for line in self.banner() {
+            println!("{}", styled(style.bold()).paint(*line));
+        }
+    }
+}
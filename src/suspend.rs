@@ -0,0 +1,192 @@
+use crate::history;
+use crate::style::styled;
+use ansi_term::Color;
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{self, IsTerminal, Write};
+use std::path::PathBuf;
+use terms::Askable;
+
+// 공유하는 문제 파일을 고치지 않고 내 세션에서만 빼 둔 문제. 프로필마다 따로 둡니다
+#[derive(Serialize, Deserialize)]
+pub struct Suspended {
+    // 목록에서 어느 문제인지 알아볼 수 있도록 문제와 문제 파일을 같이 남깁니다
+    #[serde(default)]
+    pub question: String,
+    #[serde(default)]
+    pub bank: String,
+    #[serde(default)]
+    pub chapter: u32,
+    pub at: DateTime<Local>,
+}
+
+#[derive(clap::Args)]
+pub struct SuspendArgs {
+    /// 뺄 문제의 ID (`terms replay` 나 `stats --export` 에서 볼 수 있습니다)
+    #[arg(required = true, value_name = "ID")]
+    ids: Vec<String>,
+}
+
+#[derive(clap::Args)]
+pub struct SuspendedArgs {
+    /// 이 ID 의 문제를 다시 냅니다 (여러 번 지정 가능)
+    #[arg(long = "unsuspend", value_name = "ID")]
+    unsuspend: Vec<String>,
+}
+
+fn suspended_path() -> PathBuf {
+    history::data_dir().join("suspended.yaml")
+}
+
+pub fn load() -> io::Result<BTreeMap<String, Suspended>> {
+    let data = match fs::read_to_string(suspended_path()) {
+        Ok(data) => data,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(BTreeMap::new()),
+        Err(e) => return Err(e),
+    };
+    serde_yaml::from_str(&data).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+fn save(suspended: &BTreeMap<String, Suspended>) -> io::Result<()> {
+    fs::create_dir_all(history::data_dir())?;
+    let data = serde_yaml::to_string(suspended)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    fs::write(suspended_path(), data)
+}
+
+// 문제를 푸는 중에 `:suspend` 로 뺍니다
+pub fn add(
+    suspended: &mut BTreeMap<String, Suspended>,
+    question: &dyn Askable,
+    chapter: u32,
+    bank: &str,
+) -> io::Result<()> {
+    suspended.insert(
+        question.id(),
+        Suspended {
+            question: question.text().to_string(),
+            bank: bank.to_string(),
+            chapter,
+            at: Local::now(),
+        },
+    );
+    save(suspended)
+}
+
+fn load_or_exit() -> BTreeMap<String, Suspended> {
+    load().unwrap_or_else(|e| {
+        println!(
+            "{} {}",
+            styled(Color::Red).paint("뺀 문제 목록을 읽을 수 없습니다:"),
+            e
+        );
+        std::process::exit(1)
+    })
+}
+
+fn save_or_exit(suspended: &BTreeMap<String, Suspended>) {
+    if let Err(e) = save(suspended) {
+        println!(
+            "{} {}",
+            styled(Color::Red).paint("뺀 문제 목록을 저장할 수 없습니다:"),
+            e
+        );
+        std::process::exit(1);
+    }
+}
+
+pub fn suspend(args: SuspendArgs) {
+    let mut suspended = load_or_exit();
+    // 어느 문제인지는 학습 기록에서 가장 최근에 푼 것으로 찾습니다
+    let sessions = history::load().unwrap_or_default();
+    for id in args.ids {
+        let found = sessions.iter().rev().find_map(|s| {
+            s.answers
+                .iter()
+                .find(|a| a.id == id)
+                .map(|a| (s.bank.clone(), a))
+        });
+        let entry = match found {
+            Some((bank, answer)) => Suspended {
+                question: answer.text.clone(),
+                bank,
+                chapter: answer.chapter,
+                at: Local::now(),
+            },
+            None => {
+                println!(
+                    "{} {}",
+                    styled(Color::Yellow).paint("학습 기록에 없는 ID 이지만 뺍니다:"),
+                    id
+                );
+                Suspended {
+                    question: String::new(),
+                    bank: String::new(),
+                    chapter: 0,
+                    at: Local::now(),
+                }
+            }
+        };
+        println!("뺐습니다: {} {}", id, entry.question.trim());
+        suspended.insert(id, entry);
+    }
+    save_or_exit(&suspended);
+}
+
+pub fn list(args: SuspendedArgs) {
+    let mut suspended = load_or_exit();
+    if !args.unsuspend.is_empty() {
+        for id in &args.unsuspend {
+            match suspended.remove(id) {
+                Some(_) => println!("다시 냅니다: {}", id),
+                None => println!("{} {}", styled(Color::Red).paint("뺀 문제가 아닙니다:"), id),
+            }
+        }
+        save_or_exit(&suspended);
+        return;
+    }
+    if suspended.is_empty() {
+        println!("뺀 문제가 없습니다. 문제를 푸는 중에 `:suspend` 로 뺄 수 있습니다.");
+        return;
+    }
+
+    let ids: Vec<String> = suspended.keys().cloned().collect();
+    for (i, id) in ids.iter().enumerate() {
+        let entry = &suspended[id];
+        let question = match entry.bank.as_str() {
+            "" => "(학습 기록에 없는 문제)".to_string(),
+            bank => format!("[{} 챕터 {}] {}", bank, entry.chapter, entry.question),
+        };
+        println!(
+            "{}. {} {}",
+            i + 1,
+            question,
+            styled(Color::Cyan).paint(format!("({}, {})", id, entry.at.format("%Y-%m-%d")))
+        );
+    }
+    if !io::stdin().is_terminal() {
+        return;
+    }
+
+    print!("\n다시 낼 문제 번호 (콤마로 구분, a를 입력하면 전부, Enter는 그대로): ");
+    io::stdout().flush().unwrap();
+    let crate::input::Line::Text(line) = crate::input::read_line() else {
+        return;
+    };
+    let chosen: Vec<&String> = match line.trim() {
+        "" => return,
+        "a" => ids.iter().collect(),
+        line => line
+            .split(',')
+            .filter_map(|n| n.trim().parse::<usize>().ok())
+            .filter_map(|n| n.checked_sub(1).and_then(|i| ids.get(i)))
+            .collect(),
+    };
+    for id in chosen {
+        suspended.remove(id);
+    }
+    save_or_exit(&suspended);
+    println!("뺀 문제 {}개가 남았습니다.", suspended.len());
+}
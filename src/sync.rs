@@ -0,0 +1,637 @@
+use crate::config::{self, Conflict, Remote};
+use crate::history;
+use crate::style::styled;
+use ansi_term::Color;
+use chrono::{DateTime, FixedOffset, Local};
+use serde_yaml::Value;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+#[cfg(feature = "sync")]
+use {
+    base64::Engine,
+    chrono::Utc,
+    hmac::{Hmac, Mac},
+    sha2::{Digest, Sha256},
+    std::env,
+    ureq::Agent,
+};
+
+#[derive(clap::Args)]
+pub struct SyncArgs {
+    /// 두 기기에서 모두 바뀐 기록을 다루는 방법. 생략하면 설정의 sync.conflict
+    #[arg(long, value_enum)]
+    conflict: Option<Conflict>,
+    /// 아무것도 고치지 않고 주고받을 항목 수만 보여줍니다
+    #[arg(long)]
+    dry_run: bool,
+}
+
+// 주고받는 파일. 복습할 문제는 학습 기록으로 계산하므로 따로 주고받을 것이 없습니다
+const HISTORY: &str = "history.yaml";
+const FILES: &[&str] = &[HISTORY, "suspended.yaml", "notes.yaml"];
+// 원격에 파일마다 마지막으로 고친 시각을 적어 두는 파일
+const MANIFEST: &str = "sync.yaml";
+// 마지막으로 주고받은 기록을 데이터 디렉토리에 남겨 두고, 그 뒤에 어느 쪽에서 무엇이 바뀌었는지 가립니다
+const SYNCED: &str = "synced";
+
+type Entries = BTreeMap<String, Value>;
+type Manifest = BTreeMap<String, DateTime<Local>>;
+
+// 원격 저장소. 파일 이름은 `profiles/이름/history.yaml` 처럼 홈 디렉토리 안의 경로와 같습니다
+trait Store {
+    fn get(&mut self, name: &str) -> Result<Option<Vec<u8>>, String>;
+    fn put(&mut self, name: &str, data: &[u8]) -> Result<(), String>;
+    // 올린 파일을 확정합니다. Git 은 여기서 커밋하고 push 합니다
+    fn finish(&mut self) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+pub fn run(args: SyncArgs) {
+    let Some(settings) = config::Config::load().sync else {
+        println!(
+            "{} config.yaml 의 `sync:` 에 기록을 주고받을 곳을 적어 주세요.",
+            styled(Color::Red).paint("주고받을 곳이 없습니다.")
+        );
+        std::process::exit(1);
+    };
+    let conflict = args.conflict.unwrap_or(settings.conflict);
+    let mut store = match open(&settings.remote) {
+        Ok(store) => store,
+        Err(e) => {
+            println!(
+                "{} {}",
+                styled(Color::Red).paint("원격 저장소를 열 수 없습니다:"),
+                e
+            );
+            std::process::exit(1);
+        }
+    };
+    if let Err(e) = sync(store.as_mut(), conflict, args.dry_run) {
+        println!(
+            "{} {}",
+            styled(Color::Red).paint("기록을 주고받을 수 없습니다:"),
+            e
+        );
+        std::process::exit(1);
+    }
+}
+
+fn open(remote: &Remote) -> Result<Box<dyn Store>, String> {
+    match remote {
+        Remote::Git { url, branch } => Ok(Box::new(Git::open(url, branch)?)),
+        #[cfg(feature = "sync")]
+        Remote::Webdav { url, user } => Ok(Box::new(WebDav::new(url, user.as_deref()))),
+        #[cfg(feature = "sync")]
+        Remote::S3 {
+            endpoint,
+            bucket,
+            region,
+            prefix,
+        } => Ok(Box::new(S3::new(endpoint, bucket, region, prefix)?)),
+        #[cfg(not(feature = "sync"))]
+        Remote::Webdav { .. } | Remote::S3 { .. } => {
+            Err("WebDAV 나 S3 로 주고받으려면 `sync` 기능을 켜고 빌드해야 합니다".to_string())
+        }
+    }
+}
+
+fn sync(store: &mut dyn Store, conflict: Conflict, dry_run: bool) -> Result<(), String> {
+    let dir = history::data_dir();
+    let synced = dir.join(SYNCED);
+    let prefix = remote_prefix();
+    let mut manifest: Manifest = match store.get(&format!("{}{}", prefix, MANIFEST))? {
+        Some(data) => serde_yaml::from_slice(&data).map_err(|e| format!("{}: {}", MANIFEST, e))?,
+        None => Manifest::new(),
+    };
+
+    let mut written = Vec::new();
+    let mut uploaded = false;
+    for &file in FILES {
+        let name = format!("{}{}", prefix, file);
+        let remote = entries(parse(&name, store.get(&name)?)?);
+        let local = entries(parse(file, read(&dir.join(file))?)?);
+        let base = entries(parse(file, read(&synced.join(file))?)?);
+        let local_at = fs::metadata(dir.join(file))
+            .and_then(|m| m.modified())
+            .ok()
+            .map(DateTime::<Local>::from);
+
+        let merged = match conflict {
+            Conflict::Merge => merge(&base, &local, &remote),
+            Conflict::LastWriteWins => {
+                last_write_wins(file, &base, &local, &remote, local_at, manifest.get(file))
+            }
+        };
+        let pulled = changes(&local, &merged);
+        let pushed = changes(&remote, &merged);
+        if pulled + pushed == 0 {
+            println!("{}: 바뀐 것이 없습니다", file);
+        } else {
+            println!(
+                "{}: 받은 항목 {}개, 보낸 항목 {}개",
+                file,
+                styled(Color::Green).paint(pulled.to_string()),
+                styled(Color::Green).paint(pushed.to_string())
+            );
+        }
+        if dry_run {
+            continue;
+        }
+
+        let text = render(file, &merged)?;
+        if pushed > 0 {
+            store.put(&name, text.as_bytes())?;
+            // 이 기기의 파일을 그대로 올렸으면 그 파일을 고친 시각을 적어 다음 비교에 씁니다
+            let at = match (merged == local, local_at) {
+                (true, Some(at)) => at,
+                _ => Local::now(),
+            };
+            manifest.insert(file.to_string(), at);
+            uploaded = true;
+        }
+        written.push((file, text, pulled > 0));
+    }
+    if dry_run {
+        println!("--dry-run 이라 아무것도 고치지 않았습니다.");
+        return Ok(());
+    }
+
+    if uploaded {
+        let data = serde_yaml::to_string(&manifest).map_err(|e| e.to_string())?;
+        store.put(&format!("{}{}", prefix, MANIFEST), data.as_bytes())?;
+    }
+    store.finish()?;
+    // 원격에 올린 뒤에만 이 기기의 기록과 마지막으로 맞춘 기록을 고칩니다
+    let write = |path: PathBuf, text: &str| {
+        fs::write(&path, text).map_err(|e| format!("{}: {}", path.display(), e))
+    };
+    fs::create_dir_all(&synced).map_err(|e| format!("{}: {}", synced.display(), e))?;
+    for (file, text, pulled) in written {
+        if pulled {
+            write(dir.join(file), &text)?;
+        }
+        write(synced.join(file), &text)?;
+    }
+    println!("기록을 주고받았습니다.");
+    Ok(())
+}
+
+// 기본 프로필은 원격의 맨 위에, 다른 프로필은 `profiles/이름/` 아래에 둡니다
+fn remote_prefix() -> String {
+    let dir = history::data_dir();
+    let relative = dir
+        .strip_prefix(history::home_dir())
+        .unwrap_or(Path::new(""));
+    relative
+        .components()
+        .map(|c| format!("{}/", c.as_os_str().to_string_lossy()))
+        .collect()
+}
+
+fn read(path: &Path) -> Result<Option<Vec<u8>>, String> {
+    match fs::read(path) {
+        Ok(data) => Ok(Some(data)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(format!("{}: {}", path.display(), e)),
+    }
+}
+
+fn parse(name: &str, data: Option<Vec<u8>>) -> Result<Value, String> {
+    match data {
+        Some(data) => serde_yaml::from_slice(&data).map_err(|e| format!("{}: {}", name, e)),
+        None => Ok(Value::Null),
+    }
+}
+
+// 세션은 시작한 시각으로, 빼 둔 문제와 메모는 문제 ID 로 가려냅니다
+fn entries(value: Value) -> Entries {
+    match value {
+        Value::Sequence(sessions) => sessions
+            .into_iter()
+            .filter_map(|session| {
+                let key = session.get("started_at")?.as_str()?.to_string();
+                Some((key, session))
+            })
+            .collect(),
+        Value::Mapping(map) => map
+            .into_iter()
+            .filter_map(|(key, value)| Some((key.as_str()?.to_string(), value)))
+            .collect(),
+        _ => Entries::new(),
+    }
+}
+
+fn stamp(value: &Value) -> Option<DateTime<FixedOffset>> {
+    let at = value.get("at").or_else(|| value.get("started_at"))?;
+    DateTime::parse_from_rfc3339(at.as_str()?).ok()
+}
+
+fn merge(base: &Entries, local: &Entries, remote: &Entries) -> Entries {
+    let keys: BTreeSet<&String> = base
+        .keys()
+        .chain(local.keys())
+        .chain(remote.keys())
+        .collect();
+    keys.into_iter()
+        .filter_map(|key| {
+            let base = base.get(key);
+            let picked = match (local.get(key), remote.get(key)) {
+                (Some(local), Some(remote)) if local == remote || base == Some(remote) => local,
+                (Some(local), Some(remote)) if base == Some(local) => remote,
+                (Some(local), Some(remote)) => match stamp(remote) > stamp(local) {
+                    true => remote,
+                    false => local,
+                },
+                // 한쪽에만 있으면 그쪽에서 새로 생겼거나 다른 쪽에서 지운 것입니다. 지운 뒤에 고쳤으면 남깁니다
+                (Some(only), None) | (None, Some(only)) if base == Some(only) => return None,
+                (Some(only), None) | (None, Some(only)) => only,
+                (None, None) => return None,
+            };
+            Some((key.clone(), picked.clone()))
+        })
+        .collect()
+}
+
+fn last_write_wins(
+    file: &str,
+    base: &Entries,
+    local: &Entries,
+    remote: &Entries,
+    local_at: Option<DateTime<Local>>,
+    remote_at: Option<&DateTime<Local>>,
+) -> Entries {
+    if local == base {
+        return remote.clone();
+    }
+    if remote == base {
+        return local.clone();
+    }
+    let (winner, side) = match remote_at > local_at.as_ref() {
+        true => (remote, "다른 기기"),
+        false => (local, "이 기기"),
+    };
+    println!(
+        "{}",
+        styled(Color::Yellow).paint(format!(
+            "{}: 두 기기에서 모두 바뀌어 나중에 고친 {}의 것으로 맞춥니다",
+            file, side
+        ))
+    );
+    winner.clone()
+}
+
+// 더해지거나 바뀌거나 지워진 항목 수
+fn changes(from: &Entries, to: &Entries) -> usize {
+    let changed = from
+        .iter()
+        .filter(|(key, value)| to.get(*key) != Some(value));
+    changed.count() + to.keys().filter(|key| !from.contains_key(*key)).count()
+}
+
+fn render(file: &str, entries: &Entries) -> Result<String, String> {
+    let value = if file == HISTORY {
+        let mut sessions: Vec<&Value> = entries.values().collect();
+        sessions.sort_by_key(|session| stamp(session));
+        Value::Sequence(sessions.into_iter().cloned().collect())
+    } else {
+        Value::Mapping(
+            entries
+                .iter()
+                .map(|(key, value)| (Value::String(key.clone()), value.clone()))
+                .collect(),
+        )
+    };
+    serde_yaml::to_string(&value).map_err(|e| e.to_string())
+}
+
+// 홈 디렉토리의 `sync-repo` 에 작업 사본을 두고 주고받을 때마다 원격의 마지막 커밋에 맞춥니다
+struct Git {
+    dir: PathBuf,
+    branch: String,
+    changed: bool,
+}
+
+impl Git {
+    fn open(url: &str, branch: &str) -> Result<Self, String> {
+        let dir = history::home_dir().join("sync-repo");
+        if dir.join(".git").is_dir() {
+            git(&dir, &["remote", "set-url", "origin", url])?;
+        } else {
+            fs::create_dir_all(&dir).map_err(|e| format!("{}: {}", dir.display(), e))?;
+            git(&dir, &["init", "-q"])?;
+            git(&dir, &["remote", "add", "origin", url])?;
+        }
+        git(&dir, &["fetch", "-q", "origin"])?;
+        // 아직 아무것도 올리지 않은 저장소면 맞출 커밋이 없습니다
+        let upstream = format!("refs/remotes/origin/{}", branch);
+        if git(&dir, &["rev-parse", "-q", "--verify", &upstream]).is_ok() {
+            git(&dir, &["checkout", "-q", "-f", "-B", branch, &upstream])?;
+        }
+        git(&dir, &["clean", "-q", "-f", "-d"])?;
+        Ok(Self {
+            dir,
+            branch: branch.to_string(),
+            changed: false,
+        })
+    }
+}
+
+impl Store for Git {
+    fn get(&mut self, name: &str) -> Result<Option<Vec<u8>>, String> {
+        read(&self.dir.join(name))
+    }
+
+    fn put(&mut self, name: &str, data: &[u8]) -> Result<(), String> {
+        let path = self.dir.join(name);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| format!("{}: {}", parent.display(), e))?;
+        }
+        fs::write(&path, data).map_err(|e| format!("{}: {}", path.display(), e))?;
+        self.changed = true;
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Result<(), String> {
+        if !self.changed {
+            return Ok(());
+        }
+        git(&self.dir, &["add", "-A"])?;
+        let message = format!("terms sync {}", Local::now().format("%Y-%m-%d %H:%M"));
+        let mut commit = Vec::new();
+        // Git 에 이름을 적어 두지 않은 기기에서도 커밋할 수 있게 합니다
+        if git(&self.dir, &["config", "user.email"]).is_err() {
+            commit.extend(["-c", "user.name=terms", "-c", "user.email=terms@localhost"]);
+        }
+        commit.extend(["commit", "-q", "-m", &message]);
+        git(&self.dir, &commit)?;
+        let refspec = format!("HEAD:refs/heads/{}", self.branch);
+        git(&self.dir, &["push", "-q", "origin", &refspec])
+            .map_err(|e| format!("{}\n다른 기기에서 먼저 올렸다면 다시 실행하세요", e))
+    }
+}
+
+fn git(dir: &Path, args: &[&str]) -> Result<(), String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .output()
+        .map_err(|e| format!("git 을 실행할 수 없습니다: {}", e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "git {}: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(feature = "sync")]
+fn agent() -> Agent {
+    Agent::new_with_config(
+        Agent::config_builder()
+            .http_status_as_error(false)
+            .allow_non_standard_methods(true)
+            .build(),
+    )
+}
+
+// 상태 코드와 본문을 돌려줍니다
+#[cfg(feature = "sync")]
+fn call(
+    agent: &Agent,
+    method: &str,
+    url: &str,
+    headers: &[(&str, String)],
+    body: Option<&[u8]>,
+) -> Result<(u16, Vec<u8>), String> {
+    let mut builder = ureq::http::Request::builder().method(method).uri(url);
+    for (name, value) in headers {
+        builder = builder.header(*name, value);
+    }
+    let response = match body {
+        Some(body) => builder.body(body).map(|request| agent.run(request)),
+        None => builder.body(()).map(|request| agent.run(request)),
+    }
+    .map_err(|e| e.to_string())?
+    .map_err(|e| format!("{} {}: {}", method, url, e))?;
+    let status = response.status().as_u16();
+    let data = response
+        .into_body()
+        .with_config()
+        .limit(u64::MAX)
+        .read_to_vec()
+        .map_err(|e| format!("{} {}: {}", method, url, e))?;
+    Ok((status, data))
+}
+
+#[cfg(feature = "sync")]
+fn fetched(
+    method: &str,
+    name: &str,
+    (status, data): (u16, Vec<u8>),
+) -> Result<Option<Vec<u8>>, String> {
+    match status {
+        404 => Ok(None),
+        200..=299 => Ok(Some(data)),
+        _ => Err(format!(
+            "{} {} ({}): {}",
+            method,
+            name,
+            status,
+            String::from_utf8_lossy(&data).trim()
+        )),
+    }
+}
+
+#[cfg(feature = "sync")]
+const PASSWORD_VAR: &str = "TERMS_SYNC_PASSWORD";
+
+#[cfg(feature = "sync")]
+struct WebDav {
+    agent: Agent,
+    url: String,
+    auth: Vec<(&'static str, String)>,
+}
+
+#[cfg(feature = "sync")]
+impl WebDav {
+    fn new(url: &str, user: Option<&str>) -> Self {
+        let auth = user
+            .map(|user| {
+                let password = env::var(PASSWORD_VAR).unwrap_or_default();
+                let encoded = base64::engine::general_purpose::STANDARD
+                    .encode(format!("{}:{}", user, password));
+                ("Authorization", format!("Basic {}", encoded))
+            })
+            .into_iter()
+            .collect();
+        Self {
+            agent: agent(),
+            url: url.trim_end_matches('/').to_string(),
+            auth,
+        }
+    }
+}
+
+#[cfg(feature = "sync")]
+impl Store for WebDav {
+    fn get(&mut self, name: &str) -> Result<Option<Vec<u8>>, String> {
+        let url = format!("{}/{}", self.url, name);
+        fetched(
+            "GET",
+            name,
+            call(&self.agent, "GET", &url, &self.auth, None)?,
+        )
+    }
+
+    fn put(&mut self, name: &str, data: &[u8]) -> Result<(), String> {
+        // 프로필 폴더가 없으면 PUT 이 실패하므로 먼저 만듭니다. 이미 있으면 서버가 거절할 뿐입니다
+        let parts: Vec<&str> = name.split('/').collect();
+        for depth in 1..parts.len() {
+            let folder = format!("{}/{}/", self.url, parts[..depth].join("/"));
+            call(&self.agent, "MKCOL", &folder, &self.auth, None)?;
+        }
+        let url = format!("{}/{}", self.url, name);
+        let response = call(&self.agent, "PUT", &url, &self.auth, Some(data))?;
+        fetched("PUT", name, response).map(drop)
+    }
+}
+
+#[cfg(feature = "sync")]
+struct S3 {
+    agent: Agent,
+    endpoint: String,
+    host: String,
+    bucket: String,
+    region: String,
+    prefix: String,
+    access_key: String,
+    secret_key: String,
+}
+
+#[cfg(feature = "sync")]
+const SIGNED_HEADERS: &str = "host;x-amz-content-sha256;x-amz-date";
+
+#[cfg(feature = "sync")]
+impl S3 {
+    fn new(endpoint: &str, bucket: &str, region: &str, prefix: &str) -> Result<Self, String> {
+        let (Ok(access_key), Ok(secret_key)) = (
+            env::var("AWS_ACCESS_KEY_ID"),
+            env::var("AWS_SECRET_ACCESS_KEY"),
+        ) else {
+            return Err(
+                "AWS_ACCESS_KEY_ID 와 AWS_SECRET_ACCESS_KEY 환경 변수에 키를 넣어 주세요"
+                    .to_string(),
+            );
+        };
+        let endpoint = endpoint.trim_end_matches('/');
+        let host = endpoint
+            .split_once("://")
+            .map_or(endpoint, |(_, rest)| rest)
+            .split('/')
+            .next()
+            .unwrap_or_default();
+        let prefix = prefix.trim_matches('/');
+        Ok(Self {
+            agent: agent(),
+            endpoint: endpoint.to_string(),
+            host: host.to_string(),
+            bucket: bucket.to_string(),
+            region: region.to_string(),
+            prefix: match prefix.is_empty() {
+                true => String::new(),
+                false => format!("{}/", prefix),
+            },
+            access_key,
+            secret_key,
+        })
+    }
+
+    // AWS 서명 4판으로 서명해서 보냅니다. 버킷은 호스트 대신 경로에 넣어 S3 호환 저장소에서도 쓸 수 있게 합니다
+    fn send(
+        &self,
+        method: &str,
+        name: &str,
+        body: Option<&[u8]>,
+    ) -> Result<(u16, Vec<u8>), String> {
+        let path = format!("/{}/{}{}", self.bucket, self.prefix, name)
+            .split('/')
+            .map(uri_encode)
+            .collect::<Vec<_>>()
+            .join("/");
+        let date = Utc::now().format("%Y%m%dT%H%M%SZ").to_string();
+        let day = &date[..8];
+        let payload = hex(&Sha256::digest(body.unwrap_or_default()));
+        let canonical = format!(
+            "{}\n{}\n\nhost:{}\nx-amz-content-sha256:{}\nx-amz-date:{}\n\n{}\n{}",
+            method, path, self.host, payload, date, SIGNED_HEADERS, payload
+        );
+        let scope = format!("{}/{}/s3/aws4_request", day, self.region);
+        let to_sign = format!(
+            "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+            date,
+            scope,
+            hex(&Sha256::digest(canonical.as_bytes()))
+        );
+        let key = [day, &self.region, "s3", "aws4_request"].iter().fold(
+            format!("AWS4{}", self.secret_key).into_bytes(),
+            |key, part| hmac(&key, part.as_bytes()).to_vec(),
+        );
+        let authorization = format!(
+            "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}",
+            self.access_key,
+            scope,
+            SIGNED_HEADERS,
+            hex(&hmac(&key, to_sign.as_bytes()))
+        );
+        let headers = [
+            ("x-amz-date", date.clone()),
+            ("x-amz-content-sha256", payload),
+            ("Authorization", authorization),
+        ];
+        let url = format!("{}{}", self.endpoint, path);
+        call(&self.agent, method, &url, &headers, body)
+    }
+}
+
+#[cfg(feature = "sync")]
+impl Store for S3 {
+    fn get(&mut self, name: &str) -> Result<Option<Vec<u8>>, String> {
+        fetched("GET", name, self.send("GET", name, None)?)
+    }
+
+    fn put(&mut self, name: &str, data: &[u8]) -> Result<(), String> {
+        fetched("PUT", name, self.send("PUT", name, Some(data))?).map(drop)
+    }
+}
+
+#[cfg(feature = "sync")]
+fn hmac(key: &[u8], data: &[u8]) -> [u8; 32] {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC 은 어떤 길이의 키도 받습니다");
+    mac.update(data);
+    mac.finalize().into_bytes().into()
+}
+
+#[cfg(feature = "sync")]
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[cfg(feature = "sync")]
+fn uri_encode(segment: &str) -> String {
+    segment
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
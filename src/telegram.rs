@@ -0,0 +1,480 @@
+use crate::style::styled;
+use crate::web::{asked, Submission};
+use crate::{build_questions, config, history, ChapterSet, QuizArgs, Source};
+use ansi_term::Color;
+use chrono::{DateTime, Local};
+use rand::thread_rng;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::env;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::thread;
+use std::time::Duration;
+use terms::score::Score;
+use terms::{weight, weighted_shuffle, Askable, BuiltQuestions, GeneratedQuestion, Kind};
+use ureq::Agent;
+
+#[derive(clap::Args)]
+pub struct TelegramArgs {
+    /// `/quiz` 에 수를 붙이지 않았을 때 낼 문제 수
+    #[arg(long, value_name = "N", default_value_t = 10)]
+    per_quiz: usize,
+    #[command(flatten)]
+    quiz: QuizArgs,
+}
+
+// 봇 토큰은 명령줄에 남지 않도록 환경 변수로만 받습니다
+const TOKEN_VAR: &str = "TELEGRAM_TOKEN";
+// 새 메시지가 올 때까지 한 번에 기다리는 시간(초)
+const POLL_SECS: u64 = 30;
+// 인라인 버튼 글자 수의 제한
+const BUTTON_LEN: usize = 40;
+
+// 봇을 다시 켜도 풀던 문제를 이어서 낼 수 있도록 대화방마다 저장해 둡니다
+#[derive(Serialize, Deserialize, Default)]
+struct State {
+    // 다음에 받을 업데이트 번호
+    offset: i64,
+    chats: BTreeMap<i64, Chat>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Chat {
+    // 문제 파일이 바뀌어도 알아볼 수 있도록 문제 ID 로 저장합니다
+    order: Vec<String>,
+    position: usize,
+    correct: usize,
+    points: Score,
+    answers: Vec<history::Answer>,
+    started_at: DateTime<Local>,
+    asked_at: DateTime<Local>,
+    // 지금 문제가 물을 때마다 새로 만드는 문제면 이번에 만든 것. 봇을 다시 켜도 같은 문제로 채점합니다
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    current: Option<GeneratedQuestion>,
+}
+
+fn state_path() -> PathBuf {
+    history::data_dir().join("telegram.yaml")
+}
+
+impl State {
+    fn load() -> io::Result<Self> {
+        match fs::read_to_string(state_path()) {
+            Ok(data) => serde_yaml::from_str(&data)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(State::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn save(&self) -> io::Result<()> {
+        fs::create_dir_all(history::data_dir())?;
+        let data = serde_yaml::to_string(self)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        fs::write(state_path(), data)
+    }
+}
+
+struct Client {
+    agent: Agent,
+    base: String,
+}
+
+impl Client {
+    // 요청이 너무 잦다는 응답(429)을 받으면 알려 준 시간만큼 쉬었다가 다시 보냅니다
+    fn call(&self, method: &str, body: &Value) -> Result<Value, String> {
+        loop {
+            let mut response = self
+                .agent
+                .post(format!("{}/{}", self.base, method))
+                .send_json(body)
+                .map_err(|e| e.to_string())?;
+            let reply: Value = response.body_mut().read_json().map_err(|e| e.to_string())?;
+            if reply["ok"].as_bool() == Some(true) {
+                return Ok(reply["result"].clone());
+            }
+            match reply["parameters"]["retry_after"].as_u64() {
+                Some(wait) => thread::sleep(Duration::from_secs(wait)),
+                None => {
+                    return Err(reply["description"]
+                        .as_str()
+                        .unwrap_or("알 수 없는 오류")
+                        .to_string())
+                }
+            }
+        }
+    }
+
+    fn send(&self, chat: i64, text: &str, keyboard: Option<Value>) -> Result<(), String> {
+        let mut body = json!({ "chat_id": chat, "text": text });
+        if let Some(keyboard) = keyboard {
+            body["reply_markup"] = json!({ "inline_keyboard": keyboard });
+        }
+        self.call("sendMessage", &body).map(|_| ())
+    }
+}
+
+struct Bot {
+    config: config::Config,
+    bank: String,
+    points: BTreeMap<Kind, f64>,
+    questions: BuiltQuestions,
+    ids: HashMap<String, usize>,
+    per_quiz: usize,
+    client: Client,
+}
+
+fn truncate(text: &str, len: usize) -> String {
+    match text.char_indices().nth(len) {
+        Some((end, _)) => format!("{}…", &text[..end]),
+        None => text.to_string(),
+    }
+}
+
+impl Bot {
+    fn question(&self, chat: &Chat) -> Option<&(Box<dyn Askable>, u32)> {
+        let id = chat.order.get(chat.position)?;
+        self.ids.get(id).map(|&i| &self.questions[i])
+    }
+
+    fn ask(&self, id: i64, chat: &mut Chat) -> Result<(), String> {
+        // 그 사이 문제 파일에서 빠진 문제는 건너뜁니다
+        while chat.position < chat.order.len() && self.question(chat).is_none() {
+            chat.order.remove(chat.position);
+        }
+        let Some((question, chapter)) = self.question(chat) else {
+            return self.finish(id, chat);
+        };
+        chat.current = question.generate();
+        let question = asked(question.as_ref(), &chat.current);
+        let mut text = format!(
+            "챕터 {} ({}/{}) · {}\n{}",
+            chapter,
+            chat.position + 1,
+            chat.order.len(),
+            question.kind().label(),
+            question.prompt(&mut thread_rng())
+        );
+        let keyboard = question.choices().map(|(options, marker)| {
+            let rows: Vec<Value> = options
+                .iter()
+                .enumerate()
+                .map(|(i, option)| {
+                    let label = match marker.label(i) {
+                        label if label.is_empty() => option.clone(),
+                        label => format!("{}. {}", label, option),
+                    };
+                    // 예전 문제의 버튼을 눌렀을 때 알아볼 수 있도록 문제 순서를 함께 보냅니다
+                    json!([{
+                        "text": truncate(&label, BUTTON_LEN),
+                        "callback_data": format!("{}:{}", chat.position, i),
+                    }])
+                })
+                .collect();
+            rows
+        });
+        if keyboard.is_none() {
+            text += "\n\n답을 보내 주세요. (/skip 건너뛰기, /stop 그만두기)";
+        }
+        chat.asked_at = Local::now();
+        self.client.send(id, &text, keyboard.map(Value::Array))
+    }
+
+    fn answer(&self, id: i64, chat: &mut Chat, submission: Submission) -> Result<(), String> {
+        let Some((question, chapter)) = self.question(chat) else {
+            return self.ask(id, chat);
+        };
+        let current = chat.current.take();
+        let question = asked(question.as_ref(), &current);
+        let answer = submission.resolve(question);
+        let weight = weight(question, &self.points, &self.config.points);
+        let result = match &answer {
+            Some(answer) => question.grade(answer.trim()),
+            None => Score::skipped(),
+        }
+        .weighted(weight);
+        chat.points += result;
+        if result.is_correct() {
+            chat.correct += 1;
+        }
+        let elapsed = Local::now() - chat.asked_at;
+        chat.answers.push(history::Answer {
+            id: question.id(),
+            text: question.text().to_string(),
+            chapter: *chapter,
+            kind: question.kind(),
+            group: question.group().map(str::to_string),
+            correct: result.is_correct(),
+            score: Some(result),
+            time_ms: elapsed.num_milliseconds().max(0) as u64,
+            lifeline: false,
+            options: history::options(question),
+            given: history::given(question, answer.as_slice()),
+            expected: question.correct_answer(),
+        });
+
+        let mut text = if result.skipped {
+            format!("건너뛰었습니다. 정답: {}", question.correct_answer())
+        } else if result.is_correct() {
+            "맞았습니다!".to_string()
+        } else if result.is_partial() {
+            format!(
+                "부분 정답입니다 ({}). 정답: {}",
+                result,
+                question.correct_answer()
+            )
+        } else {
+            format!("틀렸습니다. 정답: {}", question.correct_answer())
+        };
+        if let Some(reference) = question.reference() {
+            text += &format!("\n출처: {}", reference);
+        }
+        self.client.send(id, &text, None)?;
+        chat.position += 1;
+        self.ask(id, chat)
+    }
+
+    fn finish(&self, id: i64, chat: &Chat) -> Result<(), String> {
+        let mut text = format!(
+            "끝! {}문제 중 {}개를 맞혔습니다. 점수: {} ({:.0}%)",
+            chat.answers.len(),
+            chat.correct,
+            chat.points,
+            chat.points.percent()
+        );
+        if let Some(grade) = self.config.grading.grade(&chat.points) {
+            text += &format!(", 등급 {}", grade);
+        }
+        if !chat.answers.is_empty() {
+            let record = history::Session {
+                bank: self.bank.clone(),
+                started_at: chat.started_at,
+                duration_secs: (Local::now() - chat.started_at).num_milliseconds() as f64 / 1000.0,
+                answers: chat.answers.clone(),
+            };
+            if let Err(e) = history::append(record) {
+                println!(
+                    "{} {}",
+                    styled(Color::Red).paint("학습 기록을 저장할 수 없습니다:"),
+                    e
+                );
+            }
+        }
+        self.client.send(id, &(text + "\n다시 풀려면 /quiz"), None)
+    }
+
+    fn start(&self, count: usize) -> Chat {
+        let mut order: Vec<usize> = (0..self.questions.len()).collect();
+        weighted_shuffle(
+            &mut order,
+            |&i| self.questions[i].0.frequency(),
+            &mut thread_rng(),
+        );
+        order.truncate(count.max(1));
+        Chat {
+            order: order.iter().map(|&i| self.questions[i].0.id()).collect(),
+            position: 0,
+            correct: 0,
+            points: Score::default(),
+            answers: Vec::new(),
+            started_at: Local::now(),
+            asked_at: Local::now(),
+            current: None,
+        }
+    }
+
+    fn message(&self, state: &mut State, message: &Value) -> Result<(), String> {
+        let (Some(id), Some(text)) = (message["chat"]["id"].as_i64(), message["text"].as_str())
+        else {
+            return Ok(());
+        };
+        let text = text.trim();
+        // 단체방에서는 `/quiz@봇이름` 처럼 옵니다
+        let command = text
+            .strip_prefix('/')
+            .map(|rest| rest.split_whitespace().next().unwrap_or_default())
+            .map(|command| command.split('@').next().unwrap_or_default());
+        match command {
+            Some("start" | "quiz") => {
+                let count = text
+                    .split_whitespace()
+                    .nth(1)
+                    .and_then(|n| n.parse().ok())
+                    .unwrap_or(self.per_quiz);
+                let mut chat = self.start(count);
+                let result = self.ask(id, &mut chat);
+                state.chats.insert(id, chat);
+                result
+            }
+            Some("stop") => match state.chats.remove(&id) {
+                Some(chat) => self.finish(id, &chat),
+                None => self.client.send(id, "풀고 있는 문제가 없습니다.", None),
+            },
+            Some("skip") => match state.chats.get_mut(&id) {
+                Some(chat) => self.answer(id, chat, Submission::default()),
+                None => self.client.send(id, "풀고 있는 문제가 없습니다.", None),
+            },
+            _ => match state.chats.get_mut(&id) {
+                Some(chat) => {
+                    let submission = Submission {
+                        answer: Some(text.to_string()),
+                        choice: None,
+                    };
+                    self.answer(id, chat, submission)
+                }
+                None => self.client.send(
+                    id,
+                    &format!(
+                        "/quiz 로 {}의 문제를 풉니다. /quiz 20 처럼 문제 수를 정할 수 있습니다.",
+                        self.bank
+                    ),
+                    None,
+                ),
+            },
+        }
+    }
+
+    fn callback(&self, state: &mut State, query: &Value) -> Result<(), String> {
+        self.client.call(
+            "answerCallbackQuery",
+            &json!({ "callback_query_id": query["id"] }),
+        )?;
+        let message = &query["message"];
+        let (Some(id), Some((position, choice))) = (
+            message["chat"]["id"].as_i64(),
+            query["data"].as_str().and_then(|data| data.split_once(':')),
+        ) else {
+            return Ok(());
+        };
+        let Some(chat) = state.chats.get_mut(&id) else {
+            return Ok(());
+        };
+        if position.parse() != Ok(chat.position) {
+            return Ok(());
+        }
+        // 같은 버튼을 두 번 누르지 않도록 답한 문제의 버튼을 지웁니다
+        self.client.call(
+            "editMessageReplyMarkup",
+            &json!({ "chat_id": id, "message_id": message["message_id"] }),
+        )?;
+        let submission = Submission {
+            answer: None,
+            choice: choice.parse().ok(),
+        };
+        self.answer(id, chat, submission)
+    }
+}
+
+pub fn run(args: TelegramArgs) {
+    let Ok(token) = env::var(TOKEN_VAR) else {
+        println!(
+            "{} {} 환경 변수에 봇 토큰을 넣어 주세요.",
+            styled(Color::Red).paint("봇 토큰이 없습니다."),
+            TOKEN_VAR
+        );
+        std::process::exit(1);
+    };
+    let source = Source::open(&args.quiz);
+    let selected: HashSet<u32> = match &args.quiz.chapters {
+        Some(ChapterSet(chapters)) => chapters.iter().copied().collect(),
+        None => source.chapters().into_iter().collect(),
+    };
+    let points = source.points();
+    let questions = build_questions(
+        source.load(&selected, &args.quiz),
+        args.quiz.seed,
+        args.quiz.recall,
+    );
+    if questions.is_empty() {
+        println!("풀 문제가 없습니다.");
+        return;
+    }
+    let mut state = match State::load() {
+        Ok(state) => state,
+        Err(e) => {
+            println!(
+                "{} {}: {}",
+                styled(Color::Red).paint("봇 상태를 읽을 수 없습니다"),
+                state_path().display(),
+                e
+            );
+            std::process::exit(1);
+        }
+    };
+    let bot = Bot {
+        config: config::Config::load(),
+        bank: args.quiz.bank_name(),
+        points,
+        ids: questions
+            .iter()
+            .enumerate()
+            .map(|(i, (question, _))| (question.id(), i))
+            .collect(),
+        questions,
+        per_quiz: args.per_quiz,
+        client: Client {
+            agent: Agent::new_with_config(
+                Agent::config_builder().http_status_as_error(false).build(),
+            ),
+            base: format!("https://api.telegram.org/bot{}", token),
+        },
+    };
+    println!(
+        "{}: 문제 {}개, 풀고 있는 대화방 {}개. 끝내려면 Ctrl-C 를 누르세요.",
+        bot.bank,
+        bot.questions.len(),
+        state.chats.len()
+    );
+
+    loop {
+        let updates = bot.client.call(
+            "getUpdates",
+            &json!({
+                "offset": state.offset,
+                "timeout": POLL_SECS,
+                "allowed_updates": ["message", "callback_query"],
+            }),
+        );
+        let updates = match updates {
+            Ok(updates) => updates,
+            Err(e) => {
+                println!(
+                    "{} {}",
+                    styled(Color::Red).paint("텔레그램과 통신할 수 없습니다:"),
+                    e
+                );
+                thread::sleep(Duration::from_secs(5));
+                continue;
+            }
+        };
+        for update in updates.as_array().into_iter().flatten() {
+            state.offset = update["update_id"].as_i64().unwrap_or(state.offset) + 1;
+            let result = if update["callback_query"].is_object() {
+                bot.callback(&mut state, &update["callback_query"])
+            } else {
+                bot.message(&mut state, &update["message"])
+            };
+            // 끝난 대화방은 지웁니다
+            state
+                .chats
+                .retain(|_, chat| chat.position < chat.order.len());
+            if let Err(e) = result {
+                println!(
+                    "{} {}",
+                    styled(Color::Red).paint("답장을 보낼 수 없습니다:"),
+                    e
+                );
+            }
+            if let Err(e) = state.save() {
+                println!(
+                    "{} {}",
+                    styled(Color::Red).paint("봇 상태를 저장할 수 없습니다:"),
+                    e
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,320 @@
+use crate::question::{fnv1a, Askable, GeneratedQuestion};
+use crate::registry::QuestionType;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::Deserialize;
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashSet};
+
+// 변수 하나가 가질 수 있는 값들: "2..9" 같은 정수 범위나 값 목록
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Pool {
+    Range(String),
+    List(Vec<serde_yaml::Value>),
+}
+
+#[derive(Deserialize)]
+struct Entry {
+    question: String,
+    answer: String,
+    #[serde(default)]
+    vars: BTreeMap<String, Pool>,
+    #[serde(default)]
+    id: Option<String>,
+    #[serde(default = "one")]
+    count: usize,
+    #[serde(default)]
+    points: Option<f64>,
+    #[serde(default)]
+    weight: Option<f64>,
+}
+
+fn one() -> usize {
+    1
+}
+
+// 한 항목으로 만들 수 있는 문제 수. 잘못 적은 `count` 로 문제 파일을 읽다가 멈추지 않게 막습니다
+const MAX_COUNT: usize = 1000;
+
+#[derive(Clone)]
+enum Value {
+    Number(f64),
+    Text(String),
+}
+
+// 변수 하나가 가질 수 있는 값들. 범위는 펼쳐 두지 않고 뽑을 때마다 양 끝 사이에서 고릅니다
+enum Values {
+    Range(i64, i64),
+    List(Vec<Value>),
+}
+
+impl Values {
+    fn pick(&self, rng: &mut StdRng) -> Value {
+        match self {
+            Values::Range(low, high) => Value::Number(rng.gen_range(*low..=*high) as f64),
+            Values::List(values) => values[rng.gen_range(0..values.len())].clone(),
+        }
+    }
+}
+
+impl Value {
+    fn number(&self) -> Result<f64, String> {
+        match self {
+            Value::Number(n) => Ok(*n),
+            Value::Text(t) => Err(format!("숫자가 아닌 값으로 계산할 수 없습니다: {}", t)),
+        }
+    }
+
+    fn render(&self) -> String {
+        match self {
+            Value::Number(n) if n.fract() == 0.0 && n.abs() < 1e15 => format!("{}", *n as i64),
+            Value::Number(n) => {
+                let text = format!("{:.6}", n);
+                text.trim_end_matches('0').trim_end_matches('.').to_string()
+            }
+            Value::Text(t) => t.clone(),
+        }
+    }
+}
+
+// 챕터의 `templated` 키를 읽는 문제 유형. 변수마다 값을 뽑아 `{x * y}` 같은 자리를 채웁니다
+pub struct Templated {
+    rng: RefCell<StdRng>,
+}
+
+impl Templated {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            rng: RefCell::new(StdRng::seed_from_u64(seed)),
+        }
+    }
+}
+
+impl QuestionType for Templated {
+    fn key(&self) -> &str {
+        "templated"
+    }
+
+    fn build(&self, value: serde_yaml::Value) -> Result<Vec<Box<dyn Askable>>, String> {
+        let entry: Entry = serde_yaml::from_value(value).map_err(|e| e.to_string())?;
+        if entry.count > MAX_COUNT {
+            return Err(format!(
+                "count 는 {} 이하여야 합니다 ({})",
+                MAX_COUNT, entry.count
+            ));
+        }
+        let mut pools = BTreeMap::new();
+        for (name, pool) in &entry.vars {
+            pools.insert(name.as_str(), expand(name, pool)?);
+        }
+        let id = entry.id.clone().unwrap_or_else(|| {
+            format!(
+                "custom-{:08x}",
+                fnv1a(&format!("{}\0{}", entry.question, entry.answer))
+            )
+        });
+
+        let mut rng = self.rng.borrow_mut();
+        let mut seen = HashSet::new();
+        let mut questions: Vec<Box<dyn Askable>> = Vec::new();
+        // 값의 조합이 모자라면 같은 문제가 겹치지 않는 만큼만 만듭니다
+        for _ in 0..entry.count.saturating_mul(10) {
+            if questions.len() == entry.count {
+                break;
+            }
+            let vars: BTreeMap<&str, Value> = pools
+                .iter()
+                .map(|(name, values)| (*name, values.pick(&mut rng)))
+                .collect();
+            let question = fill(&entry.question, &vars)?;
+            if !seen.insert(question.clone()) {
+                continue;
+            }
+            questions.push(Box::new(GeneratedQuestion {
+                id: id.clone(),
+                answer: fill(&entry.answer, &vars)?,
+                question,
+                options: None,
+                points: entry.points,
+                weight: entry.weight,
+            }));
+        }
+        Ok(questions)
+    }
+}
+
+fn expand(name: &str, pool: &Pool) -> Result<Values, String> {
+    let values: Vec<Value> = match pool {
+        Pool::Range(range) => {
+            let bounds = range
+                .split_once("..")
+                .and_then(|(low, high)| {
+                    let high = high.strip_prefix('=').unwrap_or(high);
+                    Some((
+                        low.trim().parse::<i64>().ok()?,
+                        high.trim().parse::<i64>().ok()?,
+                    ))
+                })
+                .ok_or_else(|| format!("{}: 범위는 \"2..9\" 처럼 적어야 합니다", name))?;
+            return Ok(Values::Range(
+                bounds.0.min(bounds.1),
+                bounds.0.max(bounds.1),
+            ));
+        }
+        Pool::List(items) => items
+            .iter()
+            .map(|item| match item {
+                serde_yaml::Value::Number(n) => Value::Number(n.as_f64().unwrap_or_default()),
+                serde_yaml::Value::String(s) => Value::Text(s.clone()),
+                other => Value::Text(
+                    serde_yaml::to_string(other)
+                        .unwrap_or_default()
+                        .trim()
+                        .to_string(),
+                ),
+            })
+            .collect(),
+    };
+    if values.is_empty() {
+        return Err(format!("{}: 값이 하나도 없습니다", name));
+    }
+    Ok(Values::List(values))
+}
+
+// 글 안의 `{식}` 을 모두 계산한 값으로 바꿉니다
+fn fill(template: &str, vars: &BTreeMap<&str, Value>) -> Result<String, String> {
+    let mut out = String::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        out += &rest[..start];
+        let end = rest[start..]
+            .find('}')
+            .ok_or_else(|| format!("닫는 }} 가 없습니다: {}", template))?;
+        let expr = &rest[start + 1..start + end];
+        out += &Parser::new(expr, vars).parse()?.render();
+        rest = &rest[start + end + 1..];
+    }
+    out += rest;
+    Ok(out)
+}
+
+// 사칙연산, 나머지, 괄호, 변수만 아는 작은 계산기
+struct Parser<'a> {
+    chars: Vec<char>,
+    pos: usize,
+    vars: &'a BTreeMap<&'a str, Value>,
+    source: &'a str,
+}
+
+impl<'a> Parser<'a> {
+    fn new(source: &'a str, vars: &'a BTreeMap<&'a str, Value>) -> Self {
+        Self {
+            chars: source.chars().collect(),
+            pos: 0,
+            vars,
+            source,
+        }
+    }
+
+    fn parse(mut self) -> Result<Value, String> {
+        let value = self.sum()?;
+        self.skip_spaces();
+        if self.pos < self.chars.len() {
+            return Err(self.error());
+        }
+        Ok(value)
+    }
+
+    fn error(&self) -> String {
+        format!("식을 계산할 수 없습니다: {{{}}}", self.source)
+    }
+
+    fn skip_spaces(&mut self) {
+        while self.chars.get(self.pos).is_some_and(|c| c.is_whitespace()) {
+            self.pos += 1;
+        }
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        self.skip_spaces();
+        self.chars.get(self.pos).copied()
+    }
+
+    fn sum(&mut self) -> Result<Value, String> {
+        let mut value = self.product()?;
+        while let Some(op @ ('+' | '-')) = self.peek() {
+            self.pos += 1;
+            let (a, b) = (value.number()?, self.product()?.number()?);
+            value = Value::Number(if op == '+' { a + b } else { a - b });
+        }
+        Ok(value)
+    }
+
+    fn product(&mut self) -> Result<Value, String> {
+        let mut value = self.unary()?;
+        while let Some(op @ ('*' | '/' | '%')) = self.peek() {
+            self.pos += 1;
+            let (a, b) = (value.number()?, self.unary()?.number()?);
+            if op != '*' && b == 0.0 {
+                return Err(format!("0으로 나눌 수 없습니다: {{{}}}", self.source));
+            }
+            value = Value::Number(match op {
+                '*' => a * b,
+                '/' => a / b,
+                _ => a % b,
+            });
+        }
+        Ok(value)
+    }
+
+    fn unary(&mut self) -> Result<Value, String> {
+        if self.peek() == Some('-') {
+            self.pos += 1;
+            return Ok(Value::Number(-self.unary()?.number()?));
+        }
+        self.atom()
+    }
+
+    fn atom(&mut self) -> Result<Value, String> {
+        match self.peek() {
+            Some('(') => {
+                self.pos += 1;
+                let value = self.sum()?;
+                if self.peek() != Some(')') {
+                    return Err(self.error());
+                }
+                self.pos += 1;
+                Ok(value)
+            }
+            Some(c) if c.is_ascii_digit() || c == '.' => {
+                let start = self.pos;
+                while self
+                    .chars
+                    .get(self.pos)
+                    .is_some_and(|c| c.is_ascii_digit() || *c == '.')
+                {
+                    self.pos += 1;
+                }
+                let number: String = self.chars[start..self.pos].iter().collect();
+                number.parse().map(Value::Number).map_err(|_| self.error())
+            }
+            Some(c) if c.is_alphabetic() || c == '_' => {
+                let start = self.pos;
+                while self
+                    .chars
+                    .get(self.pos)
+                    .is_some_and(|c| c.is_alphanumeric() || *c == '_')
+                {
+                    self.pos += 1;
+                }
+                let name: String = self.chars[start..self.pos].iter().collect();
+                self.vars
+                    .get(name.as_str())
+                    .cloned()
+                    .ok_or_else(|| format!("정의되지 않은 변수입니다: {}", name))
+            }
+            _ => Err(self.error()),
+        }
+    }
+}
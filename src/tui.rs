@@ -0,0 +1,482 @@
+use crate::config::Keys;
+use crate::layout::wrap;
+use crossterm::cursor::{Hide, MoveTo, Show};
+use crossterm::event::{
+    self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEvent, KeyEventKind,
+    KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+};
+use crossterm::style::{Attribute, Print, SetAttribute};
+use crossterm::terminal::{
+    self, disable_raw_mode, enable_raw_mode, Clear, ClearType, EnterAlternateScreen,
+    LeaveAlternateScreen,
+};
+use crossterm::{execute, queue};
+use std::io::{self, IsTerminal, Write};
+use std::time::{Duration, Instant};
+use unicode_width::UnicodeWidthStr;
+
+pub enum Outcome {
+    Chosen(usize),
+    Skip,
+    Reveal,
+    Copy,
+    // 50:50 으로 틀린 보기를 지워 달라는 요청
+    Eliminate,
+    // 시험 모드에서 앞 문제로 돌아가기
+    Back,
+    Quit,
+    Timeout,
+}
+
+pub fn available() -> bool {
+    io::stdin().is_terminal() && io::stdout().is_terminal()
+}
+
+// 화면을 벗어날 때 (패닉이 나더라도) 터미널을 원래대로 돌려놓습니다
+pub struct Screen;
+
+impl Screen {
+    pub fn enter() -> io::Result<Self> {
+        enable_raw_mode()?;
+        execute!(io::stdout(), EnterAlternateScreen, EnableMouseCapture, Hide)?;
+        Ok(Screen)
+    }
+}
+
+impl Drop for Screen {
+    fn drop(&mut self) {
+        execute!(
+            io::stdout(),
+            Show,
+            DisableMouseCapture,
+            LeaveAlternateScreen
+        )
+        .ok();
+        disable_raw_mode().ok();
+    }
+}
+
+enum Mode {
+    Normal,
+    Filter,
+    // 여러 글자인 키(`:skip` 등)를 치는 중
+    Command,
+    ConfirmQuit,
+}
+
+struct Picker<'a> {
+    title: &'a str,
+    options: &'a [String],
+    markers: &'a [String],
+    keys: &'a Keys,
+    visible: Vec<usize>,
+    cursor: usize,
+    offset: usize,
+    filter: String,
+    // 치고 있는 키나 끝내기 확인
+    typed: String,
+    mode: Mode,
+    pending_g: bool,
+    // 마지막으로 그린 화면에서 보기 목록이 시작한 줄, 보이는 줄 수, 버튼 줄의 위치
+    list_top: usize,
+    height: usize,
+    buttons_row: usize,
+}
+
+const SUBMIT: &str = "[ 제출 ]";
+const SKIP: &str = "[ 건너뛰기 ]";
+
+impl Picker<'_> {
+    fn refilter(&mut self) {
+        let filter = self.filter.to_lowercase();
+        self.visible = (0..self.options.len())
+            .filter(|&i| filter.is_empty() || self.options[i].to_lowercase().contains(&filter))
+            .collect();
+        self.cursor = self.cursor.min(self.visible.len().saturating_sub(1));
+    }
+
+    fn move_to(&mut self, cursor: usize) {
+        self.cursor = cursor.min(self.visible.len().saturating_sub(1));
+    }
+
+    fn move_by(&mut self, delta: isize) {
+        self.move_to(self.cursor.saturating_add_signed(delta));
+    }
+
+    fn render(&mut self, flagged: bool, deadline: Option<Instant>) -> io::Result<()> {
+        let (cols, rows) = terminal::size()?;
+        let (cols, rows) = (cols.max(1) as usize, rows as usize);
+        let mut out = io::stdout();
+        queue!(out, Clear(ClearType::All), MoveTo(0, 0))?;
+
+        let mut row = 0;
+        for line in wrap(self.title, cols, 0) {
+            queue!(out, MoveTo(0, row as u16), Print(line))?;
+            row += 1;
+        }
+        row += 1;
+
+        // 잘려서 보이는 보기는 커서를 올리면 목록 아래에 (세 줄까지) 전부 보여줍니다
+        let detail: Vec<String> = match self.visible.get(self.cursor) {
+            Some(&index)
+                if format!("{}{}", self.markers[index], self.options[index]).width()
+                    > cols.saturating_sub(2) =>
+            {
+                let mut lines = wrap(
+                    &format!("{}{}", self.markers[index], self.options[index]),
+                    cols.saturating_sub(2),
+                    self.markers[index].width(),
+                );
+                // 보기 목록이 적어도 한 줄은 보이도록 남는 줄만큼만 씁니다
+                lines.truncate(rows.saturating_sub(row + 4).min(3));
+                lines
+            }
+            _ => Vec::new(),
+        };
+        let footer = 3 + detail.len();
+        let height = rows.saturating_sub(row + footer).max(1);
+        self.list_top = row;
+        self.height = height;
+        if self.cursor < self.offset {
+            self.offset = self.cursor;
+        } else if self.cursor >= self.offset + height {
+            self.offset = self.cursor + 1 - height;
+        }
+
+        for (line, &index) in self
+            .visible
+            .iter()
+            .enumerate()
+            .skip(self.offset)
+            .take(height)
+        {
+            let text = truncate(
+                &format!("{}{}", self.markers[index], self.options[index]),
+                cols.saturating_sub(2),
+            );
+            queue!(out, MoveTo(0, row as u16))?;
+            if line == self.cursor {
+                queue!(
+                    out,
+                    SetAttribute(Attribute::Reverse),
+                    Print(format!("> {}", text)),
+                    SetAttribute(Attribute::Reset)
+                )?;
+            } else {
+                queue!(out, Print(format!("  {}", text)))?;
+            }
+            row += 1;
+        }
+        if self.visible.is_empty() {
+            queue!(
+                out,
+                MoveTo(0, row as u16),
+                Print("  (일치하는 항목이 없습니다)")
+            )?;
+        }
+
+        let status = match self.mode {
+            Mode::Filter => format!("/{}", self.filter),
+            Mode::Command => self.typed.clone(),
+            Mode::ConfirmQuit => format!(
+                "정말 그만둘까요? ({} 입력 시 종료): {}",
+                self.keys.confirm, self.typed
+            ),
+            Mode::Normal => {
+                let mut status = String::new();
+                if !self.filter.is_empty() {
+                    status += &format!("검색: {}  ", self.filter);
+                }
+                if flagged {
+                    status += "[표시됨]  ";
+                }
+                if let Some(deadline) = deadline {
+                    let secs = deadline.saturating_duration_since(Instant::now()).as_secs();
+                    status += &format!("남은 시간 {}:{:02}", secs / 60, secs % 60);
+                }
+                status
+            }
+        };
+        let keys = self.keys;
+        let help = format!(
+            "j/k 이동  gg/G 처음/끝  / 검색  Enter 선택  {} 건너뛰기  {} 표시  {} 정답 보기  {} 복사  {} 50:50  {} 앞 문제  {} 끝내기",
+            keys.skip, keys.flag, keys.reveal, keys.copy, keys.fifty, keys.back, keys.quit
+        );
+        self.buttons_row = rows.saturating_sub(3);
+        for (i, line) in detail.iter().enumerate() {
+            queue!(
+                out,
+                MoveTo(
+                    0,
+                    (self.buttons_row.saturating_sub(detail.len()) + i) as u16
+                ),
+                SetAttribute(Attribute::Dim),
+                Print(format!("  {}", line)),
+                SetAttribute(Attribute::Reset)
+            )?;
+        }
+        queue!(
+            out,
+            MoveTo(0, self.buttons_row as u16),
+            SetAttribute(Attribute::Bold),
+            Print(format!("{}  {}", SUBMIT, SKIP)),
+            SetAttribute(Attribute::Reset),
+            MoveTo(0, rows.saturating_sub(2) as u16),
+            Print(truncate(&status, cols)),
+            MoveTo(0, rows.saturating_sub(1) as u16),
+            SetAttribute(Attribute::Dim),
+            Print(truncate(&help, cols)),
+            SetAttribute(Attribute::Reset)
+        )?;
+        out.flush()
+    }
+
+    fn handle(&mut self, key: KeyEvent, flagged: &mut bool) -> Option<Outcome> {
+        if key.modifiers.contains(KeyModifiers::CONTROL) {
+            match key.code {
+                KeyCode::Char('c') => return Some(Outcome::Quit),
+                KeyCode::Char('d') => self.move_by(5),
+                KeyCode::Char('u') => self.move_by(-5),
+                _ => {}
+            }
+            return None;
+        }
+
+        match self.mode {
+            Mode::Filter => match key.code {
+                KeyCode::Enter => self.mode = Mode::Normal,
+                KeyCode::Esc => {
+                    self.filter.clear();
+                    self.mode = Mode::Normal;
+                    self.refilter();
+                }
+                KeyCode::Backspace => {
+                    self.filter.pop();
+                    self.refilter();
+                }
+                KeyCode::Char(c) => {
+                    self.filter.push(c);
+                    self.refilter();
+                }
+                _ => {}
+            },
+            Mode::Command => match key.code {
+                KeyCode::Esc => self.mode = Mode::Normal,
+                KeyCode::Backspace => {
+                    self.typed.pop();
+                    if self.typed.is_empty() {
+                        self.mode = Mode::Normal;
+                    }
+                }
+                KeyCode::Char(c) => {
+                    self.typed.push(c);
+                    let typed = self.typed.clone();
+                    if let Some(outcome) = self.bound(&typed, flagged) {
+                        return outcome;
+                    }
+                    if !self.continues(&typed) {
+                        self.mode = Mode::Normal;
+                    }
+                }
+                _ => self.mode = Mode::Normal,
+            },
+            Mode::ConfirmQuit => match key.code {
+                KeyCode::Char(c) => {
+                    self.typed.push(c);
+                    if self.keys.confirm.matches(&self.typed) {
+                        return Some(Outcome::Quit);
+                    }
+                    if !self.keys.confirm.continues(&self.typed) {
+                        self.mode = Mode::Normal;
+                    }
+                }
+                _ => self.mode = Mode::Normal,
+            },
+            Mode::Normal => {
+                let pending_g = std::mem::take(&mut self.pending_g);
+                // 설정한 키가 이동 키보다 먼저입니다
+                if let KeyCode::Char(c) = key.code {
+                    let typed = c.to_string();
+                    if let Some(outcome) = self.bound(&typed, flagged) {
+                        return outcome;
+                    }
+                    if self.continues(&typed) {
+                        self.typed = typed;
+                        self.mode = Mode::Command;
+                        return None;
+                    }
+                }
+                match key.code {
+                    KeyCode::Char('j') | KeyCode::Down => self.move_by(1),
+                    KeyCode::Char('k') | KeyCode::Up => self.move_by(-1),
+                    KeyCode::PageDown => self.move_by(10),
+                    KeyCode::PageUp => self.move_by(-10),
+                    KeyCode::Char('g') if pending_g => self.move_to(0),
+                    KeyCode::Char('g') => self.pending_g = true,
+                    KeyCode::Home => self.move_to(0),
+                    KeyCode::Char('G') | KeyCode::End => self.move_to(usize::MAX),
+                    KeyCode::Char('/') => self.mode = Mode::Filter,
+                    KeyCode::Esc => {
+                        self.filter.clear();
+                        self.refilter();
+                    }
+                    KeyCode::Enter => {
+                        if let Some(&index) = self.visible.get(self.cursor) {
+                            return Some(Outcome::Chosen(index));
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+        None
+    }
+
+    // 설정의 `keys` 에 맞는 키를 다 쳤으면 그 동작을 합니다. 맞는 키가 없으면 None
+    fn bound(&mut self, typed: &str, flagged: &mut bool) -> Option<Option<Outcome>> {
+        let keys = self.keys;
+        let outcome = if keys.skip.matches(typed) {
+            Outcome::Skip
+        } else if keys.reveal.matches(typed) {
+            Outcome::Reveal
+        } else if keys.copy.matches(typed) {
+            Outcome::Copy
+        } else if keys.fifty.matches(typed) {
+            Outcome::Eliminate
+        } else if keys.back.matches(typed) {
+            Outcome::Back
+        } else if keys.flag.matches(typed) {
+            *flagged = !*flagged;
+            self.mode = Mode::Normal;
+            return Some(None);
+        } else if keys.quit.matches(typed) {
+            self.typed.clear();
+            self.mode = Mode::ConfirmQuit;
+            return Some(None);
+        } else {
+            return None;
+        };
+        Some(Some(outcome))
+    }
+
+    fn continues(&self, typed: &str) -> bool {
+        let keys = self.keys;
+        [
+            &keys.skip,
+            &keys.reveal,
+            &keys.copy,
+            &keys.fifty,
+            &keys.back,
+            &keys.flag,
+            &keys.quit,
+        ]
+        .iter()
+        .any(|key| key.continues(typed))
+    }
+
+    fn click(&mut self, mouse: MouseEvent) -> Option<Outcome> {
+        let (col, row) = (mouse.column as usize, mouse.row as usize);
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                if row == self.buttons_row {
+                    let submit_end = SUBMIT.width();
+                    let skip_start = submit_end + 2;
+                    if col < submit_end {
+                        return self.visible.get(self.cursor).map(|&i| Outcome::Chosen(i));
+                    } else if (skip_start..skip_start + SKIP.width()).contains(&col) {
+                        return Some(Outcome::Skip);
+                    }
+                } else if (self.list_top..self.list_top + self.height).contains(&row) {
+                    let line = self.offset + row - self.list_top;
+                    if line < self.visible.len() {
+                        self.cursor = line;
+                    }
+                }
+            }
+            MouseEventKind::ScrollDown => self.scroll(3),
+            MouseEventKind::ScrollUp => self.scroll(-3),
+            _ => {}
+        }
+        None
+    }
+
+    // 휠은 커서가 아니라 보이는 영역을 움직이고, 커서는 화면 안에 머물게 합니다
+    fn scroll(&mut self, delta: isize) {
+        let max_offset = self.visible.len().saturating_sub(self.height);
+        self.offset = self.offset.saturating_add_signed(delta).min(max_offset);
+        let last = self.offset + self.height.saturating_sub(1);
+        self.cursor = self.cursor.clamp(self.offset, last.max(self.offset));
+        self.move_to(self.cursor);
+    }
+}
+
+pub fn pick(
+    title: &str,
+    options: &[String],
+    markers: &[String],
+    keys: &Keys,
+    deadline: Option<Instant>,
+    flagged: &mut bool,
+) -> io::Result<Outcome> {
+    crate::input::release();
+    let _screen = Screen::enter()?;
+    let mut picker = Picker {
+        title,
+        options,
+        markers,
+        keys,
+        visible: Vec::new(),
+        cursor: 0,
+        offset: 0,
+        filter: String::new(),
+        typed: String::new(),
+        mode: Mode::Normal,
+        pending_g: false,
+        list_top: 0,
+        height: 1,
+        buttons_row: 0,
+    };
+    picker.refilter();
+
+    loop {
+        picker.render(*flagged, deadline)?;
+        // 남은 시간 표시를 갱신할 수 있도록 일정 간격으로 깨어납니다
+        let mut wait = Duration::from_millis(500);
+        if let Some(deadline) = deadline {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                return Ok(Outcome::Timeout);
+            }
+            wait = wait.min(remaining);
+        }
+        if !event::poll(wait)? {
+            continue;
+        }
+        match event::read()? {
+            Event::Key(key) if key.kind != KeyEventKind::Release => {
+                if let Some(outcome) = picker.handle(key, flagged) {
+                    return Ok(outcome);
+                }
+            }
+            Event::Mouse(mouse) => {
+                if let Some(outcome) = picker.click(mouse) {
+                    return Ok(outcome);
+                }
+            }
+            // 창 크기가 바뀌면 다음 그리기에서 새 크기에 맞춰 다시 나눕니다
+            Event::Resize(..) => {}
+            _ => {}
+        }
+    }
+}
+
+pub fn truncate(text: &str, width: usize) -> String {
+    let mut out = String::new();
+    for c in text.chars() {
+        if format!("{}{}", out, c).width() > width {
+            break;
+        }
+        out.push(c);
+    }
+    out
+}
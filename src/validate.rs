@@ -0,0 +1,436 @@
+use crate::registry;
+use crate::style::styled;
+use ansi_term::Color;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::Duration;
+use terms::{Askable, Chapter, MultipleChoiceQuestion, Questions, Status};
+
+#[derive(clap::Args)]
+pub struct ValidateArgs {
+    /// 검사할 YAML 문제 파일
+    file: PathBuf,
+    /// 끝내지 않고 파일이 저장될 때마다 다시 검사해 달라진 점만 보여줍니다
+    #[arg(long)]
+    watch: bool,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Error,
+    Warning,
+}
+
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Diagnostic {
+    pub level: Level,
+    pub location: String,
+    pub message: String,
+}
+
+impl Diagnostic {
+    fn line(&self) -> String {
+        let level = match self.level {
+            Level::Error => styled(Color::Red).paint("오류"),
+            Level::Warning => styled(Color::Yellow).paint("경고"),
+        };
+        format!("{} {}: {}", level, self.location, self.message)
+    }
+}
+
+pub fn run(args: ValidateArgs) {
+    let mut previous = check(&args.file);
+    for diagnostic in &previous {
+        println!("{}", diagnostic.line());
+    }
+    summary(&previous);
+    if !args.watch {
+        if previous.iter().any(|d| d.level == Level::Error) {
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    println!(
+        "{}",
+        styled(Color::Cyan)
+            .paint("파일이 저장될 때마다 다시 검사합니다. 끝내려면 Ctrl-C 를 누르세요.")
+    );
+    let modified = |path: &PathBuf| fs::metadata(path).and_then(|m| m.modified()).ok();
+    let mut last = modified(&args.file);
+    loop {
+        thread::sleep(Duration::from_millis(300));
+        let now = modified(&args.file);
+        if now == last {
+            continue;
+        }
+        last = now;
+
+        let current = check(&args.file);
+        println!();
+        for fixed in previous.difference(&current) {
+            println!("{} {}", styled(Color::Green).paint("-"), fixed.line());
+        }
+        for found in current.difference(&previous) {
+            println!("{} {}", styled(Color::Red).paint("+"), found.line());
+        }
+        summary(&current);
+        previous = current;
+    }
+}
+
+fn summary(diagnostics: &BTreeSet<Diagnostic>) {
+    let errors = diagnostics
+        .iter()
+        .filter(|d| d.level == Level::Error)
+        .count();
+    let warnings = diagnostics.len() - errors;
+    if errors == 0 {
+        println!(
+            "{} (경고 {}개)",
+            styled(Color::Green.bold()).paint("통과"),
+            warnings
+        );
+    } else {
+        println!(
+            "{} (오류 {}개, 경고 {}개)",
+            styled(Color::Red.bold()).paint("실패"),
+            errors,
+            warnings
+        );
+    }
+}
+
+fn check(path: &PathBuf) -> BTreeSet<Diagnostic> {
+    match fs::read_to_string(path) {
+        Ok(data) => {
+            let mut found = lint(&data);
+            found.extend(missing_audio(path, &data));
+            found
+        }
+        Err(e) => BTreeSet::from([Diagnostic {
+            level: Level::Error,
+            location: path.display().to_string(),
+            message: format!("파일을 읽을 수 없습니다: {}", e),
+        }]),
+    }
+}
+
+// 녹음 파일은 문제 파일이 있는 폴더를 기준으로 찾습니다 (`terms listen`)
+fn missing_audio(path: &Path, data: &str) -> Vec<Diagnostic> {
+    let Ok(questions) = Questions::parse(data) else {
+        return Vec::new();
+    };
+    let base = path.parent().unwrap_or(Path::new(""));
+    let mut found = Vec::new();
+    for chapter in &questions.chapters {
+        let matching = chapter.matching.iter().enumerate().flat_map(|(i, q)| {
+            q.pairs
+                .iter()
+                .filter_map(move |p| Some((("짝짓기", i), p.audio.as_ref()?)))
+        });
+        let spelling = chapter
+            .spelling
+            .iter()
+            .enumerate()
+            .filter_map(|(i, q)| Some((("철자", i), q.audio.as_ref()?)));
+        for ((kind, i), audio) in matching.chain(spelling) {
+            if !base.join(audio).is_file() {
+                found.push(Diagnostic {
+                    level: Level::Warning,
+                    location: format!("챕터 {} {} {}번", chapter.chapter, kind, i + 1),
+                    message: format!("녹음 파일이 없습니다: {}", audio),
+                });
+            }
+        }
+    }
+    found
+}
+
+pub fn lint(data: &str) -> BTreeSet<Diagnostic> {
+    let mut found = BTreeSet::new();
+    let questions = match Questions::parse(data) {
+        Ok(questions) => questions,
+        Err(e) => {
+            found.insert(Diagnostic {
+                level: Level::Error,
+                location: e.location(),
+                message: e.message,
+            });
+            return found;
+        }
+    };
+
+    let mut chapters = HashSet::new();
+    let mut ids: HashMap<String, String> = HashMap::new();
+    for chapter in &questions.chapters {
+        let mut report = |level, location: String, message: String| {
+            found.insert(Diagnostic {
+                level,
+                location,
+                message,
+            });
+        };
+        if !chapters.insert(chapter.chapter) {
+            report(
+                Level::Warning,
+                format!("챕터 {}", chapter.chapter),
+                "같은 챕터 번호가 두 번 나옵니다".to_string(),
+            );
+        }
+        lint_chapter(chapter, &mut report, &mut ids);
+    }
+
+    // 템플릿, 스크립트 문제는 실제로 만들어 봐야 틀린 곳을 알 수 있습니다
+    let known = registry(Some(0));
+    for chapter in questions.chapters {
+        for key in chapter.custom.keys() {
+            if !known.contains(key) {
+                found.insert(Diagnostic {
+                    level: Level::Warning,
+                    location: format!("챕터 {}", chapter.chapter),
+                    message: format!("알 수 없는 키 `{}` 는 무시됩니다", key),
+                });
+            }
+        }
+        let number = chapter.chapter;
+        if let Err(e) = known.build(vec![chapter]) {
+            found.insert(Diagnostic {
+                level: Level::Error,
+                location: format!("챕터 {} {}", number, e.location()),
+                message: e.message,
+            });
+        }
+    }
+    found
+}
+
+fn lint_chapter(
+    chapter: &Chapter,
+    report: &mut impl FnMut(Level, String, String),
+    ids: &mut HashMap<String, String>,
+) {
+    let at =
+        |kind: &str, index: usize| format!("챕터 {} {} {}번", chapter.chapter, kind, index + 1);
+    let mut common = |location: &String,
+                      question: &dyn Askable,
+                      status: Status,
+                      report: &mut dyn FnMut(Level, String, String)| {
+        if question.text().trim().is_empty() {
+            report(
+                Level::Error,
+                location.clone(),
+                "문제가 비어 있습니다".to_string(),
+            );
+        }
+        if !(question.frequency() >= 0.0 && question.frequency().is_finite()) {
+            report(
+                Level::Error,
+                location.clone(),
+                "출제 빈도(weight)는 0 이상이어야 합니다".to_string(),
+            );
+        } else if question.frequency() == 0.0 {
+            report(
+                Level::Warning,
+                location.clone(),
+                "출제 빈도(weight)가 0이라 모든 문제를 풀 때만 나옵니다".to_string(),
+            );
+        }
+        if question.points().is_some_and(|p| p <= 0.0) {
+            report(
+                Level::Warning,
+                location.clone(),
+                "배점이 0점 이하입니다".to_string(),
+            );
+        }
+        if status == Status::Retired {
+            return;
+        }
+        if let Some(id) = question.explicit_id() {
+            if let Some(first) = ids.insert(id.to_string(), location.clone()) {
+                report(
+                    Level::Error,
+                    location.clone(),
+                    format!("id `{}` 는 이미 {}에서 썼습니다", id, first),
+                );
+            }
+        }
+    };
+
+    for (i, q) in chapter.multiple_choice.iter().enumerate() {
+        let location = at("객관식", i);
+        common(&location, q, q.status, report);
+        let count = q.options.len();
+        if !(MultipleChoiceQuestion::MIN_OPTIONS..=MultipleChoiceQuestion::MAX_OPTIONS)
+            .contains(&count)
+        {
+            report(
+                Level::Error,
+                location.clone(),
+                format!(
+                    "보기는 {}~{}개여야 하는데 {}개입니다",
+                    MultipleChoiceQuestion::MIN_OPTIONS,
+                    MultipleChoiceQuestion::MAX_OPTIONS,
+                    count
+                ),
+            );
+        }
+        let letters = q.letters();
+        for answer in q.answer.all() {
+            if count > 0 && !letters.contains(&answer.to_ascii_lowercase()) {
+                report(
+                    Level::Error,
+                    location.clone(),
+                    format!(
+                        "정답 `{}` 는 {}~{} 중 하나여야 합니다",
+                        answer,
+                        letters.start(),
+                        letters.end()
+                    ),
+                );
+            }
+        }
+        for (lang, translation) in &q.translations {
+            let translated = translation.options.as_ref().map_or(count, Vec::len);
+            if translated != count {
+                report(
+                    Level::Error,
+                    location.clone(),
+                    format!(
+                        "`{}` 로 옮긴 보기는 {}개여야 하는데 {}개입니다",
+                        lang, count, translated
+                    ),
+                );
+            }
+        }
+        if q.options.iter().any(|o| o.trim().is_empty()) {
+            report(
+                Level::Error,
+                location.clone(),
+                "빈 보기가 있습니다".to_string(),
+            );
+        }
+        if q.options.iter().collect::<HashSet<_>>().len() < q.options.len() {
+            report(
+                Level::Warning,
+                location,
+                "같은 보기가 두 번 나옵니다".to_string(),
+            );
+        }
+    }
+
+    for (i, q) in chapter.matching.iter().enumerate() {
+        let location = at("짝짓기", i);
+        if q.question.trim().is_empty() {
+            report(
+                Level::Error,
+                location.clone(),
+                "문제가 비어 있습니다".to_string(),
+            );
+        }
+        if q.pairs.len() < 2 {
+            report(
+                Level::Error,
+                location.clone(),
+                "짝이 두 개 이상 있어야 합니다".to_string(),
+            );
+        }
+        for (j, pair) in q.pairs.iter().enumerate() {
+            let empty = match (
+                pair.term.trim().is_empty(),
+                pair.definition.trim().is_empty(),
+            ) {
+                (true, _) => "용어",
+                (false, true) => "정의",
+                (false, false) => continue,
+            };
+            report(
+                Level::Error,
+                location.clone(),
+                format!("{}번째 짝의 {}가 비어 있습니다", j + 1, empty),
+            );
+        }
+        let terms: HashSet<_> = q.pairs.iter().map(|p| &p.term).collect();
+        if terms.len() < q.pairs.len() {
+            report(
+                Level::Error,
+                location.clone(),
+                "같은 용어가 두 번 나옵니다".to_string(),
+            );
+        }
+        for (lang, translation) in &q.translations {
+            let translated = translation.pairs.as_ref().map_or(q.pairs.len(), Vec::len);
+            if translated != q.pairs.len() {
+                report(
+                    Level::Error,
+                    location.clone(),
+                    format!(
+                        "`{}` 로 옮긴 짝은 {}개여야 하는데 {}개입니다",
+                        lang,
+                        q.pairs.len(),
+                        translated
+                    ),
+                );
+            }
+        }
+        let definitions: HashSet<_> = q.pairs.iter().map(|p| &p.definition).collect();
+        if definitions.len() < q.pairs.len() {
+            report(
+                Level::Warning,
+                location,
+                "같은 정의가 두 번 나와서 어느 쪽을 골라도 정답이 됩니다".to_string(),
+            );
+        }
+    }
+
+    for (i, q) in chapter.fill_in_the_blanks.iter().enumerate() {
+        let location = at("빈칸", i);
+        common(&location, q, q.status, report);
+        if q.answer.all().iter().all(|a| a.trim().is_empty()) {
+            report(
+                Level::Error,
+                location.clone(),
+                "정답이 비어 있습니다".to_string(),
+            );
+        }
+        let blanks = q.blank_count();
+        let answers = q.answer.primary().split(',').count();
+        if blanks == 0 {
+            report(Level::Warning, location, "빈칸(___)이 없습니다".to_string());
+        } else if blanks > 1 && answers != blanks {
+            report(
+                Level::Warning,
+                location,
+                format!("빈칸은 {}개인데 정답은 {}개입니다", blanks, answers),
+            );
+        }
+    }
+
+    for (i, q) in chapter.spelling.iter().enumerate() {
+        let location = at("철자", i);
+        common(&location, q, q.status, report);
+        let count = q.options.len();
+        for (lang, translation) in &q.translations {
+            let translated = translation.options.as_ref().map_or(count, Vec::len);
+            if translated != count {
+                report(
+                    Level::Error,
+                    location.clone(),
+                    format!(
+                        "`{}` 로 옮긴 보기는 {}개여야 하는데 {}개입니다",
+                        lang, count, translated
+                    ),
+                );
+            }
+        }
+        if !q.options.is_empty() && !q.answer.all().iter().any(|a| q.options.contains(a)) {
+            report(
+                Level::Error,
+                location,
+                format!("정답 `{}` 가 보기에 없습니다", q.answer.primary()),
+            );
+        }
+    }
+}
@@ -0,0 +1,243 @@
+use crate::stats::pad;
+use crate::style::{self, styled};
+use crate::{config, deal, format_duration, get_user_input, print_result, Prompter, QuizArgs};
+use ansi_term::Color;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use std::io::{self, IsTerminal};
+use std::time::{Duration, Instant};
+use terms::score::Score;
+use terms::{weight, Askable};
+
+// `--count` 를 주지 않았을 때 한 판에 낼 문제 수
+const DEFAULT_COUNT: usize = 10;
+
+#[derive(Default)]
+struct Player {
+    name: String,
+    points: Score,
+    correct: usize,
+    asked: usize,
+    // 맞힌 문제에 답하는 데 걸린 시간. 점수가 같으면 덜 걸린 사람이 이깁니다
+    time: Duration,
+    // `--race` 에서 가장 먼저(빨리) 맞힌 문제 수
+    firsts: usize,
+}
+
+// 한 터미널에서 두 사람이 번갈아 문제를 풀거나(`--race` 면 같은 문제를 차례로 따로 풀어) 겨룹니다
+pub fn run(args: &QuizArgs) {
+    if args.versus.len() < 2 {
+        println!("`--versus 이름1,이름2` 처럼 두 사람 이상의 이름을 적으세요.");
+        return;
+    }
+    let config = config::Config::load();
+    let mut players: Vec<Player> = args
+        .versus
+        .iter()
+        .map(|name| Player {
+            name: name.clone(),
+            ..Default::default()
+        })
+        .collect();
+    let (mut questions, bank_points) = deal(args);
+    questions.truncate(args.count.unwrap_or(DEFAULT_COUNT));
+    if !args.race {
+        // 모두 같은 수의 문제를 풀도록 남는 문제는 버립니다
+        questions.truncate(questions.len() - questions.len() % players.len());
+    }
+    if questions.is_empty() {
+        println!("낼 문제가 없습니다.");
+        return;
+    }
+
+    let romanized = args.romanized.or(config.romanized);
+    let mut phrasing = match args.seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+    };
+    let names: Vec<&str> = players.iter().map(|p| p.name.as_str()).collect();
+    println!(
+        "{} 대결을 시작합니다. 문제 {}개{}",
+        styled(Color::Yellow.bold()).paint(names.join(" 대 ")),
+        questions.len(),
+        if args.race {
+            ", 모두 같은 문제를 풉니다."
+        } else {
+            "를 번갈아 풉니다."
+        }
+    );
+    'game: for (round, question) in questions.iter().enumerate() {
+        // 물을 때마다 새로 만드는 문제도 이번 판에서는 모두 같은 문제를 풉니다
+        let generated = question
+            .0
+            .generate()
+            .map(|g| (Box::new(g) as Box<dyn Askable>, question.1));
+        let question = generated.as_ref().unwrap_or(question);
+        let weight = weight(question.0.as_ref(), &bank_points, &config.points);
+        let turn: Vec<usize> = if args.race {
+            (0..players.len()).collect()
+        } else {
+            vec![round % players.len()]
+        };
+        let mut results = Vec::new();
+        for &i in &turn {
+            if args.race {
+                hand_over(&players[i].name);
+            }
+            println!(
+                "{} {} {}",
+                styled(Color::Yellow.bold()).paint(format!("[{}/{}]", round + 1, questions.len())),
+                styled(Color::Cyan.bold()).paint(&players[i].name),
+                styled(Color::Yellow).paint(format!("챕터 {}", question.1))
+            );
+            // 같은 문제를 여럿이 풀 때는 다 풀 때까지 정답을 보여주지 않습니다
+            let mut prompter = Prompter::new(args.race, args.tui, None, config.keys.clone());
+            prompter.typo = config.typo;
+            prompter.romanized = romanized;
+            prompter.converting = romanized.is_some();
+            prompter.reference = question.0.reference();
+            prompter.explanation = question.0.explanation().map(str::to_string);
+            prompter.retries = if args.race {
+                0
+            } else {
+                args.retries.or(config.retries).unwrap_or(0)
+            };
+            let asked_at = Instant::now();
+            let result = question
+                .0
+                .ask(&mut prompter, &mut phrasing)
+                .weighted(weight);
+            let elapsed = asked_at.elapsed();
+            if prompter.quit {
+                break 'game;
+            }
+            // 대결은 학습 기록에 남기지 않으므로 뺀 문제는 건너뛴 것으로 칩니다
+            let result = if prompter.suspended {
+                Score::skipped().weighted(weight)
+            } else {
+                result
+            };
+            results.push((i, result, elapsed, prompter.typed.take()));
+        }
+
+        if args.race {
+            hand_over("모두");
+            println!(
+                "{} {}",
+                styled(Color::Yellow).paint("문제:"),
+                question.0.text()
+            );
+        }
+        let first = results
+            .iter()
+            .filter(|(_, result, _, _)| result.is_correct())
+            .min_by_key(|(_, _, elapsed, _)| *elapsed)
+            .map(|(i, _, _, _)| *i);
+        for (i, result, elapsed, typed) in results {
+            let player = &mut players[i];
+            player.asked += 1;
+            player.points += result;
+            if result.is_correct() {
+                player.correct += 1;
+                player.time += elapsed;
+            }
+            if args.race {
+                println!(
+                    "{} ({:.1}초)",
+                    styled(Color::Cyan.bold()).paint(&player.name),
+                    elapsed.as_secs_f64()
+                );
+                print_result(result, &question.0.correct_answer(), typed.as_deref(), None);
+            }
+        }
+        if let Some(i) = first.filter(|_| args.race && players.len() > 1) {
+            players[i].firsts += 1;
+            println!(
+                "{}",
+                styled(Color::Green)
+                    .paint(format!("{} 이(가) 가장 빨리 맞혔습니다.", players[i].name))
+            );
+        }
+        println!("{}\n", scoreline(&players));
+    }
+
+    summary(&players, args.race);
+}
+
+// 다음 사람이 앞사람의 답을 보지 않도록 화면을 지우고 기다립니다
+fn hand_over(name: &str) {
+    if !io::stdout().is_terminal() {
+        return;
+    }
+    get_user_input(&format!(
+        "{} 차례입니다. 준비되면 Enter 를 누르세요.",
+        styled(Color::Cyan.bold()).paint(name)
+    ));
+    crossterm::execute!(
+        io::stdout(),
+        crossterm::terminal::Clear(crossterm::terminal::ClearType::All),
+        crossterm::cursor::MoveTo(0, 0)
+    )
+    .ok();
+}
+
+fn scoreline(players: &[Player]) -> String {
+    players
+        .iter()
+        .map(|p| format!("{} {}", p.name, p.points))
+        .collect::<Vec<_>>()
+        .join(" : ")
+}
+
+fn summary(players: &[Player], race: bool) {
+    println!("{}", styled(Color::Yellow.bold()).paint("대결 결과"));
+    let mut header = format!(
+        "{}{}{}{}",
+        pad("이름", 12),
+        pad("맞힘", 10),
+        pad("점수", 10),
+        pad("맞힌 문제 평균", 16)
+    );
+    if race {
+        header += "먼저 맞힘";
+    }
+    println!("{}", header);
+    for player in players {
+        let average = match player.correct {
+            0 => "-".to_string(),
+            correct => format_duration(player.time / correct as u32),
+        };
+        let mut line = format!(
+            "{}{}{}{}",
+            pad(&player.name, 12),
+            pad(&format!("{}/{}", player.correct, player.asked), 10),
+            pad(&player.points.to_string(), 10),
+            pad(&average, 16)
+        );
+        if race {
+            line += &player.firsts.to_string();
+        }
+        println!("{}", line);
+    }
+
+    // 점수가 같으면 맞힌 문제에 덜 걸린 사람이 앞섭니다
+    let mut ranked: Vec<&Player> = players.iter().collect();
+    ranked.sort_by(|a, b| {
+        b.points
+            .percent()
+            .total_cmp(&a.points.percent())
+            .then(a.time.cmp(&b.time))
+    });
+    let (winner, runner_up) = (ranked[0], ranked[1]);
+    if winner.points.percent() == runner_up.points.percent() && winner.time == runner_up.time {
+        println!(
+            "\n{}",
+            style::emphasis(Color::Yellow).paint("무승부입니다!")
+        );
+    } else {
+        println!(
+            "\n{}",
+            style::emphasis(Color::Green).paint(format!("{} 승리!", winner.name))
+        );
+    }
+}
@@ -0,0 +1,96 @@
+use crate::style::styled;
+use crate::suspend::Suspended;
+use crate::{history, prepare, registry, QuizArgs};
+use ansi_term::Color;
+use std::collections::{BTreeMap, HashSet, VecDeque};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use terms::{Askable, Bank};
+
+// 문제를 낼 때마다 문제 파일이 바뀌었는지 보고, 바뀌었으면 남은 문제에 반영합니다
+pub struct Watcher {
+    path: PathBuf,
+    modified: Option<SystemTime>,
+}
+
+impl Watcher {
+    pub fn new(path: PathBuf) -> Self {
+        let modified = modified(&path);
+        Self { path, modified }
+    }
+
+    pub fn poll(
+        &mut self,
+        queue: &mut VecDeque<(Box<dyn Askable>, u32)>,
+        answers: &[history::Answer],
+        suspended: &BTreeMap<String, Suspended>,
+        selected: &HashSet<u32>,
+        args: &QuizArgs,
+    ) {
+        let modified = modified(&self.path);
+        if modified == self.modified {
+            return;
+        }
+        self.modified = modified;
+
+        let fresh = fs::read_to_string(&self.path)
+            .map_err(|e| e.to_string())
+            .and_then(|data| Bank::parse(data).map_err(|e| e.to_string()))
+            .and_then(|bank| {
+                let chapters = bank
+                    .load(|c| selected.contains(&c))
+                    .map_err(|e| e.to_string())?;
+                let chapters = prepare(chapters, bank.normalize.as_ref(), args);
+                registry(args.seed)
+                    .build(chapters)
+                    .map_err(|e| e.to_string())
+            });
+        let fresh = match fresh {
+            Ok(fresh) => fresh,
+            Err(e) => {
+                println!(
+                    "{} {}\n",
+                    styled(Color::Red)
+                        .paint("바뀐 문제 파일을 읽을 수 없어 전의 문제로 계속합니다:"),
+                    e
+                );
+                return;
+            }
+        };
+
+        // 이미 푼 문제는 다시 내지 않고, 남은 문제는 고친 내용으로 바꾸고, 새 문제는 뒤에 붙입니다
+        let asked: HashSet<String> = answers.iter().map(|a| a.id.clone()).collect();
+        let waiting: Vec<String> = queue.iter().map(|(q, _)| q.id()).collect();
+        let before = queue.len();
+        let (mut updated, mut added) = (0, 0);
+        let mut replaced: Vec<Option<(Box<dyn Askable>, u32)>> =
+            waiting.iter().map(|_| None).collect();
+        for (question, chapter) in fresh {
+            let id = question.id();
+            // 템플릿 문제처럼 같은 id 가 여럿이면 아직 채우지 않은 자리부터 채웁니다
+            let slot = (0..waiting.len()).find(|&i| waiting[i] == id && replaced[i].is_none());
+            if let Some(index) = slot {
+                replaced[index] = Some((question, chapter));
+                updated += 1;
+            } else if !asked.contains(&id) && !suspended.contains_key(&id) {
+                replaced.push(Some((question, chapter)));
+                added += 1;
+            }
+        }
+        *queue = replaced.into_iter().flatten().collect();
+        let removed = before - updated;
+
+        println!(
+            "{}\n",
+            styled(Color::Cyan).paint(format!(
+                "문제 파일이 바뀌었습니다: 새 문제 {}개, 남은 문제 {}개 다시 읽음, 빠진 문제 {}개",
+                added, updated, removed
+            ))
+        );
+    }
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
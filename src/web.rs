@@ -0,0 +1,740 @@
+use crate::classroom::{Assignment, Gradebook, Prompt, Report};
+use crate::style::styled;
+use crate::{build_questions, config, history, load_bank, ChapterSet, QuizArgs, Source};
+use ansi_term::Color;
+use rand::rngs::StdRng;
+use rand::{thread_rng, Rng, SeedableRng};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fs;
+use std::io::{self, Cursor};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use terms::score::Score;
+use terms::{weight, weighted_shuffle, Askable, GeneratedQuestion};
+use tiny_http::{Header, Method, Request, Response, Server};
+
+#[derive(clap::Args)]
+pub struct ServeArgs {
+    /// 접속을 받을 주소
+    #[arg(long, value_name = "ADDR", default_value = "127.0.0.1:8080")]
+    bind: String,
+    /// 수업용: 같은 시드의 시험을 나눠 주고 `quiz --submit-to` 로 제출된 답안을 성적부로 모읍니다
+    #[arg(long)]
+    collect: bool,
+    /// 결과 수집 모드의 성적부 CSV 파일 (같은 이름의 .json 에 답안 전체를 저장합니다)
+    #[arg(
+        long,
+        value_name = "FILE",
+        default_value = "gradebook.csv",
+        requires = "collect"
+    )]
+    gradebook: PathBuf,
+    /// 이 폴더의 YAML 문제 파일도 함께 제공합니다 (API 에서 파일 이름으로 고릅니다)
+    #[arg(long, value_name = "DIR")]
+    banks: Option<PathBuf>,
+    #[command(flatten)]
+    quiz: QuizArgs,
+}
+
+// 브라우저마다 하나씩 만들어지는 풀이 세션
+struct Session {
+    bank: usize,
+    order: Vec<usize>,
+    position: usize,
+    // 지금 문제를 낼 때 고른 문장. 다시 불러도 같은 문장을 보여줍니다
+    prompt: String,
+    // 지금 문제가 물을 때마다 새로 만드는 문제면 이번에 만든 것
+    generated: Option<GeneratedQuestion>,
+    correct: usize,
+    points: Score,
+    answers: Vec<history::Answer>,
+    started_at: chrono::DateTime<chrono::Local>,
+    start: Instant,
+    asked_at: Instant,
+}
+
+// 끝난 세션은 결과를 볼 수 있도록 잠시, 풀다 만 세션은 오래 두었다가 지웁니다.
+// 그래도 너무 많으면 가장 오래 쓰지 않은 세션부터 지웁니다
+const FINISHED_TTL: Duration = Duration::from_secs(30 * 60);
+const IDLE_TTL: Duration = Duration::from_secs(6 * 60 * 60);
+const MAX_SESSIONS: usize = 1000;
+
+#[derive(Deserialize)]
+struct NewSession {
+    bank: Option<String>,
+    count: Option<usize>,
+    chapters: Option<Vec<u32>>,
+}
+
+#[derive(Deserialize, Default)]
+pub struct Submission {
+    pub answer: Option<String>,
+    pub choice: Option<usize>,
+}
+
+impl Submission {
+    // 보기 번호로 답했으면 터미널에서 답했을 때와 같은 형태로 바꿔 채점합니다
+    pub fn resolve(self, question: &dyn Askable) -> Option<String> {
+        match (self.choice, question.choices()) {
+            (Some(choice), Some((options, marker))) if choice < options.len() => {
+                Some(marker.answer(options, choice))
+            }
+            _ => self.answer.filter(|a| !a.trim().is_empty()),
+        }
+    }
+}
+
+struct Bank {
+    name: String,
+    points: BTreeMap<terms::Kind, f64>,
+    questions: Vec<(Box<dyn Askable>, u32)>,
+}
+
+// 결과 수집 모드에서 나눠 준 시험과, 그 문제들이 기본 문제 파일의 몇 번째 문제인지
+struct Exam {
+    assignment: Assignment,
+    order: Vec<usize>,
+    // 물을 때마다 새로 만드는 문제는 모든 학생이 같은 문제를 풀도록 시험을 만들 때 한 번 만듭니다
+    generated: Vec<Option<GeneratedQuestion>>,
+    negative_marking: Option<f64>,
+    // 학생마다 처음 시험을 받아 간 때. 제한 시간은 학생이 보낸 시각이 아니라 이것으로 잽니다
+    started: HashMap<String, (chrono::DateTime<chrono::Local>, Instant)>,
+}
+
+// 시간이 다 되어 자동으로 내는 답안은 오는 데 시간이 걸릴 수 있어 조금 더 받아 줍니다
+const SUBMIT_GRACE: Duration = Duration::from_secs(60);
+
+#[derive(Deserialize)]
+struct ExamStart {
+    student: String,
+}
+
+struct App {
+    config: config::Config,
+    // 첫 번째가 기본 문제 파일입니다
+    banks: Vec<Bank>,
+    sessions: HashMap<String, Session>,
+    exam: Option<Exam>,
+    gradebook: Option<Gradebook>,
+}
+
+pub fn run(args: ServeArgs) {
+    let config = config::Config::load();
+    let source = Source::open(&args.quiz);
+    let bank_points = source.points();
+    let selected: HashSet<u32> = match &args.quiz.chapters {
+        Some(ChapterSet(chapters)) => chapters.iter().copied().collect(),
+        None => source.chapters().into_iter().collect(),
+    };
+    let chapters = source.load(&selected, &args.quiz);
+
+    let mut gradebook = None;
+    let seed = args.quiz.seed.unwrap_or_else(|| thread_rng().gen());
+    if args.collect {
+        match Gradebook::open(args.gradebook.clone()) {
+            Ok(book) => gradebook = Some(book),
+            Err(e) => {
+                println!(
+                    "{} {}",
+                    styled(Color::Red).paint("성적부를 읽을 수 없습니다:"),
+                    e
+                );
+                return;
+            }
+        }
+        println!(
+            "결과 수집 모드입니다. 학생은 `terms quiz --submit-to http://{}` 로 시험을 봅니다 (시드 {}).",
+            args.bind, seed
+        );
+    }
+    let mut banks = vec![Bank {
+        name: args.quiz.bank_name(),
+        points: bank_points,
+        questions: build_questions(chapters, args.quiz.seed, args.quiz.recall),
+    }];
+    if let Some(dir) = &args.banks {
+        match load_banks(dir, &args.quiz) {
+            Ok(more) => banks.extend(more),
+            Err(e) => {
+                println!(
+                    "{} {}",
+                    styled(Color::Red).paint("문제 폴더를 읽을 수 없습니다:"),
+                    e
+                );
+                return;
+            }
+        }
+    }
+    if banks.iter().all(|b| b.questions.is_empty()) {
+        println!("풀 문제가 없습니다.");
+        return;
+    }
+    let exam = args.collect.then(|| {
+        exam(
+            &banks[0],
+            seed,
+            &args.quiz,
+            args.quiz.negative_marking.or(config.negative_marking),
+        )
+    });
+
+    let server = match Server::http(&args.bind) {
+        Ok(server) => server,
+        Err(e) => {
+            println!(
+                "{} {}",
+                styled(Color::Red).paint("서버를 열 수 없습니다:"),
+                e
+            );
+            return;
+        }
+    };
+    for bank in &banks {
+        println!("{}: 문제 {}개", bank.name, bank.questions.len());
+    }
+    println!(
+        "http://{} 에서 제공합니다. 끝내려면 Ctrl-C 를 누르세요.",
+        args.bind
+    );
+
+    let mut app = App {
+        config,
+        banks,
+        sessions: HashMap::new(),
+        exam,
+        gradebook,
+    };
+    for mut request in server.incoming_requests() {
+        let response = app.handle(&mut request);
+        request.respond(response).ok();
+    }
+}
+
+// 모든 학생이 같은 차례의 같은 문제를 풀도록 시드로 한 번만 고르고, 학생에게는 문제 글과 보기만 보냅니다
+fn exam(bank: &Bank, seed: u64, args: &QuizArgs, negative_marking: Option<f64>) -> Exam {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let questions = &bank.questions;
+    let mut order: Vec<usize> = (0..questions.len()).collect();
+    weighted_shuffle(&mut order, |&i| questions[i].0.frequency(), &mut rng);
+    order.truncate(args.count.unwrap_or(order.len()));
+    let generated: Vec<_> = order.iter().map(|&i| questions[i].0.generate()).collect();
+    let prompts: Vec<Prompt> = order
+        .iter()
+        .zip(&generated)
+        .map(|(&i, generated)| {
+            let question = asked(questions[i].0.as_ref(), generated);
+            Prompt::new(question, questions[i].1, &mut rng)
+        })
+        .collect();
+    Exam {
+        assignment: Assignment {
+            bank: bank.name.clone(),
+            #[cfg(feature = "sign")]
+            signature: crate::sign::sign_text(&serde_json::to_string(&prompts).unwrap()),
+            questions: prompts,
+            time_limit_secs: args.time_limit.map(|limit| limit.as_secs()),
+        },
+        order,
+        generated,
+        negative_marking,
+        started: HashMap::new(),
+    }
+}
+
+// 물을 때 새로 만든 문제가 있으면 그것, 없으면 문제 파일의 문제
+pub fn asked<'a>(
+    question: &'a dyn Askable,
+    generated: &'a Option<GeneratedQuestion>,
+) -> &'a dyn Askable {
+    match generated {
+        Some(generated) => generated,
+        None => question,
+    }
+}
+
+// 파일 이름 순으로 읽습니다
+fn load_banks(dir: &Path, args: &QuizArgs) -> io::Result<Vec<Bank>> {
+    let mut paths: Vec<PathBuf> = fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| {
+            matches!(
+                path.extension().and_then(|e| e.to_str()),
+                Some("yaml" | "yml")
+            )
+        })
+        .collect();
+    paths.sort();
+
+    let mut banks = Vec::new();
+    for path in paths {
+        let source = Source::Yaml {
+            bank: load_bank(Some(&path)),
+            file: path.display().to_string(),
+        };
+        let chapters: HashSet<u32> = source.chapters().into_iter().collect();
+        banks.push(Bank {
+            name: path.file_name().unwrap().to_string_lossy().into_owned(),
+            points: source.points(),
+            questions: build_questions(source.load(&chapters, args), args.seed, args.recall),
+        });
+    }
+    Ok(banks)
+}
+
+pub type Reply = Response<Cursor<Vec<u8>>>;
+
+impl App {
+    fn handle(&mut self, request: &mut Request) -> Reply {
+        let mut body = String::new();
+        if request.as_reader().read_to_string(&mut body).is_err() {
+            return error(400, "요청을 읽을 수 없습니다");
+        }
+        let path: Vec<&str> = request
+            .url()
+            .split('?')
+            .next()
+            .unwrap_or("")
+            .split('/')
+            .filter(|s| !s.is_empty())
+            .collect();
+
+        match (request.method(), path.as_slice()) {
+            (Method::Get, []) => Response::from_string(include_str!("web.html"))
+                .with_header(header("text/html; charset=utf-8")),
+            // 다른 주소에서 열린 페이지도 API 를 쓸 수 있도록 사전 요청에 답합니다
+            (Method::Options, _) => Response::from_string("")
+                .with_status_code(204)
+                .with_header(
+                    Header::from_bytes("Access-Control-Allow-Methods", "GET, POST").unwrap(),
+                )
+                .with_header(
+                    Header::from_bytes("Access-Control-Allow-Headers", "Content-Type").unwrap(),
+                )
+                .with_header(cors()),
+            (Method::Get, ["api", "banks"]) => reply(200, self.banks()),
+            (Method::Post, ["api", "sessions"]) => match serde_json::from_str(&body) {
+                Ok(new) => self.create(new),
+                Err(e) => error(400, &e.to_string()),
+            },
+            (Method::Get, ["api", "sessions", token]) => match self.sessions.get(*token) {
+                Some(session) => reply(200, self.current(session)),
+                None => error(404, "세션을 찾을 수 없습니다"),
+            },
+            (Method::Get, ["api", "sessions", token, "report"]) => {
+                match self.sessions.get(*token) {
+                    Some(session) => reply(200, self.report(session)),
+                    None => error(404, "세션을 찾을 수 없습니다"),
+                }
+            }
+            (Method::Post, ["api", "sessions", token, "answer"]) => {
+                match serde_json::from_str(&body) {
+                    Ok(submission) => self.submit(token, submission),
+                    Err(e) => error(400, &e.to_string()),
+                }
+            }
+            (Method::Post, ["api", "exam"]) => match serde_json::from_str(&body) {
+                Ok(start) => self.start(start),
+                Err(e) => error(400, &e.to_string()),
+            },
+            (Method::Get, ["api", "leaderboard"]) => match &self.gradebook {
+                Some(gradebook) => reply(200, serde_json::to_value(gradebook.standings()).unwrap()),
+                None => error(404, "결과 수집 모드가 아닙니다"),
+            },
+            (Method::Post, ["api", "reports"]) => match serde_json::from_str(&body) {
+                Ok(report) => self.collect(report),
+                Err(e) => error(400, &e.to_string()),
+            },
+            _ => error(404, "없는 주소입니다"),
+        }
+    }
+
+    // 처음 받아 가는 학생은 지금부터 시간을 재고, 다시 받아 가면 남은 시간만 알려 줍니다
+    fn start(&mut self, start: ExamStart) -> Reply {
+        let student = start.student.trim().to_string();
+        if student.is_empty() {
+            return error(400, "이름을 입력하세요");
+        }
+        if self.submitted(&student) {
+            return error(409, "이미 답안을 제출한 이름입니다");
+        }
+        let Some(exam) = &mut self.exam else {
+            return error(404, "결과 수집 모드가 아닙니다");
+        };
+        let (_, started) = *exam
+            .started
+            .entry(student)
+            .or_insert_with(|| (chrono::Local::now(), Instant::now()));
+        let mut assignment = serde_json::to_value(&exam.assignment).unwrap();
+        if let Some(limit) = exam.assignment.time_limit_secs {
+            let elapsed = started.elapsed().as_secs();
+            if elapsed >= limit {
+                return error(403, "제한 시간이 지났습니다");
+            }
+            assignment["time_limit_secs"] = (limit - elapsed).into();
+        }
+        reply(200, assignment)
+    }
+
+    fn submitted(&self, student: &str) -> bool {
+        self.gradebook
+            .as_ref()
+            .is_some_and(|gradebook| gradebook.has(student))
+    }
+
+    fn collect(&mut self, mut report: Report) -> Reply {
+        report.student = report.student.trim().to_string();
+        if self.submitted(&report.student) {
+            return error(409, "이미 답안을 제출한 이름입니다");
+        }
+        let Some(exam) = &self.exam else {
+            return error(404, "결과 수집 모드가 아닙니다");
+        };
+        let Some(&(started_at, started)) = exam.started.get(&report.student) else {
+            return error(403, "시험을 받아 가지 않은 이름입니다");
+        };
+        let elapsed = started.elapsed();
+        if let Some(limit) = exam.assignment.time_limit_secs {
+            if elapsed > Duration::from_secs(limit) + SUBMIT_GRACE {
+                return error(403, "제한 시간이 지나 제출할 수 없습니다");
+            }
+        }
+        report.session.started_at = started_at;
+        report.session.duration_secs = elapsed.as_secs_f64();
+        report.session.answers = self.regrade(exam, &report.session.answers);
+        report.session.bank = exam.assignment.bank.clone();
+        let points = report.points();
+        let grading = &self.config.grading;
+        let graded = json!({
+            "points": points,
+            "grade": grading.grade(&points),
+            "passed": grading.passed(&points),
+            "session": report.session,
+        });
+        let Some(gradebook) = &mut self.gradebook else {
+            return error(404, "결과 수집 모드가 아닙니다");
+        };
+        let student = report.student.clone();
+        match gradebook.add(report, grading) {
+            Ok(points) => {
+                println!(
+                    "{} 님이 제출했습니다: {} ({})",
+                    styled(Color::Cyan).paint(&student),
+                    points,
+                    gradebook.path().display()
+                );
+                reply(201, graded)
+            }
+            Err(e) => {
+                println!(
+                    "{} {}",
+                    styled(Color::Red).paint("성적부를 저장할 수 없습니다:"),
+                    e
+                );
+                error(500, &e.to_string())
+            }
+        }
+    }
+
+    // 학생이 보낸 점수와 정답은 버리고 낸 답만 가져와, 나눠 준 차례대로 서버의 문제로 다시 채점합니다.
+    // 틀로 만든 문제는 id 가 같을 수 있어 몇 번째 답인지로 짝을 짓고, id 가 다르면 보내지 않은 것으로 봅니다.
+    // 답하지 않았거나 보내지 않은 문제는 건너뛴 것으로 칩니다
+    fn regrade(&self, exam: &Exam, submitted: &[history::Answer]) -> Vec<history::Answer> {
+        let bank = &self.banks[0];
+        exam.order
+            .iter()
+            .enumerate()
+            .map(|(slot, &index)| {
+                let (question, chapter) = &bank.questions[index];
+                let question = asked(question.as_ref(), &exam.generated[slot]);
+                let id = question.id();
+                let submitted = submitted.get(slot).filter(|answer| answer.id == id);
+                let given: Vec<String> = submitted
+                    .and_then(|answer| answer.given.last())
+                    .filter(|answer| !answer.trim().is_empty())
+                    .cloned()
+                    .into_iter()
+                    .collect();
+                let weight = weight(question, &bank.points, &self.config.points);
+                let mut result = match given.last() {
+                    Some(answer) => question.grade(answer.trim()),
+                    None => Score::skipped(),
+                }
+                .weighted(weight);
+                if let Some(fraction) = exam.negative_marking {
+                    result = result.penalized(fraction);
+                }
+                history::Answer {
+                    text: question.text().to_string(),
+                    chapter: *chapter,
+                    kind: question.kind(),
+                    group: question.group().map(str::to_string),
+                    correct: result.is_correct(),
+                    score: Some(result),
+                    time_ms: submitted.map_or(0, |answer| answer.time_ms),
+                    lifeline: false,
+                    options: history::options(question),
+                    given: history::given(question, &given),
+                    expected: question.correct_answer(),
+                    id,
+                }
+            })
+            .collect()
+    }
+
+    fn banks(&self) -> Value {
+        let banks: Vec<Value> = self
+            .banks
+            .iter()
+            .map(|bank| {
+                let chapters: BTreeSet<u32> = bank.questions.iter().map(|(_, c)| *c).collect();
+                let mut kinds: BTreeMap<&str, usize> = BTreeMap::new();
+                for (question, _) in &bank.questions {
+                    *kinds.entry(question.kind().code()).or_default() += 1;
+                }
+                json!({
+                    "name": bank.name,
+                    "questions": bank.questions.len(),
+                    "chapters": chapters,
+                    "kinds": kinds,
+                })
+            })
+            .collect();
+        Value::Array(banks)
+    }
+
+    fn create(&mut self, new: NewSession) -> Reply {
+        let bank = match &new.bank {
+            Some(name) => match self.banks.iter().position(|b| &b.name == name) {
+                Some(bank) => bank,
+                None => return error(404, "문제 파일을 찾을 수 없습니다"),
+            },
+            None => 0,
+        };
+        // 연습 세션은 답마다 정답을 돌려주므로, 시험 중에는 시험 문제 파일로 세션을 열지 않습니다
+        if bank == 0 && self.exam.is_some() {
+            return error(
+                403,
+                "결과 수집 모드에서는 시험 문제 파일로 연습할 수 없습니다",
+            );
+        }
+        let questions = &self.banks[bank].questions;
+        let mut order: Vec<usize> = (0..questions.len())
+            .filter(|&i| match &new.chapters {
+                Some(chapters) => chapters.contains(&questions[i].1),
+                None => true,
+            })
+            .collect();
+        if order.is_empty() {
+            return error(400, "풀 문제가 없습니다");
+        }
+        weighted_shuffle(
+            &mut order,
+            |&i| questions[i].0.frequency(),
+            &mut thread_rng(),
+        );
+        order.truncate(new.count.unwrap_or(order.len()).max(1));
+
+        let generated = questions[order[0]].0.generate();
+        let prompt = asked(questions[order[0]].0.as_ref(), &generated).prompt(&mut thread_rng());
+        self.evict();
+        let token = format!("{:016x}", thread_rng().gen::<u64>());
+        let session = Session {
+            bank,
+            order,
+            position: 0,
+            prompt,
+            generated,
+            correct: 0,
+            points: Score::default(),
+            answers: Vec::new(),
+            started_at: chrono::Local::now(),
+            start: Instant::now(),
+            asked_at: Instant::now(),
+        };
+        let body = json!({ "token": token, "question": self.current(&session) });
+        self.sessions.insert(token, session);
+        reply(201, body)
+    }
+
+    fn evict(&mut self) {
+        self.sessions.retain(|_, session| {
+            let ttl = match session.position == session.order.len() {
+                true => FINISHED_TTL,
+                false => IDLE_TTL,
+            };
+            session.asked_at.elapsed() < ttl
+        });
+        while self.sessions.len() >= MAX_SESSIONS {
+            let Some(oldest) = self
+                .sessions
+                .iter()
+                .min_by_key(|(_, session)| session.asked_at)
+                .map(|(token, _)| token.clone())
+            else {
+                break;
+            };
+            self.sessions.remove(&oldest);
+        }
+    }
+
+    fn current(&self, session: &Session) -> Value {
+        let Some(&index) = session.order.get(session.position) else {
+            return json!({
+                "done": true,
+                "total": session.order.len(),
+                "correct": session.correct,
+                "points": session.points.to_string(),
+                "percent": session.points.percent(),
+                "grade": self.config.grading.grade(&session.points),
+                "passed": self.config.grading.passed(&session.points),
+            });
+        };
+        let (question, chapter) = &self.banks[session.bank].questions[index];
+        describe(
+            asked(question.as_ref(), &session.generated),
+            &session.prompt,
+            *chapter,
+            session.position + 1,
+            session.order.len(),
+        )
+    }
+
+    fn report(&self, session: &Session) -> Value {
+        json!({
+            "bank": self.banks[session.bank].name,
+            "started_at": session.started_at,
+            "done": session.position == session.order.len(),
+            "answered": session.position,
+            "total": session.order.len(),
+            "correct": session.correct,
+            "points": session.points,
+            "percent": session.points.percent(),
+            "grade": self.config.grading.grade(&session.points),
+            "passed": self.config.grading.passed(&session.points),
+            "answers": session.answers,
+        })
+    }
+
+    fn submit(&mut self, token: &str, submission: Submission) -> Reply {
+        let Some(session) = self.sessions.get_mut(token) else {
+            return error(404, "세션을 찾을 수 없습니다");
+        };
+        let Some(&index) = session.order.get(session.position) else {
+            return error(409, "이미 끝난 세션입니다");
+        };
+        let bank = &self.banks[session.bank];
+        let (question, chapter) = &bank.questions[index];
+        let generated = session.generated.take();
+        let question = asked(question.as_ref(), &generated);
+
+        let answer = submission.resolve(question);
+        let weight = weight(question, &bank.points, &self.config.points);
+        let result = match &answer {
+            Some(answer) => question.grade(answer.trim()),
+            None => Score::skipped(),
+        }
+        .weighted(weight);
+
+        session.points += result;
+        if result.is_correct() {
+            session.correct += 1;
+        }
+        session.answers.push(history::Answer {
+            id: question.id(),
+            text: question.text().to_string(),
+            chapter: *chapter,
+            kind: question.kind(),
+            group: question.group().map(str::to_string),
+            correct: result.is_correct(),
+            score: Some(result),
+            time_ms: session.asked_at.elapsed().as_millis() as u64,
+            lifeline: false,
+            options: history::options(question),
+            given: history::given(question, answer.as_slice()),
+            expected: question.correct_answer(),
+        });
+        session.position += 1;
+        session.asked_at = Instant::now();
+        if let Some(&next) = session.order.get(session.position) {
+            let next = bank.questions[next].0.as_ref();
+            session.generated = next.generate();
+            session.prompt = asked(next, &session.generated).prompt(&mut thread_rng());
+        }
+
+        if session.position == session.order.len() {
+            let record = history::Session {
+                bank: bank.name.clone(),
+                started_at: session.started_at,
+                duration_secs: session.start.elapsed().as_secs_f64(),
+                answers: session.answers.clone(),
+            };
+            if let Err(e) = history::append(record) {
+                println!(
+                    "{} {}",
+                    styled(Color::Red).paint("학습 기록을 저장할 수 없습니다:"),
+                    e
+                );
+            }
+        }
+
+        let session = &self.sessions[token];
+        reply(
+            200,
+            json!({
+                "correct": result.is_correct(),
+                "partial": result.is_partial(),
+                "skipped": answer.is_none(),
+                "score": result.to_string(),
+                "correct_answer": question.correct_answer(),
+                "next": self.current(session),
+            }),
+        )
+    }
+}
+
+pub fn describe(
+    question: &dyn Askable,
+    prompt: &str,
+    chapter: u32,
+    number: usize,
+    total: usize,
+) -> Value {
+    let (options, markers) = match question.choices() {
+        Some((options, marker)) => (
+            Some(options),
+            (0..options.len()).map(|i| marker.label(i)).collect(),
+        ),
+        None => (None, Vec::new()),
+    };
+    json!({
+        "done": false,
+        "number": number,
+        "total": total,
+        "chapter": chapter,
+        "kind": question.kind().label(),
+        "text": prompt,
+        "options": options,
+        "markers": markers,
+    })
+}
+
+pub fn header(content_type: &str) -> Header {
+    Header::from_bytes("Content-Type", content_type).unwrap()
+}
+
+fn cors() -> Header {
+    Header::from_bytes("Access-Control-Allow-Origin", "*").unwrap()
+}
+
+pub fn reply(status: u16, body: Value) -> Reply {
+    Response::from_string(body.to_string())
+        .with_status_code(status)
+        .with_header(header("application/json; charset=utf-8"))
+        .with_header(cors())
+}
+
+pub fn error(status: u16, message: &str) -> Reply {
+    reply(status, json!({ "error": message }))
+}